@@ -11,6 +11,10 @@ use std::fmt;
 use std::io;
 use std::ops::Deref;
 
+pub use patch::{patch_html_string, PatchHtmlError};
+
+mod patch;
+
 /// all the possible error when parsing html string
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
@@ -0,0 +1,229 @@
+//! patch an html string with a new node, without the need of a browser DOM
+use crate::{parse_html, ParseError};
+use sauron_core::{
+    html::node_list,
+    prelude::Render,
+    vdom::{diff, Attribute, Element, Node, Patch, PatchType},
+};
+
+/// an error when patching an html string, see [`patch_html_string`]
+#[derive(Debug, thiserror::Error)]
+pub enum PatchHtmlError {
+    /// the old html can not be parsed
+    #[error("{0}")]
+    ParseError(#[from] ParseError),
+    /// the node at the path of a patch can not be found in the parsed html
+    #[error("unable to find the node at path: {0:?}")]
+    MissingNode(Vec<usize>),
+}
+
+/// Parse the `old_html` into a vdom, diff it against the `new_node` and return the html
+/// of the old document with the patches applied.
+///
+/// This is useful for server side rendering which wants to patch a previously served html
+/// incrementally, or to test the reconciliation of the patches without a browser.
+///
+/// Limitations of the parsing of `old_html`:
+///  - only standard html tags and attributes are recognized, non-standard attributes are dropped
+///    and an unknown tag is an error
+///  - comments and doctypes are dropped, so they can not be matched against the `new_node`
+///  - whitespace only text in between elements is kept as text nodes and are diffed as such
+///  - event listeners can not be expressed in html, so they are never part of the result
+///
+/// # Example
+/// ```rust
+/// use sauron_core::html::{attributes::*, *};
+/// use sauron_core::vdom::Node;
+/// use sauron_html_parser::patch_html_string;
+///
+/// let new_node: Node<()> = ul([class("list")], [li([], [text("one")]), li([], [text("two")])]);
+/// let patched = patch_html_string(r#"<ul><li>one</li></ul>"#, &new_node).expect("must patch");
+/// assert_eq!(patched, r#"<ul class="list"><li>one</li><li>two</li></ul>"#);
+/// ```
+pub fn patch_html_string<MSG>(
+    old_html: &str,
+    new_node: &Node<MSG>,
+) -> Result<String, PatchHtmlError>
+where
+    MSG: 'static,
+{
+    let Some(old_node) = parse_html::<MSG>(old_html)? else {
+        return Ok(new_node.render_to_string());
+    };
+    let patches = diff(&old_node, new_node);
+    let mut root = Slot::from_node(None, &old_node);
+    for patch in patches.iter() {
+        root.apply_patch(patch)?;
+    }
+    Ok(root.into_node().render_to_string())
+}
+
+/// A node in the old tree which remembers its index in the old tree.
+/// The patches are all addressed with the paths in the old tree, this allows the patches
+/// to be applied in any order without the paths being invalidated by the previous patches.
+struct Slot<MSG> {
+    /// the index of this node in the old tree, None for the nodes inserted by the patches
+    old_idx: Option<usize>,
+    node: Node<MSG>,
+    children: Vec<Slot<MSG>>,
+}
+
+impl<MSG> Slot<MSG> {
+    /// the node is copied without its children, the children are stored in the slots
+    fn from_node(old_idx: Option<usize>, node: &Node<MSG>) -> Self {
+        let (node, children) = match node {
+            Node::Element(element) => (
+                Node::Element(Element {
+                    namespace: element.namespace,
+                    tag: element.tag,
+                    attrs: element.attrs.clone(),
+                    children: vec![],
+                    self_closing: element.self_closing,
+                }),
+                element.children.as_slice(),
+            ),
+            Node::Fragment(nodes) => (Node::Fragment(vec![]), nodes.as_slice()),
+            Node::NodeList(nodes) => (Node::NodeList(vec![]), nodes.as_slice()),
+            Node::Leaf(leaf) => (Node::Leaf(leaf.clone()), [].as_slice()),
+        };
+        Self {
+            old_idx,
+            node,
+            children: children
+                .iter()
+                .enumerate()
+                .map(|(idx, child)| Self::from_node(Some(idx), child))
+                .collect(),
+        }
+    }
+
+    /// a new node from the patch, which are inserted as is
+    fn new_node(node: &Node<MSG>) -> Self {
+        Self {
+            old_idx: None,
+            node: node.clone(),
+            children: vec![],
+        }
+    }
+
+    /// put back the children to the node
+    fn into_node(self) -> Node<MSG> {
+        let Self {
+            mut node, children, ..
+        } = self;
+        let children = children.into_iter().map(Self::into_node);
+        match &mut node {
+            Node::Element(element) => element.children.extend(children),
+            Node::Fragment(nodes) | Node::NodeList(nodes) => nodes.extend(children),
+            Node::Leaf(_) => (),
+        }
+        node
+    }
+
+    fn find_mut(&mut self, path: &[usize]) -> Option<&mut Self> {
+        match path.split_first() {
+            None => Some(self),
+            Some((idx, rest)) => self
+                .children
+                .iter_mut()
+                .find(|child| child.old_idx == Some(*idx))
+                .and_then(|child| child.find_mut(rest)),
+        }
+    }
+
+    /// find the parent of the node at this path and the current position of the node
+    /// in the children of the parent
+    fn find_parent_mut(&mut self, path: &[usize]) -> Option<(&mut Self, usize)> {
+        let (idx, parent_path) = path.split_last()?;
+        let parent = self.find_mut(parent_path)?;
+        let position = parent
+            .children
+            .iter()
+            .position(|child| child.old_idx == Some(*idx))?;
+        Some((parent, position))
+    }
+
+    /// apply the patch to the node at its path, it is an error when the node can not be found
+    fn apply_patch(&mut self, patch: &Patch<MSG>) -> Result<(), PatchHtmlError> {
+        let path = &patch.patch_path.path;
+        let missing = || PatchHtmlError::MissingNode(path.clone());
+        match &patch.patch_type {
+            PatchType::InsertBeforeNode { nodes } => {
+                let (parent, position) = self.find_parent_mut(path).ok_or_else(missing)?;
+                let nodes = nodes.iter().map(|node| Self::new_node(node));
+                parent.children.splice(position..position, nodes);
+            }
+            PatchType::InsertAfterNode { nodes } => {
+                let (parent, position) = self.find_parent_mut(path).ok_or_else(missing)?;
+                let nodes = nodes.iter().map(|node| Self::new_node(node));
+                parent.children.splice(position + 1..position + 1, nodes);
+            }
+            PatchType::AppendChildren { children } => {
+                let target = self.find_mut(path).ok_or_else(missing)?;
+                target
+                    .children
+                    .extend(children.iter().map(|node| Self::new_node(node)));
+            }
+            PatchType::AddAttributes { attrs } => {
+                if let Node::Element(element) = &mut self.find_mut(path).ok_or_else(missing)?.node {
+                    remove_attributes(&mut element.attrs, attrs);
+                    element.attrs.extend(attrs.iter().map(|att| (*att).clone()));
+                }
+            }
+            PatchType::RemoveAttributes { attrs } => {
+                if let Node::Element(element) = &mut self.find_mut(path).ok_or_else(missing)?.node {
+                    remove_attributes(&mut element.attrs, attrs);
+                }
+            }
+            PatchType::ReplaceNode { replacement } => {
+                if path.is_empty() {
+                    let replacement = replacement.iter().map(|node| (*node).clone());
+                    *self = Self::new_node(&node_list(replacement));
+                } else {
+                    let (parent, position) = self.find_parent_mut(path).ok_or_else(missing)?;
+                    let replacement = replacement.iter().map(|node| Self::new_node(node));
+                    parent.children.splice(position..position + 1, replacement);
+                }
+            }
+            PatchType::RemoveNode => {
+                let (parent, position) = self.find_parent_mut(path).ok_or_else(missing)?;
+                parent.children.remove(position);
+            }
+            PatchType::MoveBeforeNode { nodes_path } => {
+                self.move_nodes(path, nodes_path.iter().map(|p| &p.path[..]), 0)?;
+            }
+            PatchType::MoveAfterNode { nodes_path } => {
+                self.move_nodes(path, nodes_path.iter().map(|p| &p.path[..]), 1)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// move the nodes at `nodes_path` next to the target node,
+    /// `offset` is 0 for moving before the target node and 1 for after the target node
+    fn move_nodes<'p>(
+        &mut self,
+        path: &[usize],
+        nodes_path: impl Iterator<Item = &'p [usize]>,
+        offset: usize,
+    ) -> Result<(), PatchHtmlError> {
+        let moving = nodes_path
+            .map(|node_path| {
+                self.find_parent_mut(node_path)
+                    .map(|(parent, position)| parent.children.remove(position))
+                    .ok_or_else(|| PatchHtmlError::MissingNode(node_path.to_vec()))
+            })
+            .collect::<Result<Vec<Self>, _>>()?;
+        let (parent, position) = self
+            .find_parent_mut(path)
+            .ok_or_else(|| PatchHtmlError::MissingNode(path.to_vec()))?;
+        parent
+            .children
+            .splice(position + offset..position + offset, moving);
+        Ok(())
+    }
+}
+
+fn remove_attributes<MSG>(attrs: &mut Vec<Attribute<MSG>>, to_remove: &[&Attribute<MSG>]) {
+    attrs.retain(|att| to_remove.iter().all(|rm| rm.name() != att.name()));
+}
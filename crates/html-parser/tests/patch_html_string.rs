@@ -0,0 +1,36 @@
+use sauron_core::html::{attributes::*, *};
+use sauron_core::vdom::Node;
+use sauron_html_parser::patch_html_string;
+
+#[test]
+fn patch_small_html_to_match_new_node() {
+    let old_html = r#"<main class="app"><h1>Todos</h1><ul><li>one</li><li>two</li></ul></main>"#;
+    let new_node: Node<()> = main(
+        [class("app"), id("todos")],
+        [
+            h1([], [text("My todos")]),
+            ul([], [li([], [text("one")]), li([], [text("three")])]),
+            footer([], [text("2 items")]),
+        ],
+    );
+    let patched = patch_html_string(old_html, &new_node).expect("must patch");
+    assert_eq!(
+        patched,
+        r#"<main class="app" id="todos"><h1>My todos</h1><ul><li>one</li><li>three</li></ul><footer>2 items</footer></main>"#
+    );
+}
+
+#[test]
+fn patch_removes_nodes_and_attributes() {
+    let old_html = r#"<div class="card" title="old"><p>first</p><p>second</p></div>"#;
+    let new_node: Node<()> = div([class("card")], [p([], [text("first")])]);
+    let patched = patch_html_string(old_html, &new_node).expect("must patch");
+    assert_eq!(patched, r#"<div class="card"><p>first</p></div>"#);
+}
+
+#[test]
+fn patch_empty_html() {
+    let new_node: Node<()> = div([], [text("hello")]);
+    let patched = patch_html_string("", &new_node).expect("must patch");
+    assert_eq!(patched, "<div>hello</div>");
+}
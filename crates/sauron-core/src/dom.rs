@@ -19,26 +19,38 @@ cfg_if! {if #[cfg(feature = "with-dom")] {
     pub use application::{Application, Measurements, PreDiff, diff_if};
     #[cfg(feature = "custom_element")]
     pub use web_component::{register_web_component, WebComponent, WebComponentWrapper};
-    pub use dom_patch::{DomPatch, PatchVariant};
+    pub use dom_node::DetachedFragment;
+    pub use dom_patch::{DomPatch, PatchError, PatchVariant};
     pub use http::Http;
-    pub use program::{MountAction, MountTarget, Program};
+    pub use program::{
+        MountAction, MountTarget, Program, DEFAULT_FRAME_BUDGET,
+        DEFAULT_PRESERVED_ATTRIBUTE_PREFIX,
+    };
     pub use util::{
         document, history, now, performance,
         spawn_local, window, inject_style,
+        show_modal_dialog, close_dialog, play_media, pause_media,
+        stylesheet_loaded, fonts_ready,
+        ScrollTarget, ScrollOptions, ScrollBehavior, ScrollLogicalPosition,
+    };
+    pub use raf::{
+        animation_frames, request_animation_frame, AnimationFrameHandle, AnimationFramesHandle,
     };
-    pub use raf::{request_animation_frame, AnimationFrameHandle};
     pub use ric::{request_idle_callback, IdleCallbackHandle, IdleDeadline};
-    pub use timeout::{delay, request_timeout_callback, TimeoutCallbackHandle};
+    pub use timeout::{delay, request_timeout_callback, set_timeout, TimeoutCallbackHandle};
     pub use cmd::Cmd;
     use crate::dom::events::MountEvent;
-    pub use window::Window;
+    pub use window::{is_document_visible, on_visibility_change, Window, WindowError};
+    pub use before_unload::{on_beforeunload, BeforeUnloadHandle};
 
     mod application;
+    mod before_unload;
     pub mod cmd;
     mod dom_node;
     #[cfg(feature = "custom_element")]
     mod web_component;
     mod dom_patch;
+    mod hydrate;
     pub mod events;
     mod http;
     mod program;
@@ -47,6 +59,7 @@ cfg_if! {if #[cfg(feature = "with-dom")] {
     mod ric;
     mod window;
     mod timeout;
+    mod text_edit;
 
 
     /// Map the Event to DomEvent, which are browser events
@@ -0,0 +1,56 @@
+use crate::dom::{dom_node::intern, window};
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// handle of the `beforeunload` listener, the listener is removed from the window when
+/// this handle is dropped
+pub struct BeforeUnloadHandle {
+    closure: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl Drop for BeforeUnloadHandle {
+    fn drop(&mut self) {
+        window()
+            .remove_event_listener_with_callback(
+                intern("beforeunload"),
+                self.closure.as_ref().unchecked_ref(),
+            )
+            .expect("remove event callback");
+    }
+}
+
+/// register a `beforeunload` listener on the window, ie: to warn the user about unsaved changes
+/// before leaving the page.
+///
+/// The function is called when the page is about to be unloaded, returning a prompt string asks
+/// the browser to confirm the navigation, while returning None lets the page unload.
+/// Most browsers show their own generic message instead of the prompt string, and only when
+/// the user has interacted with the page.
+///
+/// Store the returned handle in the app, the listener is removed when it is dropped.
+/// # Example
+/// ```rust,ignore
+/// fn init(&mut self) -> Cmd<Self, Msg> {
+///     let has_unsaved = Rc::clone(&self.has_unsaved);
+///     self.unload_handle = Some(on_beforeunload(move || {
+///         has_unsaved.get().then(|| "You have unsaved changes".to_string())
+///     }));
+///     Cmd::none()
+/// }
+/// ```
+pub fn on_beforeunload<F>(f: F) -> BeforeUnloadHandle
+where
+    F: Fn() -> Option<String> + 'static,
+{
+    let closure: Closure<dyn FnMut(web_sys::Event)> = Closure::new(move |e: web_sys::Event| {
+        if let Some(prompt) = f() {
+            e.prevent_default();
+            if let Some(before_unload) = e.dyn_ref::<web_sys::BeforeUnloadEvent>() {
+                before_unload.set_return_value(&prompt);
+            }
+        }
+    });
+    window()
+        .add_event_listener_with_callback(intern("beforeunload"), closure.as_ref().unchecked_ref())
+        .expect("add event callback");
+    BeforeUnloadHandle { closure }
+}
@@ -1,8 +1,11 @@
 //! provides functionalities for commands to be executed by the system, such as
 //! when the application starts or after the application updates.
 //!
+use crate::dom;
 use crate::dom::Program;
-use crate::dom::{Application, Effects, Modifier, Task};
+use crate::dom::{Application, Effects, Modifier, ScrollOptions, Task, TimeoutCallbackHandle};
+use std::future::Future;
+use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::spawn_local;
 
 /// Cmd is a command to be executed by the system.
@@ -37,6 +40,145 @@ where
         }
     }
 
+    /// creates a Cmd which executes the function only after the view has been rendered,
+    /// that is the patches are applied into the DOM.
+    /// Use this for effects which reads from the DOM, such as measuring or focusing an element.
+    ///
+    /// Unlike [`Cmd::new`], which may execute before the browser paints the latest view,
+    /// this is scheduled in the next animation frame.
+    pub fn after_render<F>(f: F) -> Self
+    where
+        F: FnOnce(Program<APP, MSG>) + 'static,
+    {
+        Cmd::new(move |program| program.after_render(f))
+    }
+
+    /// creates a Cmd which opens the `<dialog>` with this element id as a modal,
+    /// after the view has been rendered.
+    pub fn show_modal_dialog(element_id: impl ToString) -> Self {
+        let element_id = element_id.to_string();
+        Cmd::after_render(move |_program| {
+            if let Err(e) = dom::show_modal_dialog(&element_id) {
+                log::error!("unable to show the dialog: {:?}", e);
+            }
+        })
+    }
+
+    /// creates a Cmd which closes the `<dialog>` with this element id,
+    /// after the view has been rendered.
+    pub fn close_dialog(element_id: impl ToString) -> Self {
+        let element_id = element_id.to_string();
+        Cmd::after_render(move |_program| {
+            if let Err(e) = dom::close_dialog(&element_id) {
+                log::error!("unable to close the dialog: {:?}", e);
+            }
+        })
+    }
+
+    /// creates a Cmd which starts the playback of the `<audio>` or `<video>` with this element id,
+    /// after the view has been rendered.
+    pub fn play_media(element_id: impl ToString) -> Self {
+        let element_id = element_id.to_string();
+        Cmd::after_render(move |_program| {
+            if let Err(e) = dom::play_media(&element_id) {
+                log::error!("unable to play the media: {:?}", e);
+            }
+        })
+    }
+
+    /// creates a Cmd which pauses the playback of the `<audio>` or `<video>` with this element id,
+    /// after the view has been rendered.
+    pub fn pause_media(element_id: impl ToString) -> Self {
+        let element_id = element_id.to_string();
+        Cmd::after_render(move |_program| {
+            if let Err(e) = dom::pause_media(&element_id) {
+                log::error!("unable to pause the media: {:?}", e);
+            }
+        })
+    }
+
+    /// creates a Cmd which focuses the first element in the view of the app which matches
+    /// the css selector, after the view has been rendered, ie: to focus a newly revealed input.
+    /// A warning is logged when no element matches the selector.
+    /// # Example
+    /// ```rust,ignore
+    /// Cmd::focus("#search-input")
+    /// ```
+    pub fn focus(selector: impl ToString) -> Self {
+        let selector = selector.to_string();
+        Cmd::after_render(move |program| match program.query_selector(&selector) {
+            Ok(Some(element)) => match element.dyn_ref::<web_sys::HtmlElement>() {
+                Some(element) => {
+                    if let Err(e) = element.focus() {
+                        log::error!("unable to focus `{selector}`: {:?}", e);
+                    }
+                }
+                None => log::warn!("`{selector}` is not a html element which can be focused"),
+            },
+            Ok(None) => log::warn!("no element to focus matches the selector: {selector}"),
+            Err(e) => log::error!("invalid selector `{selector}`: {:?}", e),
+        })
+    }
+
+    /// creates a Cmd which scrolls the first element which matches the css selector into view,
+    /// after the view has been rendered. See [`Program::scroll_into_view`].
+    pub fn scroll_into_view(selector: impl ToString, options: ScrollOptions) -> Self {
+        let selector = selector.to_string();
+        Cmd::after_render(move |program| {
+            if let Err(e) = program.scroll_into_view(selector, options) {
+                log::error!("unable to scroll into view: {:?}", e);
+            }
+        })
+    }
+
+    /// creates a Cmd which dispatches the `msg` after `timeout` milliseconds.
+    /// # Example
+    /// ```rust,ignore
+    /// Cmd::delay(2000, Msg::ToggleShow)
+    /// ```
+    pub fn delay(timeout: i32, msg: MSG) -> Self {
+        Cmd::from(Task::delayed(timeout, msg))
+    }
+
+    /// the same as [`Cmd::delay`], but also returns a handle which cancels the dispatch of
+    /// the `msg` when it is dropped before the `timeout` has elapsed.
+    /// Store the handle in the app, and drop or replace it to cancel.
+    pub fn delay_cancellable(timeout: i32, msg: MSG) -> (Self, TimeoutCallbackHandle) {
+        let (task, handle) = Task::delayed_cancellable(timeout, msg);
+        (Cmd::from(task), handle)
+    }
+
+    /// creates a Cmd from a future which results to either a msg or an error.
+    /// The msg is dispatched to the app, while the error is mapped into a msg by the error
+    /// handler of the program for its type, see [`Program::with_error_handler`].
+    /// # Example
+    /// ```rust,ignore
+    /// Cmd::try_single(async move {
+    ///     let user = fetch_user(id).await?;
+    ///     Ok(Msg::UserLoaded(user))
+    /// })
+    /// ```
+    pub fn try_single<F, E>(f: F) -> Self
+    where
+        F: Future<Output = Result<MSG, E>> + 'static,
+        E: 'static,
+    {
+        Cmd::new(move |program| {
+            // the same as a task, this doesn't keep the program alive
+            let program = program.downgrade();
+            spawn_local(async move {
+                let result = f.await;
+                let Some(mut program) = program.upgrade() else {
+                    return;
+                };
+                match result {
+                    Ok(msg) => program.dispatch(msg),
+                    Err(error) => program.handle_error(error),
+                }
+            });
+        })
+    }
+
     /// When you need the runtime to perform couple of commands, you can batch
     /// then together.
     pub fn batch(cmds: impl IntoIterator<Item = Self>) -> Self {
@@ -153,9 +295,15 @@ where
     APP: Application<MSG>,
 {
     fn from(mut task: Task<MSG>) -> Self {
-        Cmd::new(move |mut program| {
+        Cmd::new(move |program| {
+            // the task doesn't keep the program alive, the task is stopped
+            // once the program is dropped
+            let program = program.downgrade();
             spawn_local(async move {
                 while let Some(msg) = task.next().await {
+                    let Some(mut program) = program.upgrade() else {
+                        break;
+                    };
                     program.dispatch(msg)
                 }
             });
@@ -1,28 +1,35 @@
 use crate::{
     dom::events::MountEvent,
-    dom::{self, Application, Program},
+    dom::{self, program::ActiveClosure, program::NodeIds, Application, Program},
     dom::{document, window},
     html,
-    html::attributes::{AttributeValue, Listener, SegregatedAttributes},
+    html::attributes::{AttributeValue, Listener, ListenerOptions, SegregatedAttributes},
+    render::DATA_VDOM_ID,
     vdom,
     vdom::{Attribute, Leaf, NodeTrait},
 };
 use js_sys::Function;
 use mt_dom::TreePath;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
-use std::{cell::Cell, collections::BTreeMap};
 use wasm_bindgen::{closure::Closure, JsCast, JsValue};
 use web_sys::{
-    self, Element, HtmlButtonElement, HtmlDataElement, HtmlDetailsElement, HtmlFieldSetElement,
-    HtmlInputElement, HtmlLiElement, HtmlLinkElement, HtmlMeterElement, HtmlOptGroupElement,
-    HtmlOptionElement, HtmlOutputElement, HtmlParamElement, HtmlProgressElement, HtmlSelectElement,
-    HtmlStyleElement, HtmlTextAreaElement, Node, Text,
+    self, Element, HtmlButtonElement, HtmlDataElement, HtmlDetailsElement, HtmlDialogElement,
+    HtmlFieldSetElement, HtmlInputElement, HtmlLiElement, HtmlLinkElement, HtmlMeterElement,
+    HtmlOptGroupElement, HtmlOptionElement, HtmlOutputElement, HtmlParamElement,
+    HtmlProgressElement, HtmlSelectElement, HtmlStyleElement, HtmlTemplateElement,
+    HtmlTextAreaElement, Node, Text,
 };
 
-/// data attribute name used in assigning the node id of an element with events
-pub(crate) const DATA_VDOM_ID: &str = "data-vdom-id";
+/// the attribute which opts in the element to merge the classes instead of overwriting them
+const MERGE_CLASS: &str = "merge_class";
 
-thread_local!(static NODE_ID_COUNTER: Cell<usize> = Cell::new(1));
+/// the property of the element which holds the classes set by the view
+const OWNED_CLASSES: &str = "__sauron_owned_classes";
+
+/// the property of the element which holds the number of the program that assigned its
+/// `data-vdom-id`, see [`NodeIds`]
+const VDOM_ID_OWNER: &str = "__sauron_vdom_id_owner";
 
 // a cache of commonly used elements, so we can clone them.
 // cloning is much faster then creating the element
@@ -63,19 +70,6 @@ fn create_element(tag: &'static str) -> web_sys::Element {
     })
 }
 
-/// This is the value of the data-sauron-vdom-id.
-/// Used to uniquely identify elements that contain closures so that the DomUpdater can
-/// look them up by their unique id.
-/// When the DomUpdater sees that the element no longer exists it will drop all of it's
-/// Rc'd Closures for those events.
-fn create_unique_identifier() -> usize {
-    NODE_ID_COUNTER.with(|x| {
-        let tmp = x.get();
-        x.set(tmp + 1);
-        tmp
-    })
-}
-
 /// A node along with all of the closures that were created for that
 /// node's events and all of it's child node's events.
 impl<APP, MSG> Program<APP, MSG>
@@ -88,13 +82,27 @@ where
         document().create_text_node(txt)
     }
 
-    fn create_document_fragment(&self, nodes: &[vdom::Node<MSG>]) -> Node {
-        let doc_fragment = document().create_document_fragment();
-        for vnode in nodes {
-            let created_node = self.create_dom_node(vnode);
-            Self::append_child_and_dispatch_mount_event(&doc_fragment, &created_node)
+    /// create the DOM nodes of this virtual node into a `DocumentFragment` which is detached
+    /// from the live DOM, such that a subtree can be prepared offscreen and inserted later.
+    ///
+    /// The closures of the event listeners are not managed by the program, they are returned
+    /// along with the fragment and must be kept alive as long as the nodes are in use.
+    pub fn create_detached_fragment(&self, vnode: &vdom::Node<MSG>) -> DetachedFragment {
+        let fragment = document().create_document_fragment();
+        let created_node = self.create_dom_node(vnode);
+        Self::append_child_and_dispatch_mount_event(&fragment, &created_node);
+
+        let mut closures = ActiveClosure::new();
+        let children = fragment.children();
+        for i in 0..children.length() {
+            let element = children.item(i).expect("must have a child element");
+            for vdom_id in get_node_descendant_data_vdom_id(&element, &self.app_context.node_ids) {
+                if let Some(listeners) = self.node_closures.borrow_mut().remove(&vdom_id) {
+                    closures.insert(vdom_id, listeners);
+                }
+            }
         }
-        doc_fragment.into()
+        DetachedFragment { fragment, closures }
     }
 
     fn create_leaf_node(&self, leaf: &Leaf) -> Node {
@@ -116,30 +124,48 @@ where
     /// Create and return a `CreatedNode` instance (containing a DOM `Node`
     /// together with potentially related closures) for this virtual node.
     pub fn create_dom_node(&self, vnode: &vdom::Node<MSG>) -> Node {
-        match vnode {
-            vdom::Node::Leaf(leaf_node) => self.create_leaf_node(leaf_node),
-            vdom::Node::Element(element_node) => {
-                let created_node = self.create_element_node(element_node);
-                for child in element_node.children().iter() {
-                    if let Some(child_text) = child.as_safe_html() {
-                        // https://developer.mozilla.org/en-US/docs/Web/API/Element/insertAdjacentHTML
-                        let created_element: &Element = created_node.unchecked_ref();
-                        created_element
-                            .insert_adjacent_html(intern("beforeend"), child_text)
-                            .expect("must not error");
-                    } else {
-                        let created_child = self.create_dom_node(child);
-
-                        Self::append_child_and_dispatch_mount_event(&created_node, &created_child);
-                    }
+        let created_node = self.create_node_without_children(vnode);
+        // an explicit stack of the created nodes along with their virtual children which are
+        // not created yet, instead of recursion, so a very deep tree doesn't overflow the stack.
+        // The children are appended to their parent once all of their descendants are created.
+        let mut stack = vec![(created_node, vdom::child_nodes(vnode).iter())];
+        loop {
+            let Some((created_node, vchildren)) = stack.last_mut() else {
+                unreachable!("the created node is returned before the stack is emptied");
+            };
+            if let Some(vchild) = vchildren.next() {
+                if let Some(child_html) = vchild.as_safe_html() {
+                    append_safe_html(&children_container(created_node), child_html);
+                } else {
+                    let created_child = self.create_node_without_children(vchild);
+                    stack.push((created_child, vdom::child_nodes(vchild).iter()));
                 }
-                created_node
+            } else {
+                let (created_node, _) = stack.pop().expect("must have the created node");
+                let Some((parent_node, _)) = stack.last() else {
+                    return created_node;
+                };
+                Self::append_child_and_dispatch_mount_event(
+                    &children_container(parent_node),
+                    &created_node,
+                );
             }
-            vdom::Node::Fragment(nodes) => self.create_document_fragment(nodes),
+        }
+    }
+
+    /// create the DOM node of this virtual node, the children are created by the caller.
+    /// Fragments and node lists are created as `DocumentFragment`s, their children are moved
+    /// into the parent when the fragment is appended.
+    fn create_node_without_children(&self, vnode: &vdom::Node<MSG>) -> Node {
+        match vnode {
+            vdom::Node::Leaf(leaf_node) => self.create_leaf_node(leaf_node),
+            vdom::Node::Element(element_node) => self.create_element_node(element_node),
             // NodeList that goes here is only possible when it is the root_node,
             // since node_list as children will be unrolled into as child_elements of the parent
             // We need to wrap this node_list into doc_fragment since root_node is only 1 element
-            vdom::Node::NodeList(node_list) => self.create_document_fragment(node_list),
+            vdom::Node::Fragment(_) | vdom::Node::NodeList(_) => {
+                document().create_document_fragment().into()
+            }
         }
     }
 
@@ -150,8 +176,7 @@ where
         !existing.is_undefined()
     }
 
-    /// Build a DOM element by recursively creating DOM nodes for this element and it's
-    /// children, it's children's children, etc.
+    /// Build a DOM element with its attributes and event listeners, without its children
     fn create_element_node(&self, velem: &vdom::Element<MSG>) -> Node {
         let document = document();
 
@@ -217,6 +242,14 @@ where
     /// the same call, but on a subsequent call to each other. Using the if-else-if here for
     /// attributes, style, function_call.
     pub fn set_element_attribute(&self, element: &Element, attr: &Attribute<MSG>) {
+        if *attr.name() == "style" {
+            if let Some(merged_styles) = html::attributes::merge_style_declarations(attr) {
+                element
+                    .set_attribute(intern(attr.name()), &merged_styles)
+                    .unwrap_or_else(|_| panic!("Error setting an attribute for {element:?}"));
+                return;
+            }
+        }
         let SegregatedAttributes {
             listeners,
             plain_values,
@@ -226,7 +259,10 @@ where
 
         // set simple values
         if let Some(merged_plain_values) =
-            html::attributes::merge_plain_attributes_values(&plain_values)
+            html::attributes::merge_plain_attributes_values_with_separator(
+                &plain_values,
+                html::attributes::value_separator(attr.name()),
+            )
         {
             if let Some(namespace) = attr.namespace() {
                 // Warning NOTE: set_attribute_ns should only be called
@@ -278,6 +314,19 @@ where
                             });
                         Self::set_checked(element, is_checked)
                     }
+                    "class" if Self::is_merging_class(element) => {
+                        Self::merge_class_list(element, &merged_plain_values)
+                    }
+                    MERGE_CLASS => {
+                        element
+                            .set_attribute(intern(attr_name), &merged_plain_values)
+                            .unwrap_or_else(|_| {
+                                panic!("Error setting an attribute for {element:?}")
+                            });
+                        // the classes which are already set are from the view
+                        let classes = element.get_attribute(intern("class")).unwrap_or_default();
+                        Self::set_owned_classes(element, &classes);
+                    }
                     "disabled" => {
                         let is_disabled: bool = plain_values
                             .first()
@@ -307,6 +356,8 @@ where
             element
                 .set_attribute(intern(attr.name()), &merged_styles)
                 .unwrap_or_else(|_| panic!("Error setting an attribute_ns for {element:?}"));
+        } else if *attr.name() == "class" && Self::is_merging_class(element) {
+            Self::merge_class_list(element, "");
         } else {
             //if the merged attribute is blank of empty when string is trimmed
             //remove the attribute
@@ -324,30 +375,97 @@ where
             }
         }
 
-        let listener_closures: BTreeMap<&'static str, Closure<dyn FnMut(web_sys::Event)>> =
-            BTreeMap::from_iter(listeners.iter().map(|listener| {
-                let event_name = attr.name();
-                let closure = self
-                    .add_event_listener(element, event_name, listener)
-                    .expect("add listener");
-
-                (*event_name, closure)
-            }));
+        // set the javascript properties directly on the element object
+        for property in attr.value().iter().filter_map(|v| v.as_property()) {
+            js_sys::Reflect::set(element, &JsValue::from_str(attr.name()), property)
+                .unwrap_or_else(|_| panic!("Error setting a property for {element:?}"));
+        }
 
         if !listeners.is_empty() {
-            let unique_id = create_unique_identifier();
+            let event_name = attr.name();
+            // multiple listeners of the same event are all attached, and fires in the order they
+            // are declared in the view
+            let listener_closures: Vec<ListenerClosure> = listeners
+                .iter()
+                .map(|listener| ListenerClosure {
+                    closure: self
+                        .add_event_listener(element, event_name, listener)
+                        .expect("add listener"),
+                    capture: listener.options().capture,
+                })
+                .collect();
+
+            self.store_node_closures(element, *event_name, listener_closures);
+        }
+    }
+
+    /// store the closures of the event listeners attached to this element, so they are kept
+    /// alive and are dropped when the element is removed
+    fn store_node_closures(
+        &self,
+        element: &Element,
+        event_name: &'static str,
+        closures: Vec<ListenerClosure>,
+    ) {
+        // reuse the data-vdom-id if the element already has listeners of the other events,
+        // otherwise the closures of those events can not be found anymore when removing.
+        // The data-vdom-id of the server rendered elements are adopted when hydrating.
+        let node_ids = &self.app_context.node_ids;
+        let unique_id = own_vdom_id(element, node_ids).unwrap_or_else(|| {
+            let unique_id = node_ids.create();
             // set the data-sauron_vdom-id this will be read later on
             // when it's time to remove this element and its closures and event listeners
             element
                 .set_attribute(intern(DATA_VDOM_ID), &unique_id.to_string())
                 .expect("Could not set attribute on element");
+            set_vdom_id_owner(element, node_ids);
+            unique_id
+        });
+
+        self.node_closures
+            .borrow_mut()
+            .entry(unique_id)
+            .or_default()
+            .entry(event_name)
+            .or_default()
+            .extend(closures);
+    }
 
-            self.node_closures
-                .borrow_mut()
-                .insert(unique_id, listener_closures);
+    /// adopt the `data-vdom-id` of a server rendered element when hydrating, so the closures of
+    /// its listeners are stored with the same id the server emitted.
+    /// The ids of the server rendered html must have been reserved beforehand.
+    pub(crate) fn adopt_vdom_id(&self, element: &Element) {
+        if let Some(vdom_id) = element
+            .get_attribute(intern(DATA_VDOM_ID))
+            .and_then(|vdom_id| vdom_id.parse::<usize>().ok())
+        {
+            let node_ids = &self.app_context.node_ids;
+            node_ids.reserve(vdom_id);
+            set_vdom_id_owner(element, node_ids);
         }
     }
 
+    /// add an event listener to a live element, ie: from an integration with other libraries.
+    /// Unlike attaching the listener manually, the closure is managed by the program and is
+    /// dropped when the element is removed from the DOM by a patch.
+    pub fn add_managed_event_listener<F>(
+        &self,
+        element: &Element,
+        event_name: &'static str,
+        f: F,
+    ) -> Result<(), JsValue>
+    where
+        F: Fn(dom::Event) -> MSG + 'static,
+    {
+        let closure = self.add_event_listener(element, event_name, &Listener::from(f))?;
+        let listener_closure = ListenerClosure {
+            closure,
+            capture: false,
+        };
+        self.store_node_closures(element, event_name, vec![listener_closure]);
+        Ok(())
+    }
+
     /// attach and event listener to an event target
     pub fn add_event_listeners(
         &self,
@@ -357,8 +475,15 @@ where
         for event_attr in event_listeners.into_iter() {
             for event_cb in event_attr.value() {
                 let listener = event_cb.as_event_listener().expect("expecting a callback");
-                let closure = self.add_event_listener(target, event_attr.name(), listener)?;
-                self.event_closures.borrow_mut().push(closure);
+                let closure = ListenerClosure {
+                    closure: self.add_event_listener(target, event_attr.name(), listener)?,
+                    capture: listener.options().capture,
+                };
+                self.event_closures.borrow_mut().push((
+                    target.clone(),
+                    *event_attr.name(),
+                    closure,
+                ));
             }
         }
         Ok(())
@@ -372,19 +497,33 @@ where
         listener: &Listener<dom::Event, MSG>,
     ) -> Result<Closure<dyn FnMut(web_sys::Event)>, JsValue> {
         let program = Program::downgrade(&self);
+        let options = listener.options();
         let listener = listener.clone();
 
         let closure: Closure<dyn FnMut(web_sys::Event)> =
             Closure::new(move |event: web_sys::Event| {
-                let msg = listener.emit(dom::Event::from(event));
-                let mut program = program.upgrade().expect("must upgrade");
-                program.dispatch(msg);
+                if let Some(msg) = listener.emit_maybe(dom::Event::from(event)) {
+                    let mut program = program.upgrade().expect("must upgrade");
+                    program.dispatch(msg);
+                }
             });
 
-        event_target.add_event_listener_with_callback(
-            intern(event_name),
-            closure.as_ref().unchecked_ref(),
-        )?;
+        if options == ListenerOptions::default() {
+            event_target.add_event_listener_with_callback(
+                intern(event_name),
+                closure.as_ref().unchecked_ref(),
+            )?;
+        } else {
+            let listener_options = web_sys::AddEventListenerOptions::new();
+            listener_options.set_passive(options.passive);
+            listener_options.set_capture(options.capture);
+            listener_options.set_once(options.once);
+            event_target.add_event_listener_with_callback_and_add_event_listener_options(
+                intern(event_name),
+                closure.as_ref().unchecked_ref(),
+                &listener_options,
+            )?;
+        }
         Ok(closure)
     }
 
@@ -400,17 +539,17 @@ where
         }
     }
 
-    /// explicitly call set_open for details
+    /// explicitly call set_open for details and dialog
     /// since setting the attribute `open` to false will not close it.
     ///
-    /// TODO: HtmlDialogElement ( but it is not supported on firefox and in safari, only works on chrome)
-    ///
     /// Applies to:
     ///  - dialog
     ///  - details
     fn set_open(element: &Element, is_open: bool) {
         if let Some(details) = element.dyn_ref::<HtmlDetailsElement>() {
             details.set_open(is_open);
+        } else if let Some(dialog) = element.dyn_ref::<HtmlDialogElement>() {
+            dialog.set_open(is_open);
         }
     }
 
@@ -472,6 +611,10 @@ where
             elm.set_value(value);
         } else if let Some(elm) = element.dyn_ref::<HtmlParamElement>() {
             elm.set_value(value);
+        } else if let Ok(value) = value.trim().parse::<f64>() {
+            // the value of progress and meter is a number, which may be set as a string,
+            // ie: from the `node!` macro or the parsed html
+            Self::set_value_f64(element, value);
         }
     }
 
@@ -509,6 +652,11 @@ where
 
     /// remove element attribute,
     /// takes care of special case such as checked
+    ///
+    /// Removing the attribute alone doesn't reset the state of the element,
+    /// since the displayed state lives in the element property, ie: an input which
+    /// the user has typed into will still display the typed value.
+    /// Therefore the properties `value`, `open`, `checked` and `disabled` are explicitly reset.
     pub fn remove_element_attribute(
         element: &Element,
         attr: &Attribute<MSG>,
@@ -526,6 +674,11 @@ where
             "disabled" => {
                 Self::set_disabled(element, false);
             }
+            "class" if Self::is_merging_class(element) => {
+                // only remove the classes set by the view, the external classes are kept
+                Self::merge_class_list(element, "");
+                return Ok(());
+            }
             _ => (),
         }
         //actually remove the element
@@ -534,15 +687,153 @@ where
         Ok(())
     }
 
+    /// the element opted in to merge the class list with [`html::attributes::merge_class`]
+    fn is_merging_class(element: &Element) -> bool {
+        element.get_attribute(intern(MERGE_CLASS)).as_deref() == Some("true")
+    }
+
+    /// the classes set by the view in the previous update
+    fn owned_classes(element: &Element) -> String {
+        js_sys::Reflect::get(element, &JsValue::from_str(OWNED_CLASSES))
+            .ok()
+            .and_then(|classes| classes.as_string())
+            .unwrap_or_default()
+    }
+
+    fn set_owned_classes(element: &Element, classes: &str) {
+        js_sys::Reflect::set(
+            element,
+            &JsValue::from_str(OWNED_CLASSES),
+            &JsValue::from_str(classes),
+        )
+        .expect("must set the owned classes");
+    }
+
+    /// remove the classes which was previously set by the view but is no longer in `classes`,
+    /// then add the `classes`, the classes added outside of the view are left untouched
+    fn merge_class_list(element: &Element, classes: &str) {
+        let class_list = element.class_list();
+        let old_classes = Self::owned_classes(element);
+        for old_class in old_classes.split_whitespace() {
+            if !classes.split_whitespace().any(|class| class == old_class) {
+                class_list.remove_1(old_class).expect("must remove class");
+            }
+        }
+        for class in classes.split_whitespace() {
+            class_list.add_1(class).expect("must add class");
+        }
+        Self::set_owned_classes(element, classes);
+    }
+
+    /// unset the javascript property of this attribute from the element object
+    pub(crate) fn remove_element_property(
+        element: &Element,
+        attr: &Attribute<MSG>,
+    ) -> Result<(), JsValue> {
+        js_sys::Reflect::set(
+            element,
+            &JsValue::from_str(attr.name()),
+            &JsValue::UNDEFINED,
+        )?;
+        Ok(())
+    }
+
+    /// replace the event listeners attached to the DOM with the listeners of this vdom.
+    ///
+    /// The diff considers listeners of the same type as equal, so the DOM would otherwise
+    /// still hold the closures which are created from a previous vdom.
+    /// `node` is the DOM node created from `vnode`, for fragments it is the node which
+    /// contains the unrolled children.
+    pub(crate) fn rewire_event_listeners(
+        &self,
+        vnode: &vdom::Node<MSG>,
+        node: &Node,
+    ) -> Result<(), JsValue> {
+        let vchildren = match vnode {
+            vdom::Node::Element(velem) => {
+                let element: &Element = node.unchecked_ref();
+                let attrs = mt_dom::merge_attributes_of_same_name(
+                    &velem.attributes().iter().collect::<Vec<_>>(),
+                );
+                for attr in attrs.iter() {
+                    if attr.value().iter().any(|v| v.as_event_listener().is_some()) {
+                        self.remove_own_event_listeners_with_name(attr.name(), element)?;
+                        for listener in attr
+                            .value()
+                            .iter()
+                            .filter(|v| v.as_event_listener().is_some())
+                        {
+                            let listener_attr = mt_dom::attr(*attr.name(), (*listener).clone());
+                            self.set_element_attribute(element, &listener_attr);
+                        }
+                    }
+                }
+                velem.children()
+            }
+            vdom::Node::Fragment(nodes) | vdom::Node::NodeList(nodes) => nodes.as_slice(),
+            vdom::Node::Leaf(_) => return Ok(()),
+        };
+        let vchildren = unroll_fragments(vchildren);
+        // safe html can be parsed into any number of DOM nodes, so the
+        // children can not be matched by their index anymore
+        if vchildren.iter().any(|child| child.is_safe_html()) {
+            return Ok(());
+        }
+        let children = children_container(node).child_nodes();
+        for (i, vchild) in vchildren.into_iter().enumerate() {
+            if let Some(child) = children.item(i as u32) {
+                self.rewire_event_listeners(vchild, &child)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// remove the listeners of this event which are attached to this element only,
+    /// the listeners of the descendants are not touched
+    fn remove_own_event_listeners_with_name(
+        &self,
+        event_name: &'static str,
+        element: &Element,
+    ) -> Result<(), JsValue> {
+        if let Some(vdom_id) = own_vdom_id(element, &self.app_context.node_ids) {
+            let mut node_closures = self.node_closures.borrow_mut();
+            if let Some(old_closure) = node_closures.get_mut(&vdom_id) {
+                if let Some(closures) = old_closure.remove(event_name) {
+                    for oc in closures {
+                        oc.remove_from(element, event_name)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// remove all the event listeners which are attached to this element only,
+    /// the listeners of the descendants are not touched
+    pub(crate) fn remove_own_event_listeners(&self, element: &Element) -> Result<(), JsValue> {
+        if let Some(vdom_id) = own_vdom_id(element, &self.app_context.node_ids) {
+            if let Some(old_closure) = self.node_closures.borrow_mut().remove(&vdom_id) {
+                for (event, closures) in old_closure.iter() {
+                    for oc in closures {
+                        oc.remove_from(element, event)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// remove all the event listeners for this node
     pub(crate) fn remove_event_listeners(&self, node: &Element) -> Result<(), JsValue> {
-        let all_descendant_vdom_id = get_node_descendant_data_vdom_id(node);
+        let all_descendant_vdom_id =
+            get_node_descendant_data_vdom_id(node, &self.app_context.node_ids);
         let mut node_closures = self.node_closures.borrow_mut();
         for vdom_id in all_descendant_vdom_id {
             if let Some(old_closure) = node_closures.get(&vdom_id) {
-                for (event, oc) in old_closure.iter() {
-                    let func: &Function = oc.as_ref().unchecked_ref();
-                    node.remove_event_listener_with_callback(intern(event), func)?;
+                for (event, closures) in old_closure.iter() {
+                    for oc in closures {
+                        oc.remove_from(node, event)?;
+                    }
                 }
                 // remove closure active_closure in dom_updater to free up memory
                 node_closures
@@ -564,19 +855,17 @@ where
         event_name: &'static str,
         node: &Element,
     ) -> Result<(), JsValue> {
-        let all_descendant_vdom_id = get_node_descendant_data_vdom_id(node);
+        let all_descendant_vdom_id =
+            get_node_descendant_data_vdom_id(node, &self.app_context.node_ids);
         let mut node_closures = self.node_closures.borrow_mut();
         for vdom_id in all_descendant_vdom_id {
             if let Some(old_closure) = node_closures.get_mut(&vdom_id) {
-                for (event, oc) in old_closure.iter() {
-                    if *event == event_name {
-                        let func: &Function = oc.as_ref().unchecked_ref();
-                        node.remove_event_listener_with_callback(intern(event), func)?;
+                if let Some(closures) = old_closure.remove(event_name) {
+                    for oc in closures {
+                        oc.remove_from(node, event_name)?;
                     }
                 }
 
-                old_closure.retain(|event, _oc| *event != event_name);
-
                 // remove closure active_closure in dom_updater to free up memory
                 if old_closure.is_empty() {
                     node_closures
@@ -591,18 +880,139 @@ where
     }
 }
 
-pub(crate) fn find_node(node: &Node, path: &mut TreePath) -> Option<Node> {
-    if path.is_empty() {
-        Some(node.clone())
+/// DOM nodes which are created off the live DOM, see [`Program::create_detached_fragment`]
+pub struct DetachedFragment {
+    /// the fragment containing the created nodes,
+    /// which can be inserted anywhere in the document
+    pub fragment: web_sys::DocumentFragment,
+    /// the closures of the event listeners attached to the created nodes,
+    /// the listeners will stop working when these are dropped
+    pub closures: ActiveClosure,
+}
+
+/// the closure of an event listener attached to an element
+pub struct ListenerClosure {
+    pub(crate) closure: Closure<dyn FnMut(web_sys::Event)>,
+    /// the listener has to be removed with the same `capture` option it is attached with
+    pub(crate) capture: bool,
+}
+
+impl ListenerClosure {
+    /// remove this listener from the event target
+    pub(crate) fn remove_from(
+        &self,
+        target: &web_sys::EventTarget,
+        event_name: &str,
+    ) -> Result<(), JsValue> {
+        let func: &Function = self.closure.as_ref().unchecked_ref();
+        target.remove_event_listener_with_callback_and_bool(intern(event_name), func, self.capture)
+    }
+}
+
+/// detach the listeners of the `node_closures` from this node and all of its descendants
+pub(crate) fn detach_node_closures(node: &Node, node_closures: &ActiveClosure, node_ids: &NodeIds) {
+    let selector = format!("[{DATA_VDOM_ID}]");
+    let mut elements: Vec<Element> = vec![];
+    let descendants = if let Some(element) = node.dyn_ref::<Element>() {
+        elements.push(element.clone());
+        element.query_selector_all(&selector)
+    } else if let Some(fragment) = node.dyn_ref::<web_sys::DocumentFragment>() {
+        fragment.query_selector_all(&selector)
     } else {
-        let idx = path.remove_first();
-        let children = node.child_nodes();
-        if let Some(child) = children.item(idx as u32) {
-            find_node(&child, path)
+        return;
+    };
+    if let Ok(descendants) = descendants {
+        elements.extend(
+            (0..descendants.length())
+                .filter_map(|i| descendants.item(i))
+                .map(|node| node.unchecked_into()),
+        );
+    }
+    for element in elements {
+        let listeners =
+            own_vdom_id(&element, node_ids).and_then(|vdom_id| node_closures.get(&vdom_id));
+        for (event_name, closures) in listeners.into_iter().flatten() {
+            for closure in closures {
+                if let Err(e) = closure.remove_from(&element, event_name) {
+                    log::error!("unable to remove the event listener: {e:?}");
+                }
+            }
+        }
+    }
+}
+
+/// the children of the fragments and node lists are unrolled into the children of their parent
+pub(crate) fn unroll_fragments<MSG>(nodes: &[vdom::Node<MSG>]) -> Vec<&vdom::Node<MSG>> {
+    let mut unrolled = vec![];
+    for node in nodes {
+        match node {
+            vdom::Node::Fragment(nodes) | vdom::Node::NodeList(nodes) => {
+                unrolled.extend(unroll_fragments(nodes))
+            }
+            _ => unrolled.push(node),
+        }
+    }
+    unrolled
+}
+
+/// return the node which holds the children of this node.
+/// The children of a `<template>` element are in its content document fragment instead.
+pub(crate) fn children_container(node: &Node) -> Node {
+    if let Some(template) = node.dyn_ref::<HtmlTemplateElement>() {
+        template.content().into()
+    } else {
+        node.clone()
+    }
+}
+
+/// parse the safe html and append the parsed nodes to this node.
+/// The children of a `<template>` go into its content, which is a document fragment.
+pub(crate) fn append_safe_html(container: &Node, html: &str) {
+    if let Some(element) = container.dyn_ref::<Element>() {
+        // https://developer.mozilla.org/en-US/docs/Web/API/Element/insertAdjacentHTML
+        element
+            .insert_adjacent_html(intern("beforeend"), html)
+            .expect("must not error");
+    } else {
+        let template: HtmlTemplateElement = create_element("template").unchecked_into();
+        template.set_inner_html(html);
+        container
+            .append_child(&template.content())
+            .expect("must append the safe html");
+    }
+}
+
+/// the elements with `autofocus`, which is rendered as `autofocus="false"` when it is false
+const AUTOFOCUS_SELECTOR: &str = r#"[autofocus]:not([autofocus="false"])"#;
+
+/// find the first element with `autofocus` in this node, including itself.
+///
+/// The browser only focuses the `autofocus` element which is in the document when the page
+/// loads, so the dynamically created elements must be focused explicitly.
+pub(crate) fn find_autofocus(node: &Node) -> Option<web_sys::HtmlElement> {
+    let found = if let Some(element) = node.dyn_ref::<Element>() {
+        if element.matches(AUTOFOCUS_SELECTOR).unwrap_or(false) {
+            Some(element.clone())
         } else {
-            None
+            element.query_selector(AUTOFOCUS_SELECTOR).ok().flatten()
         }
+    } else if let Some(fragment) = node.dyn_ref::<web_sys::DocumentFragment>() {
+        fragment.query_selector(AUTOFOCUS_SELECTOR).ok().flatten()
+    } else {
+        None
+    };
+    found.and_then(|element| element.dyn_into().ok())
+}
+
+/// find the node at this path, this is a loop instead of recursion
+/// so a very deep path doesn't overflow the stack
+pub(crate) fn find_node(node: &Node, path: &mut TreePath) -> Option<Node> {
+    let mut node = node.clone();
+    while !path.is_empty() {
+        let idx = path.remove_first();
+        node = children_container(&node).child_nodes().item(idx as u32)?;
     }
+    Some(node)
 }
 
 pub(crate) fn find_all_nodes(
@@ -621,26 +1031,47 @@ pub(crate) fn find_all_nodes(
     nodes_to_patch
 }
 
-/// Get the "data-vdom-id" of all the desendent of this node including itself
+/// the `data-vdom-id` of this element, when it is assigned by the program of these `node_ids`
+fn own_vdom_id(element: &Element, node_ids: &NodeIds) -> Option<usize> {
+    let owner = js_sys::Reflect::get(element, &JsValue::from_str(VDOM_ID_OWNER)).ok()?;
+    if owner.as_f64() == Some(node_ids.program_id as f64) {
+        element
+            .get_attribute(intern(DATA_VDOM_ID))
+            .and_then(|vdom_id| vdom_id.parse::<usize>().ok())
+    } else {
+        None
+    }
+}
+
+/// mark the `data-vdom-id` of this element as assigned by the program of these `node_ids`
+fn set_vdom_id_owner(element: &Element, node_ids: &NodeIds) {
+    js_sys::Reflect::set(
+        element,
+        &JsValue::from_str(VDOM_ID_OWNER),
+        &JsValue::from_f64(node_ids.program_id as f64),
+    )
+    .expect("must set the owner of the vdom id");
+}
+
+/// Get the "data-vdom-id" of all the desendent of this node including itself,
+/// which are assigned by the program of these `node_ids`.
 /// This is needed to free-up the closure that was attached ActiveClosure manually
-fn get_node_descendant_data_vdom_id(root_element: &Element) -> Vec<usize> {
+fn get_node_descendant_data_vdom_id(root_element: &Element, node_ids: &NodeIds) -> Vec<usize> {
     let mut data_vdom_id = vec![];
-
-    if let Some(vdom_id_str) = root_element.get_attribute(intern(DATA_VDOM_ID)) {
-        let vdom_id = vdom_id_str
-            .parse::<usize>()
-            .expect("unable to parse sauron_vdom-id");
-        data_vdom_id.push(vdom_id);
-    }
-
-    let children = root_element.child_nodes();
-    let child_node_count = children.length();
-    for i in 0..child_node_count {
-        let child_node = children.item(i).expect("Expecting a child node");
-        if child_node.node_type() == Node::ELEMENT_NODE {
-            let child_element = child_node.unchecked_ref::<Element>();
-            let child_data_vdom_id = get_node_descendant_data_vdom_id(child_element);
-            data_vdom_id.extend(child_data_vdom_id);
+    // an explicit stack instead of recursion, so a very deep tree doesn't overflow the stack
+    let mut stack = vec![root_element.clone()];
+    while let Some(element) = stack.pop() {
+        if let Some(vdom_id) = own_vdom_id(&element, node_ids) {
+            data_vdom_id.push(vdom_id);
+        }
+        // the descendants of a template are in its content,
+        // pushed in reverse so the ids are in document order
+        let children = children_container(&element).child_nodes();
+        for i in (0..children.length()).rev() {
+            let child_node = children.item(i).expect("Expecting a child node");
+            if child_node.node_type() == Node::ELEMENT_NODE {
+                stack.push(child_node.unchecked_into());
+            }
         }
     }
     data_vdom_id
@@ -1,7 +1,10 @@
+use crate::dom::dom_node::children_container;
 use crate::dom::dom_node::find_all_nodes;
 use crate::dom::dom_node::intern;
+use crate::dom::text_edit::{text_edit, TextEdit};
 use crate::dom::{Application, Program};
-use crate::vdom::{Attribute, AttributeValue, Patch, PatchType};
+use crate::html::attributes::Special;
+use crate::vdom::{self, Attribute, AttributeValue, Patch, PatchType};
 use mt_dom::TreePath;
 use std::collections::BTreeMap;
 use wasm_bindgen::JsCast;
@@ -23,6 +26,61 @@ pub struct DomPatch<MSG> {
     pub patch_variant: PatchVariant<MSG>,
 }
 
+/// an error when converting the patches into the DOM patches or when applying them
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PatchError {
+    /// the node at the path of the patch can not be found in the DOM,
+    /// ie: the DOM was modified outside of the program
+    #[error("unable to find the node at path: {path:?}")]
+    MissingNode {
+        /// the path of the node in the vdom
+        path: TreePath,
+    },
+    /// the node at the path of the patch has a different tag than the vdom
+    #[error("expecting a tag: {expected:?}, but found: {found:?} at path: {path:?}")]
+    TagMismatch {
+        /// the path of the node in the vdom
+        path: TreePath,
+        /// the tag of the node in the vdom
+        expected: &'static str,
+        /// the tag of the node found in the DOM
+        found: String,
+    },
+    /// the target node can not be patched with this patch, ie: a text node can not have a
+    /// node inserted after it
+    #[error("unsupported node type: {node_type} for patching at path: {path:?}")]
+    UnsupportedNodeType {
+        /// the path of the node in the vdom
+        path: TreePath,
+        /// the `nodeType` of the target node
+        node_type: u16,
+    },
+    /// there is no element with this `id` in the current vdom to be patched as a subtree
+    #[error("unable to find the subtree with id: {id:?}")]
+    MissingSubtree {
+        /// the id of the subtree root element
+        id: String,
+    },
+    /// the DOM operation failed
+    #[error("dom error: {0:?}")]
+    DomError(JsValue),
+}
+
+impl From<JsValue> for PatchError {
+    fn from(error: JsValue) -> Self {
+        Self::DomError(error)
+    }
+}
+
+impl From<PatchError> for JsValue {
+    fn from(error: PatchError) -> Self {
+        match error {
+            PatchError::DomError(error) => error,
+            _ => JsValue::from_str(&error.to_string()),
+        }
+    }
+}
+
 /// patch variant
 pub enum PatchVariant<MSG> {
     /// Insert nodes before the target node
@@ -55,6 +113,21 @@ pub enum PatchVariant<MSG> {
         /// the replacement node
         replacement: Vec<Node>,
     },
+    /// Replace the target element with an element of a different tag, the children of the
+    /// target element are moved into the replacement instead of being recreated
+    ReplaceTag {
+        /// the replacement element, created without children
+        replacement: Node,
+    },
+    /// Change the characters of the target text node, which is kept in the DOM
+    ChangeText {
+        /// where the change starts, in UTF-16 code units
+        offset: u32,
+        /// the number of UTF-16 code units which are removed from the text
+        delete_count: u32,
+        /// the text which is inserted at the offset
+        insert: String,
+    },
     /// Remove the target node
     RemoveNode,
     /// Move the target node before the node specified in the path location
@@ -78,7 +151,7 @@ where
     pub(crate) fn convert_patches(
         &self,
         patches: &[Patch<MSG>],
-    ) -> Result<Vec<DomPatch<MSG>>, JsValue> {
+    ) -> Result<Vec<DomPatch<MSG>>, PatchError> {
         let nodes_to_find: Vec<(&TreePath, Option<&&'static str>)> = patches
             .iter()
             .map(|patch| (patch.path(), patch.tag()))
@@ -90,34 +163,38 @@ where
             )
             .collect();
 
-        let nodes_lookup = find_all_nodes(
-            self.root_node
-                .borrow()
-                .as_ref()
-                .expect("must have a root node"),
-            &nodes_to_find,
-        );
+        let nodes_lookup = {
+            let root_node = self.root_node.borrow();
+            let root_node = root_node.as_ref().ok_or(PatchError::MissingNode {
+                path: TreePath::new(vec![]),
+            })?;
+            find_all_nodes(root_node, &nodes_to_find)
+        };
 
-        let dom_patches:Vec<DomPatch<MSG>> = patches.iter().map(|patch|{
-            let patch_path = patch.path();
-            let patch_tag = patch.tag();
-            if let Some(target_node) = nodes_lookup.get(patch_path) {
+        patches
+            .iter()
+            .map(|patch| {
+                let patch_path = patch.path();
+                let target_node =
+                    nodes_lookup
+                        .get(patch_path)
+                        .ok_or_else(|| PatchError::MissingNode {
+                            path: patch_path.clone(),
+                        })?;
                 let target_element: &Element = target_node.unchecked_ref();
-                if let Some(tag) = patch_tag {
+                if let Some(tag) = patch.tag() {
                     let target_tag = target_element.tag_name().to_lowercase();
                     if target_tag != **tag {
-                        panic!(
-                            "expecting a tag: {tag:?}, but found: {target_tag:?}"
-                        );
+                        return Err(PatchError::TagMismatch {
+                            path: patch_path.clone(),
+                            expected: tag,
+                            found: target_tag,
+                        });
                     }
                 }
                 self.convert_patch(&nodes_lookup, target_element, patch)
-            } else {
-                unreachable!("Getting here means we didn't find the element of next node that we are supposed to patch, patch_path: {:?}, with tag: {:?}", patch_path, patch_tag);
-            }
-        }).collect();
-
-        Ok(dom_patches)
+            })
+            .collect()
     }
     /// convert a virtual DOM Patch into a created DOM node Patch
     pub fn convert_patch(
@@ -125,7 +202,7 @@ where
         nodes_lookup: &BTreeMap<TreePath, Node>,
         target_element: &Element,
         patch: &Patch<MSG>,
-    ) -> DomPatch<MSG> {
+    ) -> Result<DomPatch<MSG>, PatchError> {
         let target_element = target_element.clone();
         let Patch {
             patch_path,
@@ -134,8 +211,31 @@ where
         } = patch;
 
         let patch_path = patch_path.clone();
+        let find_node = |path: &TreePath| {
+            nodes_lookup
+                .get(path)
+                .cloned()
+                .ok_or_else(|| PatchError::MissingNode { path: path.clone() })
+        };
+
+        if let Some(TextEdit {
+            offset,
+            delete_count,
+            insert,
+        }) = self.changed_text(patch)
+        {
+            return Ok(DomPatch {
+                patch_path,
+                target_element,
+                patch_variant: PatchVariant::ChangeText {
+                    offset,
+                    delete_count,
+                    insert,
+                },
+            });
+        }
 
-        match patch_type {
+        let dom_patch = match patch_type {
             PatchType::InsertBeforeNode { nodes } => {
                 let nodes: Vec<Node> = nodes
                     .iter()
@@ -174,6 +274,29 @@ where
                 },
             },
 
+            PatchType::ReplaceNode { .. } if self.is_retagged_element(patch) => {
+                let PatchType::ReplaceNode { replacement } = patch_type else {
+                    unreachable!("must be a replace node patch");
+                };
+                let vdom::Node::Element(new_element) = replacement[0] else {
+                    unreachable!("must be an element");
+                };
+                // the children are moved from the target element when the patch is applied
+                let childless = vdom::Node::Element(vdom::Element {
+                    namespace: new_element.namespace,
+                    tag: new_element.tag,
+                    attrs: new_element.attrs.clone(),
+                    children: vec![],
+                    self_closing: new_element.self_closing,
+                });
+                DomPatch {
+                    patch_path,
+                    target_element,
+                    patch_variant: PatchVariant::ReplaceTag {
+                        replacement: self.create_dom_node(&childless),
+                    },
+                }
+            }
             PatchType::ReplaceNode { replacement } => {
                 let replacement: Vec<Node> = replacement
                     .iter()
@@ -191,15 +314,8 @@ where
                 patch_variant: PatchVariant::RemoveNode,
             },
             PatchType::MoveBeforeNode { nodes_path } => {
-                let for_moving: Vec<Node> = nodes_path
-                    .iter()
-                    .map(|path| {
-                        nodes_lookup
-                            .get(path)
-                            .expect("must have found the node")
-                            .clone()
-                    })
-                    .collect();
+                let for_moving: Vec<Node> =
+                    nodes_path.iter().map(find_node).collect::<Result<_, _>>()?;
                 DomPatch {
                     patch_path,
                     target_element,
@@ -207,15 +323,8 @@ where
                 }
             }
             PatchType::MoveAfterNode { nodes_path } => {
-                let for_moving: Vec<Node> = nodes_path
-                    .iter()
-                    .map(|path| {
-                        nodes_lookup
-                            .get(path)
-                            .expect("must have found the node")
-                            .clone()
-                    })
-                    .collect();
+                let for_moving: Vec<Node> =
+                    nodes_path.iter().map(find_node).collect::<Result<_, _>>()?;
                 DomPatch {
                     patch_path,
                     target_element,
@@ -234,10 +343,60 @@ where
                     patch_variant: PatchVariant::AppendChildren { children },
                 }
             }
+        };
+        Ok(dom_patch)
+    }
+
+    /// returns true if this patch replaces an element with an element of a different tag,
+    /// but with the same children. The children in the DOM can then be moved into the new
+    /// element, so they keep their state such as the focus, scroll position and typed value.
+    fn is_retagged_element(&self, patch: &Patch<MSG>) -> bool {
+        let PatchType::ReplaceNode { replacement } = &patch.patch_type else {
+            return false;
+        };
+        let [vdom::Node::Element(new_element)] = replacement.as_slice() else {
+            return false;
+        };
+        let current_vdom = self.app_context.current_vdom();
+        let Some(vdom::Node::Element(old_element)) =
+            vdom::node_at_path(&current_vdom, &patch.patch_path.path)
+        else {
+            return false;
+        };
+        // the replace attribute explicitly asks for the node to be recreated
+        let is_explicit_replace = new_element
+            .get_value("replace")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        !is_explicit_replace
+            && old_element.tag() != new_element.tag()
+            && old_element.namespace == new_element.namespace
+            && !old_element.children.is_empty()
+            && old_element.children == new_element.children
+    }
+
+    /// the edit of the characters when this patch replaces a text node with another text,
+    /// only when the incremental text is enabled with [`Program::with_incremental_text`]
+    fn changed_text(&self, patch: &Patch<MSG>) -> Option<TextEdit> {
+        if !self.incremental_text.get() {
+            return None;
         }
+        let PatchType::ReplaceNode { replacement } = &patch.patch_type else {
+            return None;
+        };
+        let [vdom::Node::Leaf(new_leaf)] = replacement.as_slice() else {
+            return None;
+        };
+        let new_text = new_leaf.as_text()?;
+        let current_vdom = self.app_context.current_vdom();
+        let vdom::Node::Leaf(old_leaf) = vdom::node_at_path(&current_vdom, &patch.patch_path.path)?
+        else {
+            return None;
+        };
+        text_edit(old_leaf.as_text()?, new_text)
     }
 
-    pub(crate) fn apply_dom_patch(&mut self, dom_patch: DomPatch<MSG>) -> Result<(), JsValue> {
+    pub(crate) fn apply_dom_patch(&mut self, dom_patch: DomPatch<MSG>) -> Result<(), PatchError> {
         let DomPatch {
             patch_path,
             target_element,
@@ -247,36 +406,36 @@ where
         match patch_variant {
             PatchVariant::InsertBeforeNode { nodes } => {
                 // we insert the node before this target element
-                if let Some(parent_target) = target_element.parent_node() {
-                    for for_insert in nodes {
-                        parent_target
-                            .insert_before(&for_insert, Some(&target_element))
-                            .expect("must remove target node");
-                        Self::dispatch_mount_event(&for_insert);
-                    }
-                } else {
-                    panic!("unable to get parent node of the target element: {target_element:?} for patching: {nodes:#?}");
+                let parent_target =
+                    target_element
+                        .parent_node()
+                        .ok_or_else(|| PatchError::MissingNode {
+                            path: patch_path.clone(),
+                        })?;
+                for for_insert in nodes {
+                    parent_target.insert_before(&for_insert, Some(&target_element))?;
+                    Self::dispatch_mount_event(&for_insert);
                 }
             }
 
             PatchVariant::InsertAfterNode { nodes } => {
                 // we insert the node before this target element
                 for for_insert in nodes.into_iter().rev() {
-                    let created_element: &Element = for_insert
-                        .dyn_ref()
-                        .expect("only elements is supported for now");
-                    target_element
-                        .insert_adjacent_element(intern("afterend"), created_element)
-                        .expect("must insert after the target element");
+                    let created_element: &Element =
+                        for_insert
+                            .dyn_ref()
+                            .ok_or_else(|| PatchError::UnsupportedNodeType {
+                                path: patch_path.clone(),
+                                node_type: for_insert.node_type(),
+                            })?;
+                    target_element.insert_adjacent_element(intern("afterend"), created_element)?;
                     Self::dispatch_mount_event(&for_insert);
                 }
             }
             PatchVariant::AppendChildren { children } => {
+                let children_container = children_container(target_element.unchecked_ref());
                 for child in children.into_iter() {
-                    Self::append_child_and_dispatch_mount_event(
-                        target_element.unchecked_ref(),
-                        &child,
-                    );
+                    Self::append_child_and_dispatch_mount_event(&children_container, &child);
                 }
             }
 
@@ -295,6 +454,9 @@ where
                             AttributeValue::EventListener(_) => {
                                 self.remove_event_listener_with_name(attr.name(), &target_element)?;
                             }
+                            AttributeValue::Property(_) => {
+                                Self::remove_element_property(&target_element, attr)?;
+                            }
                             AttributeValue::FunctionCall(_)
                             | AttributeValue::Style(_)
                             | AttributeValue::Empty => (),
@@ -307,37 +469,37 @@ where
             // including the associated closures of the descendant of replaced node
             // before it is actully replaced in the DOM
             PatchVariant::ReplaceNode { mut replacement } => {
-                let first_node = replacement.pop().expect("must have a first node");
+                debug_assert!(!replacement.is_empty(), "must have a replacement node");
+                let Some(first_node) = replacement.pop() else {
+                    return Ok(());
+                };
                 if target_element.node_type() == Node::DOCUMENT_FRAGMENT_NODE {
                     // if we are patching a fragment mode in the top-level document
                     // it has no access to it's parent other than accessing the mount-node itself
                     if patch_path.is_empty() {
                         let mount_node = self.mount_node();
                         Self::clear_children(&mount_node);
-                        mount_node
-                            .append_child(&first_node)
-                            .expect("must append child");
+                        mount_node.append_child(&first_node)?;
                         Self::dispatch_mount_event(&first_node);
 
                         for node in replacement.into_iter() {
                             let node_elm: &web_sys::Element = node.unchecked_ref();
-                            mount_node.append_child(node_elm).expect("append child");
+                            mount_node.append_child(node_elm)?;
                             Self::dispatch_mount_event(node_elm);
                         }
                     } else {
                         // the diffing algorithmn doesn't concern with fragment, instead it test the nodes contain in the fragment as if it where a list of nodes
-                        unreachable!("patching a document fragment other than the root_node should not happen");
+                        return Err(PatchError::UnsupportedNodeType {
+                            path: patch_path,
+                            node_type: Node::DOCUMENT_FRAGMENT_NODE,
+                        });
                     }
                 } else {
                     if target_element.node_type() == Node::ELEMENT_NODE {
                         self.remove_event_listeners(&target_element)?;
                     }
                     //let first_node = replacement.pop().expect("must have a first node");
-                    target_element
-                        .replace_with_with_node_1(&first_node)
-                        .unwrap_or_else(|e| {
-                            panic!("unable to replace node with {first_node:?}, {e:?}");
-                        });
+                    target_element.replace_with_with_node_1(&first_node)?;
 
                     Self::dispatch_mount_event(&first_node);
 
@@ -345,9 +507,7 @@ where
 
                     for node in replacement.into_iter() {
                         let node_elm: &web_sys::Element = node.unchecked_ref();
-                        first_node_elm
-                            .insert_adjacent_element(intern("beforebegin"), node_elm)
-                            .expect("append child");
+                        first_node_elm.insert_adjacent_element(intern("beforebegin"), node_elm)?;
                         Self::dispatch_mount_event(node_elm);
                     }
                 }
@@ -363,49 +523,79 @@ where
                     log::info!("the root_node is replaced with {:?}", &self.root_node);
                 }
             }
+            PatchVariant::ReplaceTag { replacement } => {
+                let target_container = children_container(target_element.unchecked_ref());
+                let replacement_container = children_container(&replacement);
+                while let Some(child) = target_container.first_child() {
+                    replacement_container.append_child(&child)?;
+                }
+                // only the listeners of the target element are removed,
+                // the moved children keep their listeners
+                self.remove_own_event_listeners(&target_element)?;
+                target_element.replace_with_with_node_1(&replacement)?;
+                Self::dispatch_mount_event(&replacement);
+                if patch_path.path.is_empty() {
+                    *self.root_node.borrow_mut() = Some(replacement);
+                }
+            }
+            PatchVariant::ChangeText {
+                offset,
+                delete_count,
+                insert,
+            } => {
+                let text_node: &web_sys::CharacterData = target_element.unchecked_ref();
+                text_node.replace_data(offset, delete_count, &insert)?;
+            }
             PatchVariant::RemoveNode => {
-                let parent_target = target_element
-                    .parent_node()
-                    .expect("must have a parent node");
-                parent_target
-                    .remove_child(&target_element)
-                    .expect("must remove target node");
+                let parent_target =
+                    target_element
+                        .parent_node()
+                        .ok_or_else(|| PatchError::MissingNode {
+                            path: patch_path.clone(),
+                        })?;
+                parent_target.remove_child(&target_element)?;
                 if target_element.node_type() == Node::ELEMENT_NODE {
                     self.remove_event_listeners(&target_element)?;
                 }
             }
             PatchVariant::MoveBeforeNode { for_moving } => {
-                if let Some(target_parent) = target_element.parent_node() {
-                    for move_node in for_moving {
-                        let move_node_parent = move_node
+                let target_parent =
+                    target_element
+                        .parent_node()
+                        .ok_or_else(|| PatchError::MissingNode {
+                            path: patch_path.clone(),
+                        })?;
+                for move_node in for_moving {
+                    let move_node_parent =
+                        move_node
                             .parent_node()
-                            .expect("node for moving must have parent");
-                        let move_node = move_node_parent
-                            .remove_child(&move_node)
-                            .expect("must remove child");
-                        target_parent
-                            .insert_before(&move_node, Some(&target_element))
-                            .expect("must insert before this node");
-                    }
-                } else {
-                    panic!("unable to get the parent node of the target element");
+                            .ok_or_else(|| PatchError::MissingNode {
+                                path: patch_path.clone(),
+                            })?;
+                    let move_node = move_node_parent.remove_child(&move_node)?;
+                    target_parent.insert_before(&move_node, Some(&target_element))?;
                 }
             }
 
             PatchVariant::MoveAfterNode { for_moving } => {
-                for move_node in for_moving {
-                    let move_node_parent = move_node
-                        .parent_node()
-                        .expect("node for moving must have parent");
-                    let to_move_node = move_node_parent
-                        .remove_child(&move_node)
-                        .expect("must remove child");
-
-                    let to_move_element: &web_sys::Element =
-                        to_move_node.dyn_ref().expect("an element");
+                // the nodes are moved with `insert_before` of the next sibling instead of
+                // `insert_adjacent_element`, so text nodes can also be moved and be the target
+                let target_parent =
                     target_element
-                        .insert_adjacent_element(intern("afterend"), to_move_element)
-                        .expect("must insert before this node");
+                        .parent_node()
+                        .ok_or_else(|| PatchError::MissingNode {
+                            path: patch_path.clone(),
+                        })?;
+                for move_node in for_moving {
+                    let move_node_parent =
+                        move_node
+                            .parent_node()
+                            .ok_or_else(|| PatchError::MissingNode {
+                                path: patch_path.clone(),
+                            })?;
+                    let move_node = move_node_parent.remove_child(&move_node)?;
+                    let next_sibling = target_element.next_sibling();
+                    target_parent.insert_before(&move_node, next_sibling.as_ref())?;
                 }
             }
         }
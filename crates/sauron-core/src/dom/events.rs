@@ -1,19 +1,25 @@
 //! Create [events][0] Object
 //!
 //! [0]: https://developer.mozilla.org/en-US/docs/Web/Events
-use crate::dom::{document, window, Event};
+use crate::dom::{document, set_timeout, window, Event, TimeoutCallbackHandle};
 use crate::{
-    html::attributes::AttributeValue,
+    html::attributes::{AttributeValue, ListenerOptions},
     vdom::{Attribute, Listener},
 };
+use std::{cell::RefCell, rc::Rc};
 use wasm_bindgen::JsCast;
-#[cfg(web_sys_unstable_apis)]
-pub use web_sys::ClipboardEvent;
+
+mod input_date;
+
+pub use input_date::{InputDate, InputDateTime, InputTime, ParseInputDateError};
 pub use web_sys::{
-    AnimationEvent, FocusEvent, HashChangeEvent, KeyboardEvent, MouseEvent, Selection, TouchEvent,
-    TransitionEvent,
+    AnimationEvent, File, FocusEvent, HashChangeEvent, KeyboardEvent, MouseEvent, PointerEvent,
+    Selection, TouchEvent, TransitionEvent, ValidityState, WheelEvent,
+};
+use web_sys::{
+    EventTarget, FileList, HtmlDetailsElement, HtmlElement, HtmlFormElement, HtmlInputElement,
+    HtmlMediaElement, HtmlSelectElement, HtmlTextAreaElement,
 };
-use web_sys::{EventTarget, FileList, HtmlDetailsElement, HtmlElement, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
 
 #[derive(Clone, Copy)]
 #[repr(i16)]
@@ -87,6 +93,71 @@ where
     mt_dom::attr(event_name, AttributeValue::EventListener(Listener::from(f)))
 }
 
+/// an event builder where the callback may choose not to emit a msg
+/// by returning None
+pub fn on_maybe<F, MSG>(event_name: &'static str, f: F) -> Attribute<MSG>
+where
+    F: Fn(Event) -> Option<MSG> + 'static,
+    MSG: 'static,
+{
+    mt_dom::attr(
+        event_name,
+        AttributeValue::EventListener(Listener::maybe(f)),
+    )
+}
+
+/// Set the options of the event listeners of an event attribute
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::{*, events::*}};
+///
+/// enum Msg { Scrolled(i32, i32) }
+/// let view: Node<Msg> = div([on_scroll(|(top, left)| Msg::Scrolled(top, left)).passive()], []);
+/// ```
+pub trait EventOptions {
+    /// the listener will never call `prevent_default`, which lets the browser scroll
+    /// without waiting for the listener
+    fn passive(self) -> Self;
+    /// the listener is called in the capture phase, before the listeners of the descendants
+    fn capture(self) -> Self;
+    /// the listener is removed after it is called once
+    fn once(self) -> Self;
+}
+
+impl<MSG> EventOptions for Attribute<MSG>
+where
+    MSG: 'static,
+{
+    fn passive(self) -> Self {
+        with_listener_options(self, |options| options.passive = true)
+    }
+
+    fn capture(self) -> Self {
+        with_listener_options(self, |options| options.capture = true)
+    }
+
+    fn once(self) -> Self {
+        with_listener_options(self, |options| options.once = true)
+    }
+}
+
+fn with_listener_options<MSG>(
+    mut attr: Attribute<MSG>,
+    set_option: impl Fn(&mut ListenerOptions),
+) -> Attribute<MSG>
+where
+    MSG: 'static,
+{
+    for value in attr.value.iter_mut() {
+        if let AttributeValue::EventListener(listener) = value {
+            let mut options = listener.options();
+            set_option(&mut options);
+            *listener = listener.clone().with_options(options);
+        }
+    }
+    attr
+}
+
 /// on click event
 pub fn on_click<F, MSG>(f: F) -> Attribute<MSG>
 where
@@ -96,26 +167,87 @@ where
     on("click", move |event: Event| f(to_mouse_event(event)))
 }
 
+thread_local! {
+    static SCROLL_END_EVENT: RefCell<Option<web_sys::Event>> = const { RefCell::new(None) };
+}
+
+/// returns true if this is the `scroll` event which is being dispatched by an [`on_scroll_end`]
+/// when its wait is over, rather than a scroll of the browser
+fn is_scroll_end_event(event: &Event) -> bool {
+    let Event::WebEvent(web_event) = event else {
+        return false;
+    };
+    SCROLL_END_EVENT.with(|scroll_end| scroll_end.borrow().as_ref() == Some(web_event))
+}
+
 /// attach callback to the scroll event
+///
+/// Note: the `scroll` events which are dispatched by [`on_scroll_end`] are ignored.
 pub fn on_scroll<F, MSG>(f: F) -> Attribute<MSG>
 where
     F: Fn((i32, i32)) -> MSG + 'static,
     MSG: 'static,
 {
-    on("scroll", move |event: Event| {
-        let web_event = event.as_web().expect("must be a web event");
-        let target = web_event.target().expect("can't get target");
-        if let Some(element) = target.dyn_ref::<web_sys::Element>() {
-            let scroll_top = element.scroll_top();
-            let scroll_left = element.scroll_left();
-            f((scroll_top, scroll_left))
-        } else {
-            let window = window();
-            let scroll_top = window.page_y_offset().expect("must get page offset") as i32;
-            let scroll_left = window.page_x_offset().expect("must get page offset") as i32;
-            f((scroll_top, scroll_left))
+    on_maybe("scroll", move |event: Event| {
+        if is_scroll_end_event(&event) {
+            return None;
+        }
+        Some(f(to_scroll_position(event)))
+    })
+}
+
+/// attach a callback which is called once when the scrolling of the element has paused
+/// for `ms` milliseconds, with the `(scroll_top, scroll_left)` where the scrolling stopped.
+///
+/// Unlike the native `scrollend` event, this is also fired when the scrolling only
+/// pauses while the user is still holding the scrollbar or touching the screen.
+/// When the wait is over, an extra `scroll` event is dispatched to the element and the msg is
+/// emitted from it. This event doesn't bubble and is ignored by [`on_scroll`] and the other
+/// `on_scroll_end` of the element, but the listeners attached with `on("scroll", ..)` are
+/// called once more.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::{*, events::*}};
+///
+/// enum Msg { ScrollStopped(i32, i32) }
+/// let view: Node<Msg> = div([on_scroll_end(150, |(top, left)| Msg::ScrollStopped(top, left))], []);
+/// ```
+pub fn on_scroll_end<F, MSG>(ms: i32, f: F) -> Attribute<MSG>
+where
+    F: Fn((i32, i32)) -> MSG + 'static,
+    MSG: 'static,
+{
+    // the pending timeout is cleared when it is replaced, restarting the wait
+    let pending: Rc<RefCell<Option<TimeoutCallbackHandle>>> = Rc::new(RefCell::new(None));
+    // the scroll event which is dispatched when the wait of this listener is over,
+    // to tell it apart from the one of the other `on_scroll_end` of the same element
+    let scroll_end: Rc<RefCell<Option<web_sys::Event>>> = Rc::new(RefCell::new(None));
+    on_maybe("scroll", move |event: Event| {
+        let web_event = event.clone().as_web().expect("must be a web event");
+        if is_scroll_end_event(&event) {
+            let is_own = scroll_end.borrow().as_ref() == Some(&web_event);
+            return is_own.then(|| f(to_scroll_position(event)));
         }
+        let target = web_event
+            .current_target()
+            .expect("must have a current target");
+        let scroll_end = Rc::clone(&scroll_end);
+        let handle = set_timeout(
+            move || {
+                let event = web_sys::Event::new("scroll").expect("must create event");
+                *scroll_end.borrow_mut() = Some(event.clone());
+                SCROLL_END_EVENT.with(|current| *current.borrow_mut() = Some(event.clone()));
+                // dispatched synchronously, so the marker is set only during its listeners
+                target.dispatch_event(&event).expect("must dispatch event");
+                SCROLL_END_EVENT.with(|current| *current.borrow_mut() = None);
+                *scroll_end.borrow_mut() = None;
+            },
+            ms,
+        );
+        *pending.borrow_mut() = Some(handle);
+        None
     })
+    .passive()
 }
 
 /// an event when a virtual Node is mounted the field node is the actual
@@ -224,6 +356,22 @@ fn to_transition_event(event: Event) -> TransitionEvent {
         .expect("unable to cast to transition event")
 }
 
+/// the wheel event contains the `delta_x`, `delta_y`, `delta_z` and `delta_mode`,
+/// it also derefs to MouseEvent for accessing the pointer position
+fn to_wheel_event(event: Event) -> WheelEvent {
+    let web_event = event.as_web().expect("must be a web_sys event");
+    web_event.dyn_into().expect("unable to cast to wheel event")
+}
+
+/// the pointer event contains the `pointer_id` of the pointer,
+/// it also derefs to MouseEvent for accessing the pointer position
+fn to_pointer_event(event: Event) -> PointerEvent {
+    let web_event = event.as_web().expect("must be a web_sys event");
+    web_event
+        .dyn_into()
+        .expect("unable to cast to pointer event")
+}
+
 fn to_touch_event(event: Event) -> TouchEvent {
     let web_event = event.as_web().expect("must be web sys event");
     web_event.dyn_into().expect("unable to cast to touch event")
@@ -243,8 +391,7 @@ fn to_hashchange_event(event: Event) -> HashChangeEvent {
         .expect("unable to cast to hashchange event")
 }
 
-/// TODO: expand this much farther by getting the InputEvent data, data_transfer, event_type,
-/// is_composing events.
+/// TODO: expand this much farther by getting the InputEvent data_transfer.
 /// a custom InputEvent to contain the input string value
 #[derive(Debug)]
 pub struct InputEvent {
@@ -276,6 +423,46 @@ impl InputEvent {
         }
     }
 
+    /// the native `InputEvent`, None if the event is not an `InputEvent`,
+    /// ie: a plain `Event` dispatched with the `input` name
+    pub fn as_input_event(&self) -> Option<&web_sys::InputEvent> {
+        self.event.dyn_ref::<web_sys::InputEvent>()
+    }
+
+    /// the kind of the change to the content, ie: `insertText`, `deleteContentBackward`,
+    /// `insertFromPaste`.
+    /// None if the event is not an `InputEvent`
+    pub fn input_type(&self) -> Option<String> {
+        self.as_input_event()
+            .map(|input_event| input_event.input_type())
+    }
+
+    /// the inserted text, None if the event is not an `InputEvent` or when nothing is
+    /// inserted such as a deletion
+    pub fn data(&self) -> Option<String> {
+        self.as_input_event()
+            .and_then(|input_event| input_event.data())
+    }
+
+    /// the content of the target element in this format, ie: of a `contenteditable` element
+    /// which has no `value`. None if the target is not an element
+    pub fn content(&self, format: ContentFormat) -> Option<String> {
+        let target = self.event.target()?;
+        let element = target.dyn_ref::<web_sys::Element>()?;
+        match format {
+            ContentFormat::Text => element.text_content(),
+            ContentFormat::Html => Some(element.inner_html()),
+        }
+    }
+
+    /// returns true if this input event is fired in the middle of an IME composition
+    pub fn is_composing(&self) -> bool {
+        self.event
+            .dyn_ref::<web_sys::InputEvent>()
+            .map(|input_event| input_event.is_composing())
+            .unwrap_or(false)
+    }
+
     /// return the list of files from the input event
     pub fn files(&self) -> FileList {
         let target: EventTarget = self.event.target().expect("Unable to get event target");
@@ -299,11 +486,243 @@ impl InputEvent {
     }
 }
 
+/// the format of the content of a `contenteditable` element, see [`on_content_input`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentFormat {
+    /// the `textContent` of the element, without the markup
+    Text,
+    /// the `innerHTML` of the element, including the markup of the rich text
+    Html,
+}
+
 fn to_input_event(event: Event) -> InputEvent {
     let web_event = event.as_web().expect("must be a web event");
     InputEvent::new(web_event)
 }
 
+/// attach an input listener which is only dispatched for the native `InputEvent`, so its
+/// [`InputEvent::input_type`] and [`InputEvent::data`] are always present,
+/// ie: to tell the deletions apart from the insertions.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::{*, events::*}};
+///
+/// enum Msg {
+///     Deleted(String),
+///     Changed(String),
+/// }
+///
+/// let view: Node<Msg> = input(
+///     [on_input_raw(|ie| {
+///         if ie.input_type().unwrap_or_default().starts_with("delete") {
+///             Msg::Deleted(ie.value())
+///         } else {
+///             Msg::Changed(ie.value())
+///         }
+///     })],
+///     [],
+/// );
+/// ```
+pub fn on_input_raw<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(InputEvent) -> MSG + 'static,
+    MSG: 'static,
+{
+    on_maybe("input", move |event: Event| {
+        let input_event = to_input_event(event);
+        input_event.as_input_event()?;
+        Some(f(input_event))
+    })
+}
+
+/// attach an input listener for a `contenteditable` element, which has no `value`,
+/// the content of the element is read in the `format` instead.
+/// The content is read from the element itself, so it is up to date even for the input events
+/// in the middle of an IME composition.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::{*, attributes::*, events::*}};
+///
+/// enum Msg { Edited(String) }
+///
+/// let editor: Node<Msg> = div(
+///     [contenteditable(true), on_content_input(ContentFormat::Html, Msg::Edited)],
+///     [],
+/// );
+/// ```
+pub fn on_content_input<F, MSG>(format: ContentFormat, f: F) -> Attribute<MSG>
+where
+    F: Fn(String) -> MSG + 'static,
+    MSG: 'static,
+{
+    on_maybe("input", move |event: Event| {
+        to_input_event(event).content(format).map(&f)
+    })
+}
+
+/// attach an input listener which is aware of IME composition.
+///
+/// When `skip_composing` is true, input events that are fired in the middle of a composition
+/// (ie: typing CJK characters) are not dispatched, instead a single input is emitted
+/// when the composition ends.
+/// Since this listens to both `input` and `compositionend` events, the attributes has to be
+/// added to the element with `with_attributes`.
+/// # Examples
+/// ```rust,ignore
+/// use sauron::{*, html::events::*};
+///
+/// let view: Node<String> = input([r#type("text")], [])
+///     .with_attributes(on_input_with_composition(true, |ie| ie.value()));
+/// ```
+pub fn on_input_with_composition<F, MSG>(
+    skip_composing: bool,
+    f: F,
+) -> impl IntoIterator<Item = Attribute<MSG>>
+where
+    F: Fn(InputEvent) -> MSG + 'static,
+    MSG: 'static,
+{
+    if !skip_composing {
+        return vec![on_input(f)];
+    }
+    let f = Rc::new(f);
+    let f2 = Rc::clone(&f);
+    // the value emitted at compositionend, browsers such as firefox follows the
+    // compositionend with a non-composing input event of the same value, which we skip
+    let composed_value: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+    let composed_value2 = Rc::clone(&composed_value);
+    vec![
+        on_maybe("input", move |event: Event| {
+            let input_event = to_input_event(event);
+            if input_event.is_composing() {
+                return None;
+            }
+            if let Some(composed) = composed_value.borrow_mut().take() {
+                if composed == input_event.value() {
+                    return None;
+                }
+            }
+            Some(f(input_event))
+        }),
+        on_maybe("compositionend", move |event: Event| {
+            let input_event = to_input_event(event);
+            *composed_value2.borrow_mut() = Some(input_event.value());
+            Some(f2(input_event))
+        }),
+    ]
+}
+
+/// attach an input listener for number and range inputs which parses the value as `f64`.
+/// The msg is not dispatched when the value can not be parsed, ie: the input is empty.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::{*, attributes::*, events::*}};
+///
+/// enum Msg { VolumeChanged(f64) }
+/// let slider: Node<Msg> = input([r#type("range"), on_input_number(Msg::VolumeChanged)], []);
+/// ```
+pub fn on_input_number<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(f64) -> MSG + 'static,
+    MSG: 'static,
+{
+    on_maybe("input", move |event: Event| {
+        to_input_event(event)
+            .value()
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .map(&f)
+    })
+}
+
+/// attach an input listener for number and range inputs which parses the value as `i64`.
+/// The msg is not dispatched when the value can not be parsed as an integer.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::{*, attributes::*, events::*}};
+///
+/// enum Msg { QuantityChanged(i64) }
+/// let quantity: Node<Msg> = input([r#type("number"), on_input_int(Msg::QuantityChanged)], []);
+/// ```
+pub fn on_input_int<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(i64) -> MSG + 'static,
+    MSG: 'static,
+{
+    on_maybe("input", move |event: Event| {
+        to_input_event(event)
+            .value()
+            .trim()
+            .parse::<i64>()
+            .ok()
+            .map(&f)
+    })
+}
+
+/// attach an input listener for the `<input type="date">` which parses the value into an [`InputDate`].
+/// The error is passed when the value can not be parsed, ie: the date is cleared or is only partially filled.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::{*, attributes::*, events::*}};
+///
+/// enum Msg { BirthdayChanged(Result<InputDate, ParseInputDateError>) }
+/// let birthday: Node<Msg> = input([r#type("date"), on_input_date(Msg::BirthdayChanged)], []);
+/// ```
+pub fn on_input_date<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(Result<InputDate, ParseInputDateError>) -> MSG + 'static,
+    MSG: 'static,
+{
+    on_input_parsed(f)
+}
+
+/// attach an input listener for the `<input type="time">` which parses the value into an [`InputTime`].
+/// The error is passed when the value can not be parsed, ie: the time is cleared.
+pub fn on_input_time<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(Result<InputTime, ParseInputDateError>) -> MSG + 'static,
+    MSG: 'static,
+{
+    on_input_parsed(f)
+}
+
+/// attach an input listener for the `<input type="datetime-local">` which parses the value into an [`InputDateTime`].
+/// The error is passed when the value can not be parsed, ie: the date or time is cleared.
+pub fn on_input_datetime<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(Result<InputDateTime, ParseInputDateError>) -> MSG + 'static,
+    MSG: 'static,
+{
+    on_input_parsed(f)
+}
+
+fn on_input_parsed<T, F, MSG>(f: F) -> Attribute<MSG>
+where
+    T: std::str::FromStr<Err = ParseInputDateError>,
+    F: Fn(Result<T, ParseInputDateError>) -> MSG + 'static,
+    MSG: 'static,
+{
+    on("input", move |event: Event| f(to_value(event).parse()))
+}
+
+fn to_scroll_position(event: Event) -> (i32, i32) {
+    let web_event = event.as_web().expect("must be a web event");
+    let target = web_event.target().expect("can't get target");
+    if let Some(element) = target.dyn_ref::<web_sys::Element>() {
+        (element.scroll_top(), element.scroll_left())
+    } else {
+        let window = window();
+        let scroll_top = window.page_y_offset().expect("must get page offset") as i32;
+        let scroll_left = window.page_x_offset().expect("must get page offset") as i32;
+        (scroll_top, scroll_left)
+    }
+}
+
+fn to_value(event: Event) -> String {
+    to_input_event(event).value()
+}
+
 fn to_checked(event: Event) -> bool {
     let web_event = event.as_web().expect("must be a web event");
     let target: EventTarget = web_event.target().expect("Unable to get event target");
@@ -314,26 +733,249 @@ fn to_checked(event: Event) -> bool {
     }
 }
 
+/// the validity of the input, textarea or select, `None` for the other elements
+fn to_validity(event: Event) -> Option<ValidityState> {
+    let web_event = event.as_web().expect("must be a web event");
+    let target: EventTarget = web_event.target().expect("Unable to get event target");
+    if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+        Some(input.validity())
+    } else if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
+        Some(textarea.validity())
+    } else {
+        target
+            .dyn_ref::<HtmlSelectElement>()
+            .map(|select| select.validity())
+    }
+}
+
+/// the selected files of the input, empty for the other elements
+fn to_files(event: Event) -> Vec<File> {
+    let web_event = event.as_web().expect("must be a web event");
+    let target: EventTarget = web_event.target().expect("Unable to get event target");
+    target
+        .dyn_ref::<HtmlInputElement>()
+        .and_then(|input| input.files())
+        .map(|files| (0..files.length()).filter_map(|i| files.get(i)).collect())
+        .unwrap_or_default()
+}
+
 fn to_open(event: Event) -> bool {
     let web_event = event.as_web().expect("must be a web event");
     let target: EventTarget = web_event.target().expect("Unable to get event target");
     if let Some(details) = target.dyn_ref::<HtmlDetailsElement>() {
         details.open()
     } else {
-        panic!("must be a html input element");
+        panic!("must be a html details element");
     }
 }
 
+/// the playback position of the `<audio>` or `<video>` in seconds, `None` for the other elements
+fn to_current_time(event: Event) -> Option<f64> {
+    let web_event = event.as_web().expect("must be a web event");
+    let target: EventTarget = web_event.target().expect("Unable to get event target");
+    target
+        .dyn_ref::<HtmlMediaElement>()
+        .map(|media| media.current_time())
+}
+
+/// a custom ClipboardEvent to access the data being copied, cut or pasted
+///
 /// Note: paste event happens before the data is inserted into the target element
 /// therefore trying to access the data on the target element triggered from paste will get an
-/// empty text
-#[cfg(web_sys_unstable_apis)]
+/// empty text, use [`ClipboardEvent::text`] instead.
+#[derive(Debug)]
+pub struct ClipboardEvent {
+    /// the actual dom event
+    pub event: web_sys::ClipboardEvent,
+}
+
+impl ClipboardEvent {
+    fn new(event: web_sys::ClipboardEvent) -> Self {
+        ClipboardEvent { event }
+    }
+
+    /// the plain text content of the clipboard data
+    pub fn text(&self) -> Option<String> {
+        self.event
+            .clipboard_data()
+            .and_then(|data_transfer| data_transfer.get_data("text/plain").ok())
+    }
+
+    /// set the plain text content which will be put into the clipboard.
+    /// Only takes effect on copy and cut events and must be paired with
+    /// [`ClipboardEvent::prevent_default`] to override the default behavior.
+    pub fn set_text(&self, text: &str) {
+        if let Some(data_transfer) = self.event.clipboard_data() {
+            data_transfer
+                .set_data("text/plain", text)
+                .expect("must set clipboard data");
+        }
+    }
+
+    /// prevent the browser from doing the default clipboard behavior,
+    /// such as inserting the pasted text into the target element
+    pub fn prevent_default(&self) {
+        self.event.prevent_default();
+    }
+}
+
 fn to_clipboard_event(event: Event) -> ClipboardEvent {
-    event
-        .as_web()
-        .expect("must be a web event")
-        .dyn_into()
-        .expect("unable to cast to clipboard event")
+    ClipboardEvent::new(
+        event
+            .as_web()
+            .expect("must be a web event")
+            .dyn_into()
+            .expect("unable to cast to clipboard event"),
+    )
+}
+
+/// a field of a form which fails its constraints, see [`on_submit_validated`]
+#[derive(Debug, Clone)]
+pub struct InvalidField {
+    /// the `name` of the field, or its `id` when it has no name
+    pub name: String,
+    /// tells which of the constraints of the field has failed
+    pub validity: ValidityState,
+    /// the message which the browser shows for the failed constraint
+    pub message: String,
+}
+
+/// the fields of the form which fails their constraints such as `required`, `min`, `max`,
+/// `step` or `pattern`, in the order they appear in the form.
+///
+/// Each of the invalid field also dispatches its `invalid` event, see [`on_invalid`].
+pub fn invalid_fields(form: &HtmlFormElement) -> Vec<InvalidField> {
+    let elements = form.elements();
+    (0..elements.length())
+        .filter_map(|i| elements.item(i))
+        .filter_map(|element| {
+            let (valid, validity, message) =
+                if let Some(input) = element.dyn_ref::<HtmlInputElement>() {
+                    (
+                        input.check_validity(),
+                        input.validity(),
+                        input.validation_message(),
+                    )
+                } else if let Some(textarea) = element.dyn_ref::<HtmlTextAreaElement>() {
+                    (
+                        textarea.check_validity(),
+                        textarea.validity(),
+                        textarea.validation_message(),
+                    )
+                } else if let Some(select) = element.dyn_ref::<HtmlSelectElement>() {
+                    (
+                        select.check_validity(),
+                        select.validity(),
+                        select.validation_message(),
+                    )
+                } else {
+                    return None;
+                };
+            if valid {
+                return None;
+            }
+            let name = element
+                .get_attribute("name")
+                .unwrap_or_else(|| element.id());
+            Some(InvalidField {
+                name,
+                validity,
+                message: message.unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// dispatch `on_valid` when the form is submitted and all of its fields are valid,
+/// otherwise the submission is prevented and `on_invalid` is dispatched with the invalid fields.
+///
+/// The browser doesn't dispatch the `submit` event of a form with invalid fields and shows
+/// its own validation messages instead, so set `novalidate` on the form to show a custom
+/// validation UI with this.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::{*, attributes::*, events::*}};
+///
+/// enum Msg {
+///     Submit,
+///     Invalid(Vec<InvalidField>),
+/// }
+///
+/// let view: Node<Msg> = form(
+///     [
+///         novalidate(true),
+///         on_submit_validated(|e| {
+///             e.prevent_default();
+///             Msg::Submit
+///         }, Msg::Invalid),
+///     ],
+///     [input([r#type("email"), name("email"), required(true)], [])],
+/// );
+/// ```
+pub fn on_submit_validated<F, V, MSG>(on_valid: F, on_invalid: V) -> Attribute<MSG>
+where
+    F: Fn(web_sys::Event) -> MSG + 'static,
+    V: Fn(Vec<InvalidField>) -> MSG + 'static,
+    MSG: 'static,
+{
+    on("submit", move |event: Event| {
+        let web_event = to_webevent(event);
+        let form: HtmlFormElement = web_event
+            .current_target()
+            .expect("must have a target")
+            .dyn_into()
+            .expect("must be a form element");
+        let invalid = invalid_fields(&form);
+        if invalid.is_empty() {
+            on_valid(web_event)
+        } else {
+            web_event.prevent_default();
+            on_invalid(invalid)
+        }
+    })
+}
+
+/// a custom SelectionEvent to access the range of the selected text in an input or textarea
+#[derive(Debug)]
+pub struct SelectionEvent {
+    /// the actual dom event
+    pub event: web_sys::Event,
+}
+
+impl SelectionEvent {
+    fn new(event: web_sys::Event) -> Self {
+        SelectionEvent { event }
+    }
+
+    /// the index of the first selected character,
+    /// None if the target does not support selection such as `<input type="number">`
+    pub fn selection_start(&self) -> Option<u32> {
+        let target: EventTarget = self.event.target().expect("Unable to get event target");
+        if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+            input.selection_start().ok().flatten()
+        } else if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
+            textarea.selection_start().ok().flatten()
+        } else {
+            None
+        }
+    }
+
+    /// the index after the last selected character,
+    /// None if the target does not support selection such as `<input type="number">`
+    pub fn selection_end(&self) -> Option<u32> {
+        let target: EventTarget = self.event.target().expect("Unable to get event target");
+        if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+            input.selection_end().ok().flatten()
+        } else if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
+            textarea.selection_end().ok().flatten()
+        } else {
+            None
+        }
+    }
+}
+
+fn to_selection_event(event: Event) -> SelectionEvent {
+    SelectionEvent::new(event.as_web().expect("must be a web event"))
 }
 
 fn to_selection(_event: Event) -> Option<Selection> {
@@ -348,21 +990,46 @@ fn to_selection(_event: Event) -> Option<Selection> {
 declare_html_events! {
     on_auxclick => auxclick => to_mouse_event => MouseEvent;
     on_animationend => animationend => to_animation_event => AnimationEvent;
+    ///
+    /// Dispatched when a transition is created, before its delay has elapsed.
+    /// Use `TransitionEvent::property_name` to tell which css property is transitioning.
+    on_transitionrun => transitionrun => to_transition_event => TransitionEvent;
+    ///
+    /// Dispatched when a running transition is interrupted, ie: the transitioned property is
+    /// changed again or the element is hidden, `transitionend` is not dispatched in that case.
+    on_transitioncancel => transitioncancel => to_transition_event => TransitionEvent;
     on_transitionend => transitionend => to_transition_event => TransitionEvent;
     on_contextmenu => contextmenu => to_mouse_event => MouseEvent;
     on_dblclick  => dblclick => to_mouse_event => MouseEvent;
     on_mousedown => mousedown => to_mouse_event => MouseEvent;
+    ///
+    /// Unlike `on_mouseover`, this doesn't bubble and is not dispatched again when the pointer
+    /// moves in between the descendants, it is dispatched once when the pointer enters the element.
     on_mouseenter => mouseenter => to_mouse_event => MouseEvent;
+    ///
+    /// Unlike `on_mouseout`, this doesn't bubble and is dispatched once when the pointer
+    /// leaves the element and all of its descendants.
     on_mouseleave => mouseleave => to_mouse_event => MouseEvent;
     on_mousemove => mousemove => to_mouse_event => MouseEvent;
     on_mouseover => mouseover => to_mouse_event => MouseEvent;
     on_mouseout => mouseout => to_mouse_event => MouseEvent;
     on_mouseup => mouseup => to_mouse_event => MouseEvent;
+    ///
+    /// Dispatched when the element captures a pointer with `set_pointer_capture`,
+    /// use `PointerEvent::pointer_id` to tell which pointer is captured.
+    on_gotpointercapture => gotpointercapture => to_pointer_event => PointerEvent;
+    ///
+    /// Dispatched when the pointer capture is released, either explicitly with
+    /// `release_pointer_capture` or implicitly when the pointer is lifted.
+    on_lostpointercapture => lostpointercapture => to_pointer_event => PointerEvent;
     on_pointerlockchange => pointerlockchange => to_mouse_event => MouseEvent;
     on_pointerlockerror => pointerlockerror => to_mouse_event => MouseEvent;
     on_popstate => popstate => to_webevent => web_sys::Event;
-    on_select => select => to_webevent => web_sys::Event;
-    on_wheel => wheel => to_mouse_event => MouseEvent;
+    ///
+    /// Dispatched when the text in an input or textarea is selected,
+    /// the `SelectionEvent` tells the range of the selected text.
+    on_select => select => to_selection_event => SelectionEvent;
+    on_wheel => wheel => to_wheel_event => WheelEvent;
     on_doubleclick => dblclick => to_mouse_event => MouseEvent;
     on_keydown => keydown => to_keyboard_event => KeyboardEvent;
     on_keypress => keypress => to_keyboard_event => KeyboardEvent;
@@ -373,15 +1040,44 @@ declare_html_events! {
     on_touchmove => touchmove => to_touch_event => TouchEvent;
     on_focus => focus => to_focus_event => FocusEvent;
     on_blur => blur => to_focus_event => FocusEvent;
+    ///
+    /// Dispatched when the form is reset, ie: by a `<button type="reset">` or `form.reset()`.
+    /// Call `prevent_default` on the event to keep the current values of the form fields.
     on_reset => reset => to_webevent => web_sys::Event;
+    on_close => close => to_webevent => web_sys::Event;
+    on_cancel => cancel => to_webevent => web_sys::Event;
     on_submit => submit => to_webevent => web_sys::Event;
     on_input => input => to_input_event => InputEvent;
     on_checked => input => to_checked => bool;
-    #[cfg(web_sys_unstable_apis)]
     on_paste => paste => to_clipboard_event => ClipboardEvent;
-    #[cfg(web_sys_unstable_apis)]
     on_copy => copy => to_clipboard_event => ClipboardEvent;
+    on_cut => cut => to_clipboard_event => ClipboardEvent;
     on_change => change => to_input_event => InputEvent;
+    ///
+    /// Unlike `on_input`, this is dispatched only when the value is committed:
+    /// immediately on selection for `select`, and when the element loses focus for text inputs.
+    on_change_value => change => to_value => String;
+    ///
+    /// Dispatched immediately when a checkbox or radio button is toggled, with its checked state.
+    on_change_checked => change => to_checked => bool;
+    ///
+    /// Dispatched when the element fails its constraints such as `required`, `min`, `max`,
+    /// `step` or `pattern`, when the form is submitted or validity is checked.
+    /// The `ValidityState` tells which of the constraints has failed, it is `None` when the
+    /// target is not an input, textarea or select element.
+    on_invalid => invalid => to_validity => Option<ValidityState>;
+    ///
+    /// Dispatched when the files of an `<input type="file">` are selected, with all of
+    /// the selected files. The list is empty when the selection is cleared, or when the target
+    /// is not an input element.
+    on_file_change => change => to_files => Vec<File>;
+    on_play => play => to_webevent => web_sys::Event;
+    on_pause => pause => to_webevent => web_sys::Event;
+    on_ended => ended => to_webevent => web_sys::Event;
+    ///
+    /// Dispatched when the playback position of an `<audio>` or `<video>` changes,
+    /// with the current time in seconds, it is `None` when the target is not a media element.
+    on_timeupdate => timeupdate => to_current_time => Option<f64>;
     on_broadcast => broadcast => to_input_event => InputEvent;
     on_hashchange => hashchange => to_hashchange_event => HashChangeEvent;
     on_readystatechange => readystatechange => to_webevent => web_sys::Event;
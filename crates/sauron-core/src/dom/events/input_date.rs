@@ -0,0 +1,213 @@
+//! the typed values of the `date`, `time` and `datetime-local` inputs,
+//! see [`on_input_date`], [`on_input_time`] and [`on_input_datetime`]
+//!
+//! [`on_input_date`]: crate::dom::events::on_input_date
+//! [`on_input_time`]: crate::dom::events::on_input_time
+//! [`on_input_datetime`]: crate::dom::events::on_input_datetime
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// the value of an `<input type="date">`, which is formatted as `YYYY-MM-DD`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InputDate {
+    /// the year, which can have more than 4 digits
+    pub year: i32,
+    /// the month, from 1 to 12
+    pub month: u32,
+    /// the day of the month, from 1 to 31
+    pub day: u32,
+}
+
+/// the value of an `<input type="time">`, which is formatted as `HH:MM`, `HH:MM:SS` or
+/// `HH:MM:SS.sss` depending on the `step` of the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InputTime {
+    /// the hour, from 0 to 23
+    pub hour: u32,
+    /// the minute, from 0 to 59
+    pub minute: u32,
+    /// the second, from 0 to 59, 0 when the value has no seconds
+    pub second: u32,
+    /// the milliseconds, from 0 to 999, 0 when the value has no fraction of seconds
+    pub millisecond: u32,
+}
+
+/// the value of an `<input type="datetime-local">`, which is formatted as `YYYY-MM-DDTHH:MM`
+/// with the optional seconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InputDateTime {
+    /// the date part of the value
+    pub date: InputDate,
+    /// the time part of the value
+    pub time: InputTime,
+}
+
+/// the value of the input can not be parsed, ie: the input is empty or is only partially filled
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid date or time value: {value:?}")]
+pub struct ParseInputDateError {
+    /// the value of the input as is
+    pub value: String,
+}
+
+impl ParseInputDateError {
+    fn new(value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+        }
+    }
+}
+
+/// parse a number which only has ascii digits, the number of digits must be within `len`
+fn parse_digits<T: FromStr>(s: &str, len: RangeInclusive<usize>) -> Option<T> {
+    if len.contains(&s.len()) && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl FromStr for InputDate {
+    type Err = ParseInputDateError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let err = || ParseInputDateError::new(value);
+        // the year can have more than 4 digits, so the month and day are taken from the end
+        let (rest, day) = value.rsplit_once('-').ok_or_else(err)?;
+        let (year, month) = rest.rsplit_once('-').ok_or_else(err)?;
+        let year: i32 = parse_digits(year, 4..=6).ok_or_else(err)?;
+        let month: u32 = parse_digits(month, 2..=2).ok_or_else(err)?;
+        let day: u32 = parse_digits(day, 2..=2).ok_or_else(err)?;
+        if year > 0 && (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day)
+        {
+            Ok(Self { year, month, day })
+        } else {
+            Err(err())
+        }
+    }
+}
+
+impl FromStr for InputTime {
+    type Err = ParseInputDateError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let err = || ParseInputDateError::new(value);
+        let (seconds, millisecond) = match value.split_once('.') {
+            Some((seconds, fraction)) => {
+                // `.5` is 500 milliseconds
+                let fraction_ms: u32 = parse_digits(fraction, 1..=3).ok_or_else(err)?;
+                (seconds, fraction_ms * 10_u32.pow(3 - fraction.len() as u32))
+            }
+            None => (value, 0),
+        };
+        let field = |part: &str, max: u32| parse_digits(part, 2..=2).filter(|n| *n <= max);
+        let parts: Vec<&str> = seconds.split(':').collect();
+        let (hour, minute, second) = match parts.as_slice() {
+            // the fraction of seconds can only be used with the seconds
+            [hour, minute] if seconds.len() == value.len() => (*hour, *minute, "00"),
+            [hour, minute, second] => (*hour, *minute, *second),
+            _ => return Err(err()),
+        };
+        Ok(Self {
+            hour: field(hour, 23).ok_or_else(err)?,
+            minute: field(minute, 59).ok_or_else(err)?,
+            second: field(second, 59).ok_or_else(err)?,
+            millisecond,
+        })
+    }
+}
+
+impl FromStr for InputDateTime {
+    type Err = ParseInputDateError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let err = || ParseInputDateError::new(value);
+        let (date, time) = value.split_once(['T', ' ']).ok_or_else(err)?;
+        Ok(Self {
+            date: date.parse().map_err(|_| err())?,
+            time: time.parse().map_err(|_| err())?,
+        })
+    }
+}
+
+impl fmt::Display for InputDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl fmt::Display for InputTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)?;
+        if self.second != 0 || self.millisecond != 0 {
+            write!(f, ":{:02}", self.second)?;
+        }
+        if self.millisecond != 0 {
+            write!(f, ".{:03}", self.millisecond)?;
+        }
+        Ok(())
+    }
+}
+
+/// formatted as the normalized value of the `datetime-local` input, which can be set back
+/// as the `value` of the input
+impl fmt::Display for InputDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}T{}", self.date, self.time)
+    }
+}
+
+/// the conversion into the `chrono` types, which fails when the fields of the value are
+/// set to an invalid date or time
+#[cfg(feature = "with-chrono")]
+mod chrono_conversion {
+    use super::{InputDate, InputDateTime, InputTime, ParseInputDateError};
+
+    impl TryFrom<InputDate> for chrono::NaiveDate {
+        type Error = ParseInputDateError;
+
+        fn try_from(date: InputDate) -> Result<Self, Self::Error> {
+            chrono::NaiveDate::from_ymd_opt(date.year, date.month, date.day)
+                .ok_or_else(|| ParseInputDateError::new(&date.to_string()))
+        }
+    }
+
+    impl TryFrom<InputTime> for chrono::NaiveTime {
+        type Error = ParseInputDateError;
+
+        fn try_from(time: InputTime) -> Result<Self, Self::Error> {
+            chrono::NaiveTime::from_hms_milli_opt(
+                time.hour,
+                time.minute,
+                time.second,
+                time.millisecond,
+            )
+            .ok_or_else(|| ParseInputDateError::new(&time.to_string()))
+        }
+    }
+
+    impl TryFrom<InputDateTime> for chrono::NaiveDateTime {
+        type Error = ParseInputDateError;
+
+        fn try_from(date_time: InputDateTime) -> Result<Self, Self::Error> {
+            Ok(chrono::NaiveDateTime::new(
+                date_time.date.try_into()?,
+                date_time.time.try_into()?,
+            ))
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! adopt the server rendered html as the DOM of the app, see [`MountAction::Hydrate`]
+//!
+//! [`MountAction::Hydrate`]: crate::dom::MountAction::Hydrate
+use crate::dom::dom_node::{append_safe_html, children_container, intern, unroll_fragments};
+use crate::dom::{document, Application, Program};
+use crate::render::DATA_VDOM_ID;
+use crate::vdom::{self, Leaf, NodeTrait};
+use std::collections::BTreeSet;
+use wasm_bindgen::JsCast;
+use web_sys::{CharacterData, DocumentFragment, Element, Node};
+
+impl<APP, MSG> Program<APP, MSG>
+where
+    MSG: 'static,
+    APP: Application<MSG>,
+{
+    /// adopt the children of the mount node, which are the server render of the same view, as
+    /// the DOM nodes of this vdom and return the root node.
+    ///
+    /// The nodes which don't match the vdom are created again, the attributes of the elements
+    /// which are not in the vdom are removed except those with the preserved prefix.
+    pub(crate) fn hydrate_mount_node(&self, mount_node: &Node, vnode: &vdom::Node<MSG>) -> Node {
+        // the nodes which are created again must not take the ids of the server rendered html
+        for vdom_id in server_vdom_ids(mount_node) {
+            self.app_context.node_ids.reserve(vdom_id);
+        }
+        match vnode {
+            // the children of a root fragment are in the mount node
+            vdom::Node::Fragment(nodes) | vdom::Node::NodeList(nodes) => {
+                self.hydrate_children(mount_node, &unroll_fragments(nodes));
+                document().create_document_fragment().into()
+            }
+            _ => {
+                self.hydrate_children(mount_node, &[vnode]);
+                mount_node
+                    .first_child()
+                    .expect("must have the hydrated root node")
+            }
+        }
+    }
+
+    /// adopt the children of this node as the DOM nodes of these virtual children,
+    /// the children are created again when they can not be matched
+    fn hydrate_children(&self, parent: &Node, vchildren: &[&vdom::Node<MSG>]) {
+        let container = children_container(parent);
+        // safe html can be parsed into any number of DOM nodes
+        if vchildren.iter().any(|vchild| vchild.is_safe_html()) {
+            self.recreate_children(parent, vchildren);
+            return;
+        }
+        let mut children = child_nodes(&container);
+        if children.len() != vchildren.len() {
+            // the new lines and indents of a pretty rendered html are not in the vdom
+            for child in children.iter().filter(|child| is_whitespace_text(child)) {
+                container.remove_child(child).expect("must remove child");
+            }
+            children = child_nodes(&container);
+        }
+        if children.len() != vchildren.len() {
+            self.recreate_children(parent, vchildren);
+            return;
+        }
+        for (vchild, child) in vchildren.iter().zip(children) {
+            self.hydrate_node(vchild, &child);
+        }
+    }
+
+    /// adopt this DOM node as the node of the vnode, or replace it with a created node when
+    /// they don't match
+    fn hydrate_node(&self, vnode: &vdom::Node<MSG>, node: &Node) {
+        match (vnode, node.dyn_ref::<Element>()) {
+            (vdom::Node::Element(velem), Some(element))
+                if element.local_name().eq_ignore_ascii_case(velem.tag()) =>
+            {
+                self.hydrate_element(velem, element);
+                Self::dispatch_mount_event(node);
+            }
+            (vdom::Node::Leaf(Leaf::Text(txt)), None) if node.node_type() == Node::TEXT_NODE => {
+                set_character_data(node, txt);
+            }
+            (vdom::Node::Leaf(Leaf::Comment(comment)), None)
+                if node.node_type() == Node::COMMENT_NODE =>
+            {
+                set_character_data(node, comment);
+            }
+            _ => {
+                let created_node = self.create_dom_node(vnode);
+                node.parent_node()
+                    .expect("must have a parent node")
+                    .replace_child(&created_node, node)
+                    .expect("must replace child");
+                Self::dispatch_mount_event(&created_node);
+            }
+        }
+    }
+
+    /// set the attributes and attach the event listeners of the velem to the server rendered
+    /// element, then adopt its children
+    fn hydrate_element(&self, velem: &vdom::Element<MSG>, element: &Element) {
+        let attrs =
+            mt_dom::merge_attributes_of_same_name(&velem.attributes().iter().collect::<Vec<_>>());
+        let attr_names: BTreeSet<&str> = attrs.iter().map(|attr| *attr.name()).collect();
+        let has_listeners = attrs
+            .iter()
+            .any(|attr| attr.value().iter().any(|v| v.as_event_listener().is_some()));
+        // the closures of the listeners are stored with the id the server emitted
+        if has_listeners {
+            self.adopt_vdom_id(element);
+        }
+        let preserved_prefix = self.mount_procedure.preserved_attribute_prefix;
+        for name in element
+            .get_attribute_names()
+            .iter()
+            .filter_map(|name| name.as_string())
+        {
+            let is_preserved =
+                (name == DATA_VDOM_ID && has_listeners) || name.starts_with(preserved_prefix);
+            if !is_preserved && !attr_names.contains(name.as_str()) {
+                element
+                    .remove_attribute(intern(&name))
+                    .expect("must remove attribute");
+            }
+        }
+        for attr in attrs.iter() {
+            self.set_element_attribute(element, attr);
+        }
+        self.hydrate_children(element, &unroll_fragments(velem.children()));
+    }
+
+    /// replace the children of this node with the nodes created from the virtual children
+    fn recreate_children(&self, parent: &Node, vchildren: &[&vdom::Node<MSG>]) {
+        let container = children_container(parent);
+        Self::clear_children(&container);
+        for vchild in vchildren {
+            if let Some(child_html) = vchild.as_safe_html() {
+                append_safe_html(&container, child_html);
+            } else {
+                let created_child = self.create_dom_node(vchild);
+                Self::append_child_and_dispatch_mount_event(&container, &created_child);
+            }
+        }
+    }
+}
+
+/// the `data-vdom-id` of the server rendered elements in this node,
+/// see [`RenderOptions::emit_vdom_id`]
+///
+/// [`RenderOptions::emit_vdom_id`]: crate::render::RenderOptions::emit_vdom_id
+fn server_vdom_ids(node: &Node) -> Vec<usize> {
+    let selector = format!("[{DATA_VDOM_ID}]");
+    let elements = if let Some(element) = node.dyn_ref::<Element>() {
+        element.query_selector_all(&selector)
+    } else if let Some(fragment) = node.dyn_ref::<DocumentFragment>() {
+        fragment.query_selector_all(&selector)
+    } else {
+        return vec![];
+    };
+    let Ok(elements) = elements else {
+        return vec![];
+    };
+    (0..elements.length())
+        .filter_map(|i| elements.item(i))
+        .filter_map(|node| node.unchecked_into::<Element>().get_attribute(DATA_VDOM_ID))
+        .filter_map(|vdom_id| vdom_id.parse::<usize>().ok())
+        .collect()
+}
+
+fn child_nodes(node: &Node) -> Vec<Node> {
+    let children = node.child_nodes();
+    (0..children.length())
+        .filter_map(|i| children.item(i))
+        .collect()
+}
+
+fn is_whitespace_text(node: &Node) -> bool {
+    node.node_type() == Node::TEXT_NODE
+        && node.text_content().is_some_and(|txt| txt.trim().is_empty())
+}
+
+/// set the text of the text or comment node, only when it is changed
+fn set_character_data(node: &Node, data: &str) {
+    let character_data: &CharacterData = node.unchecked_ref();
+    if character_data.data() != data {
+        character_data.set_data(data);
+    }
+}
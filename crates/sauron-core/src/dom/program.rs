@@ -1,17 +1,24 @@
+use crate::dom::dom_node::{detach_node_closures, find_autofocus, ListenerClosure};
+use crate::dom::events::{invalid_fields, InvalidField};
 use crate::dom::program::app_context::WeakContext;
-#[cfg(feature = "with-raf")]
 use crate::dom::request_animation_frame;
 #[cfg(feature = "with-ric")]
 use crate::dom::request_idle_callback;
 #[cfg(feature = "prediff")]
 use crate::dom::PreDiff;
-use crate::dom::{document, now, IdleDeadline, Measurements, Modifier};
-use crate::dom::{util::body, AnimationFrameHandle, Application, DomPatch, IdleCallbackHandle};
+use crate::dom::{delay, document, now, spawn_local, IdleDeadline, Measurements, Modifier};
+use crate::dom::{
+    util::body, AnimationFrameHandle, Application, DomPatch, IdleCallbackHandle, PatchError,
+};
+use crate::dom::{ScrollOptions, ScrollTarget};
 use crate::html::{self, attributes::class, text};
 use crate::vdom;
 use crate::vdom::diff;
+use crate::vdom::NodeTrait;
 use crate::vdom::KEY;
 use app_context::AppContext;
+pub(crate) use app_context::NodeIds;
+use error_boundary::ErrorBoundary;
 use mt_dom::{diff_recursive, TreePath};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::BTreeMap;
@@ -20,7 +27,7 @@ use std::hash::{Hash, Hasher};
 use std::mem::ManuallyDrop;
 use std::{
     any::TypeId,
-    cell::{Ref, RefCell, RefMut},
+    cell::{Cell, Ref, RefCell, RefMut},
     rc::Rc,
     rc::Weak,
 };
@@ -29,8 +36,12 @@ use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{self, Element, Node};
 
 mod app_context;
+mod error_boundary;
+mod error_handler;
 
-pub(crate) type EventClosures = Vec<Closure<dyn FnMut(web_sys::Event)>>;
+/// the closures of the event listeners attached to the window or document, along with
+/// the target and the event name they are attached to
+pub(crate) type EventClosures = Vec<(web_sys::EventTarget, &'static str, ListenerClosure)>;
 pub(crate) type Closures = Vec<Closure<dyn FnMut()>>;
 
 /// Program handle the lifecycle of the APP
@@ -51,7 +62,7 @@ where
     pub node_closures: Rc<RefCell<ActiveClosure>>,
 
     /// specify how the root node is mounted into the mount node
-    mount_procedure: MountProcedure,
+    pub(crate) mount_procedure: MountProcedure,
 
     /// Pending patches that hasn't been applied to the DOM yet
     /// for optimization purposes to avoid sluggishness of the app, when a patch
@@ -69,6 +80,19 @@ where
     /// generic closures that has no argument
     pub closures: Rc<RefCell<Closures>>,
     last_update: Rc<RefCell<Option<f64>>>,
+    /// whether to validate the vdom before patching
+    validate_vdom: Rc<Cell<bool>>,
+    /// whether to merge the adjacent text nodes of the vdom before patching
+    normalize_text: Rc<Cell<bool>>,
+    /// called with the patches computed in each update, see [`Program::on_patch`]
+    patch_listener: Rc<RefCell<Option<PatchListener<MSG>>>>,
+    /// the frame budget of the msgs dispatched with [`Program::batched_update`]
+    frame_batch: Rc<Cell<FrameBatch>>,
+    /// the msgs of [`Program::batched_update`] which are not dispatched yet, kept apart from
+    /// the pending msgs of the app so they are only dispatched within the frame budget
+    batched_msgs: Rc<RefCell<VecDeque<MSG>>>,
+    /// whether the changed text nodes are updated with only the changed characters
+    pub(crate) incremental_text: Rc<Cell<bool>>,
 }
 
 pub struct WeakProgram<APP, MSG>
@@ -86,15 +110,41 @@ where
     pub(crate) event_closures: Weak<RefCell<EventClosures>>,
     pub(crate) closures: Weak<RefCell<Closures>>,
     last_update: Weak<RefCell<Option<f64>>>,
+    validate_vdom: Weak<Cell<bool>>,
+    normalize_text: Weak<Cell<bool>>,
+    patch_listener: Weak<RefCell<Option<PatchListener<MSG>>>>,
+    frame_batch: Weak<Cell<FrameBatch>>,
+    batched_msgs: Weak<RefCell<VecDeque<MSG>>>,
+    incremental_text: Weak<Cell<bool>>,
 }
 
+/// a callback which is called with the patches computed in each update
+pub type PatchListener<MSG> = Box<dyn Fn(&[vdom::Patch<MSG>])>;
+
 /// Closures that we are holding on to to make sure that they don't get invalidated after a
 /// VirtualNode is dropped.
 ///
 /// The usize is a unique identifier that is associated with the DOM element that this closure is
 /// attached to.
-pub type ActiveClosure =
-    BTreeMap<usize, BTreeMap<&'static str, Closure<dyn FnMut(web_sys::Event)>>>;
+/// An event can have multiple closures when there are multiple listeners to the same event.
+pub type ActiveClosure = BTreeMap<usize, BTreeMap<&'static str, Vec<ListenerClosure>>>;
+
+/// the default time in milliseconds spent on dispatching the batched msgs in each frame,
+/// which leaves the rest of the 16ms frame for rendering and the browser
+pub const DEFAULT_FRAME_BUDGET: f64 = 8.0;
+
+/// the default prefix of the attributes which are kept on the server rendered elements
+/// when hydrating, see [`Program::with_preserved_attribute_prefix`]
+pub const DEFAULT_PRESERVED_ATTRIBUTE_PREFIX: &str = "data-ssr";
+
+/// the state of the msgs dispatched with [`Program::batched_update`]
+#[derive(Clone, Copy)]
+struct FrameBatch {
+    /// the time in milliseconds which the msgs are dispatched in each frame
+    budget: f64,
+    /// whether an animation frame is already requested for the batched msgs
+    scheduled: bool,
+}
 
 /// specify how the App is mounted to the DOM
 #[derive(Clone, Copy)]
@@ -105,6 +155,10 @@ pub enum MountAction {
     ClearAppend,
     /// replace the target mount node with the APP's root node
     Replace,
+    /// adopt the children of the target mount node, which are the server rendered html of the
+    /// same view, as the APP's nodes. The event listeners are attached to the existing elements
+    /// and only the nodes which don't match the view are created again.
+    Hydrate,
 }
 
 /// specify whether to attach the Node in shadow_root
@@ -118,9 +172,11 @@ pub enum MountTarget {
 
 /// specify how the root node will be mounted to the mount node
 #[derive(Clone, Copy)]
-struct MountProcedure {
+pub(crate) struct MountProcedure {
     action: MountAction,
     target: MountTarget,
+    /// the attributes with this prefix are kept on the elements when hydrating
+    pub(crate) preserved_attribute_prefix: &'static str,
 }
 
 impl<APP, MSG> WeakProgram<APP, MSG>
@@ -129,44 +185,25 @@ where
 {
     ///
     pub fn upgrade(&self) -> Option<Program<APP, MSG>> {
-        if let Some(app_context) = self.app_context.upgrade() {
-            if let Some(root_node) = self.root_node.upgrade() {
-                if let Some(mount_node) = self.mount_node.upgrade() {
-                    if let Some(node_closures) = self.node_closures.upgrade() {
-                        if let Some(pending_patches) = self.pending_patches.upgrade() {
-                            if let Some(idle_callback_handles) =
-                                self.idle_callback_handles.upgrade()
-                            {
-                                if let Some(animation_frame_handles) =
-                                    self.animation_frame_handles.upgrade()
-                                {
-                                    if let Some(event_closures) = self.event_closures.upgrade() {
-                                        if let Some(closures) = self.closures.upgrade() {
-                                            if let Some(last_update) = self.last_update.upgrade() {
-                                                return Some(Program {
-                                                    app_context,
-                                                    root_node,
-                                                    mount_node,
-                                                    node_closures,
-                                                    mount_procedure: self.mount_procedure,
-                                                    pending_patches,
-                                                    idle_callback_handles,
-                                                    animation_frame_handles,
-                                                    event_closures,
-                                                    closures,
-                                                    last_update,
-                                                });
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        None
+        Some(Program {
+            app_context: self.app_context.upgrade()?,
+            root_node: self.root_node.upgrade()?,
+            mount_node: self.mount_node.upgrade()?,
+            node_closures: self.node_closures.upgrade()?,
+            mount_procedure: self.mount_procedure,
+            pending_patches: self.pending_patches.upgrade()?,
+            idle_callback_handles: self.idle_callback_handles.upgrade()?,
+            animation_frame_handles: self.animation_frame_handles.upgrade()?,
+            event_closures: self.event_closures.upgrade()?,
+            closures: self.closures.upgrade()?,
+            last_update: self.last_update.upgrade()?,
+            validate_vdom: self.validate_vdom.upgrade()?,
+            normalize_text: self.normalize_text.upgrade()?,
+            patch_listener: self.patch_listener.upgrade()?,
+            frame_batch: self.frame_batch.upgrade()?,
+            batched_msgs: self.batched_msgs.upgrade()?,
+            incremental_text: self.incremental_text.upgrade()?,
+        })
     }
 }
 
@@ -187,6 +224,12 @@ where
             event_closures: Weak::clone(&self.event_closures),
             closures: Weak::clone(&self.closures),
             last_update: Weak::clone(&self.last_update),
+            validate_vdom: Weak::clone(&self.validate_vdom),
+            normalize_text: Weak::clone(&self.normalize_text),
+            patch_listener: Weak::clone(&self.patch_listener),
+            frame_batch: Weak::clone(&self.frame_batch),
+            batched_msgs: Weak::clone(&self.batched_msgs),
+            incremental_text: Weak::clone(&self.incremental_text),
         }
     }
 }
@@ -209,6 +252,12 @@ where
             event_closures: Rc::downgrade(&self.event_closures),
             closures: Rc::downgrade(&self.closures),
             last_update: Rc::downgrade(&self.last_update),
+            validate_vdom: Rc::downgrade(&self.validate_vdom),
+            normalize_text: Rc::downgrade(&self.normalize_text),
+            patch_listener: Rc::downgrade(&self.patch_listener),
+            frame_batch: Rc::downgrade(&self.frame_batch),
+            batched_msgs: Rc::downgrade(&self.batched_msgs),
+            incremental_text: Rc::downgrade(&self.incremental_text),
         }
     }
 }
@@ -230,6 +279,12 @@ where
             event_closures: Rc::clone(&self.event_closures),
             closures: Rc::clone(&self.closures),
             last_update: Rc::clone(&self.last_update),
+            validate_vdom: Rc::clone(&self.validate_vdom),
+            normalize_text: Rc::clone(&self.normalize_text),
+            patch_listener: Rc::clone(&self.patch_listener),
+            frame_batch: Rc::clone(&self.frame_batch),
+            batched_msgs: Rc::clone(&self.batched_msgs),
+            incremental_text: Rc::clone(&self.incremental_text),
         }
     }
 }
@@ -239,7 +294,25 @@ where
     MSG: 'static,
 {
     fn drop(&mut self) {
-        // program is dropped
+        // the clones of the program are dropped all the time,
+        // only the last handle of the program frees up its resources
+        if Rc::strong_count(&self.node_closures) > 1 {
+            return;
+        }
+        // the closures are dropped together with the program,
+        // the listeners must be detached so the DOM won't call the dropped closures
+        detach_node_closures(
+            &self.mount_node.borrow(),
+            &self.node_closures.borrow(),
+            &self.app_context.node_ids,
+        );
+        for (target, event_name, closure) in self.event_closures.borrow_mut().drain(..) {
+            if let Err(e) = closure.remove_from(&target, event_name) {
+                log::error!("unable to remove the event listener: {e:?}");
+            }
+        }
+        self.idle_callback_handles.borrow_mut().clear();
+        self.animation_frame_handles.borrow_mut().clear();
     }
 }
 
@@ -276,13 +349,384 @@ where
             root_node: Rc::new(RefCell::new(None)),
             mount_node: Rc::new(RefCell::new(mount_node.clone())),
             node_closures: Rc::new(RefCell::new(ActiveClosure::new())),
-            mount_procedure: MountProcedure { action, target },
+            mount_procedure: MountProcedure {
+                action,
+                target,
+                preserved_attribute_prefix: DEFAULT_PRESERVED_ATTRIBUTE_PREFIX,
+            },
             pending_patches: Rc::new(RefCell::new(VecDeque::new())),
             idle_callback_handles: Rc::new(RefCell::new(vec![])),
             animation_frame_handles: Rc::new(RefCell::new(vec![])),
             event_closures: Rc::new(RefCell::new(vec![])),
             closures: Rc::new(RefCell::new(vec![])),
             last_update: Rc::new(RefCell::new(None)),
+            validate_vdom: Rc::new(Cell::new(false)),
+            normalize_text: Rc::new(Cell::new(false)),
+            patch_listener: Rc::new(RefCell::new(None)),
+            frame_batch: Rc::new(Cell::new(FrameBatch {
+                budget: DEFAULT_FRAME_BUDGET,
+                scheduled: false,
+            })),
+            batched_msgs: Rc::new(RefCell::new(VecDeque::new())),
+            incremental_text: Rc::new(Cell::new(false)),
+        }
+    }
+
+    /// Validate each new view of the app before it is patched into the DOM,
+    /// and log the warnings of malformed nodes, such as children in a void element,
+    /// duplicate keys among siblings and text directly inside a table row.
+    ///
+    /// This is off by default since it traverses the whole view in every update.
+    pub fn with_validation(self, validate_vdom: bool) -> Self {
+        self.validate_vdom.set(validate_vdom);
+        self
+    }
+
+    /// Set the time in milliseconds spent on dispatching the msgs of
+    /// [`Program::batched_update`] in each animation frame, the default is
+    /// [`DEFAULT_FRAME_BUDGET`].
+    ///
+    /// A lower budget keeps the page more responsive, but takes more frames to dispatch
+    /// a flood of msgs.
+    pub fn with_frame_budget(self, budget: f64) -> Self {
+        let mut frame_batch = self.frame_batch.get();
+        frame_batch.budget = budget;
+        self.frame_batch.set(frame_batch);
+        self
+    }
+
+    /// Set fixed attributes, such as a `class` or data attributes, on the mount node.
+    /// The mount node is not part of the view of the app, so these attributes are not touched
+    /// by the diffs of the view.
+    ///
+    /// Note: this has no effect when mounted with [`MountAction::Replace`],
+    /// since the mount node is replaced by the view.
+    /// # Example
+    /// ```rust,ignore
+    /// let mut program = Program::new(App::default(), &mount, MountAction::Append, MountTarget::MountNode)
+    ///     .with_root_attributes([class("app-root"), attr("data-theme", "dark")]);
+    /// program.mount();
+    /// ```
+    pub fn with_root_attributes(
+        self,
+        attrs: impl IntoIterator<Item = vdom::Attribute<MSG>>,
+    ) -> Self {
+        let attrs: Vec<vdom::Attribute<MSG>> = attrs.into_iter().collect();
+        if let Some(mount_element) = self.mount_node.borrow().dyn_ref::<Element>() {
+            self.set_element_attributes(mount_element, &attrs.iter().collect::<Vec<_>>());
+        } else {
+            log::warn!("the mount node is not an element, the root attributes are not set");
+        }
+        self
+    }
+
+    /// register a callback which is called with the patches computed in each update,
+    /// ie: for logging, metrics or devtools.
+    /// This replaces the previously registered callback.
+    ///
+    /// Unlike the `log-patches` feature, this works on release builds
+    /// and nothing is done when no callback is registered.
+    /// # Example
+    /// ```rust,ignore
+    /// program.on_patch(|patches| log::info!("applying {} patches", patches.len()));
+    /// ```
+    pub fn on_patch<F>(&self, f: F)
+    where
+        F: Fn(&[vdom::Patch<MSG>]) + 'static,
+    {
+        *self.patch_listener.borrow_mut() = Some(Box::new(f));
+    }
+
+    /// call the registered patch listener with the patches
+    fn notify_patch_listener(&self, patches: &[vdom::Patch<MSG>]) {
+        if let Some(patch_listener) = self.patch_listener.borrow().as_ref() {
+            patch_listener(patches);
+        }
+    }
+
+    /// Catch the panics in the `view` and `update` of the app, keeping the program alive.
+    /// When the `view` panics, the node returned by `fallback` is rendered in its place.
+    /// The panic message is passed to `on_error` and the resulting msg is dispatched to the app,
+    /// only once until the view of the app renders successfully again, so an app which keeps
+    /// on panicking will not be stuck dispatching error msgs.
+    ///
+    /// Limitations:
+    ///  - the panics are caught with `std::panic::catch_unwind`, which needs unwinding.
+    ///    The `wasm32-unknown-unknown` target aborts on panic by default, so the app still
+    ///    dies there unless it is built with `-C panic=unwind` on a toolchain which supports
+    ///    unwinding in wasm. The panic hook, such as `console_error_panic_hook`, is still called.
+    ///  - the initial view is created in [`Program::new`], before the error boundary is set,
+    ///    so a panic in the initial view is not caught.
+    ///  - the state of the app may be left half updated by the panicking `update`.
+    /// # Example
+    /// ```rust,ignore
+    /// let mut program = Program::new(App::default(), &mount, MountAction::Append, MountTarget::MountNode)
+    ///     .with_error_boundary(|message| p([], [text!("something went wrong: {message}")]), Msg::Crashed);
+    /// program.mount();
+    /// ```
+    pub fn with_error_boundary<V, E>(self, fallback: V, on_error: E) -> Self
+    where
+        V: Fn(&str) -> vdom::Node<MSG> + 'static,
+        E: Fn(String) -> MSG + 'static,
+    {
+        *self.app_context.error_boundary.borrow_mut() =
+            Some(ErrorBoundary::new(fallback, on_error));
+        self
+    }
+
+    /// Map the errors of type `E` from the commands into a msg of the app, so the errors of
+    /// the effects are handled in one place instead of in each command.
+    /// This can be called once for each error type, the error of a type with no handler
+    /// is logged instead.
+    ///
+    /// The errors are routed here with [`Cmd::try_single`] or [`Program::handle_error`].
+    /// # Example
+    /// ```rust,ignore
+    /// let mut program = Program::new(App::default(), &mount, MountAction::Append, MountTarget::MountNode)
+    ///     .with_error_handler(|e: FetchError| Msg::Failed(e.to_string()));
+    /// program.mount();
+    /// ```
+    ///
+    /// [`Cmd::try_single`]: crate::dom::Cmd::try_single
+    pub fn with_error_handler<E, F>(self, f: F) -> Self
+    where
+        E: 'static,
+        F: Fn(E) -> MSG + 'static,
+    {
+        self.app_context.error_handlers.borrow_mut().push(f);
+        self
+    }
+
+    /// dispatch the msg which the error handler for this error type maps the `error` into,
+    /// see [`Program::with_error_handler`]
+    pub fn handle_error<E: 'static>(&mut self, error: E) {
+        let msg = self.app_context.error_handlers.borrow().handle(error);
+        if let Some(msg) = msg {
+            self.dispatch(msg);
+        }
+    }
+
+    /// Merge the adjacent text nodes of each new view of the app before it is patched into
+    /// the DOM, such as the texts of conditional `node_list` that are next to each other.
+    /// This keeps the number of text nodes in the DOM minimal, making the subsequent diffs and
+    /// patches lighter.
+    ///
+    /// This is off by default since it traverses the whole view in every update.
+    /// The paths from `Application::prediff` are computed from the view without merging,
+    /// so they are ignored and the whole view is diffed when this is on.
+    pub fn with_text_normalization(self, normalize_text: bool) -> Self {
+        self.normalize_text.set(normalize_text);
+        self
+    }
+
+    /// Update the changed text nodes with only the characters which changed, instead of
+    /// replacing the whole text node. The text node is kept in the DOM, so the selection
+    /// and the caret inside a large text, ie: in a collaborative editor, are not lost when
+    /// a small part of it changes.
+    ///
+    /// This is off by default since the characters of the old and new text are compared
+    /// in each change, which is wasteful for small texts.
+    pub fn with_incremental_text(self, incremental_text: bool) -> Self {
+        self.incremental_text.set(incremental_text);
+        self
+    }
+
+    /// Create a program with the supplied app as its initial model, without mounting it
+    /// into the document.
+    /// The program uses a detached element as its mount node, this is useful for testing
+    /// the view of the app for a given model.
+    /// # Example
+    /// ```rust,ignore
+    /// # use sauron::prelude::*;
+    /// struct App{ count: i32 }
+    /// # impl Application<()> for App{
+    /// #     fn view(&self) -> Node<()>{
+    /// #         text(self.count)
+    /// #     }
+    /// #     fn update(&mut self, _: ()) -> Cmd<Self, ()> {
+    /// #         Cmd::none()
+    /// #     }
+    /// # }
+    /// let program = Program::with_initial_model(App{ count: 42 });
+    /// assert_eq!("42", program.view().render_to_string());
+    /// ```
+    pub fn with_initial_model(app: APP) -> Self {
+        let detached: web_sys::Node = document()
+            .create_element("div")
+            .expect("must create element")
+            .into();
+        Self::new(app, &detached, MountAction::Append, MountTarget::MountNode)
+    }
+
+    /// execute the function in the next animation frame, at which point the pending patches
+    /// are already applied and the DOM reflects the latest view of the app
+    pub(crate) fn after_render<F>(&self, f: F)
+    where
+        F: FnOnce(Program<APP, MSG>) + 'static,
+    {
+        let program = Program::downgrade(&self);
+        let mut f = Some(f);
+        let handle = request_animation_frame(move || {
+            if let Some(mut program) = program.upgrade() {
+                if let Err(e) = program.apply_pending_patches() {
+                    log::error!("unable to apply the pending patches: {e}");
+                }
+                if let Some(f) = f.take() {
+                    f(program);
+                }
+            } else {
+                log::warn!("unable to upgrade program, in after_render");
+            }
+        })
+        .expect("must execute");
+        self.animation_frame_handles.borrow_mut().push(handle);
+    }
+
+    /// replace the running app with a new instance, ie: when hot reloading.
+    /// The mount node is preserved, the DOM is patched to reflect the view of the new app
+    /// and the event listeners are rewired to the listeners of the new view.
+    pub fn replace_app(&mut self, new_app: APP) -> Result<(), PatchError> {
+        *self.app_context.app.borrow_mut() = new_app;
+        self.update_dom(&Modifier::default(), None)?;
+        // apply the patches now, since the listeners are rewired on the patched DOM
+        self.apply_pending_patches()?;
+
+        let current_vdom = self.app_context.current_vdom();
+        let root_node = self.root_node.borrow().clone();
+        if let Some(root_node) = root_node {
+            // the children of a root fragment are moved into the mount node
+            let container = if root_node.node_type() == Node::DOCUMENT_FRAGMENT_NODE {
+                self.mount_node()
+            } else {
+                root_node
+            };
+            self.rewire_event_listeners(&current_vdom, &container)?;
+        }
+        Ok(())
+    }
+
+    /// unmount the app from the DOM and free up its resources, ie: when a page which hosts
+    /// multiple apps removes this app.
+    ///
+    /// The root node is removed from the DOM, the event listeners of the elements, the window
+    /// and the document are removed and their closures are dropped, and the pending patches
+    /// and scheduled callbacks are discarded.
+    /// When the root node of the app is a fragment, the children of the mount node are removed.
+    pub fn shutdown(&mut self) {
+        let root_node = self.root_node.borrow_mut().take();
+        if let Some(root_node) = root_node {
+            // the children of a root fragment are moved into the mount node
+            let nodes: Vec<Node> = if root_node.node_type() == Node::DOCUMENT_FRAGMENT_NODE {
+                let children = self.mount_node().child_nodes();
+                (0..children.length())
+                    .filter_map(|i| children.item(i))
+                    .collect()
+            } else {
+                vec![root_node]
+            };
+            // the rest of the resources are still freed up when a node can not be removed
+            for node in nodes {
+                if let Some(element) = node.dyn_ref::<Element>() {
+                    if let Err(e) = self.remove_event_listeners(element) {
+                        log::error!("unable to remove the event listeners: {e:?}");
+                    }
+                }
+                if let Some(parent) = node.parent_node() {
+                    if let Err(e) = parent.remove_child(&node) {
+                        log::error!("unable to remove the node: {e:?}");
+                    }
+                }
+            }
+        }
+        self.node_closures.borrow_mut().clear();
+        for (target, event_name, closure) in self.event_closures.borrow_mut().drain(..) {
+            if let Err(e) = closure.remove_from(&target, event_name) {
+                log::error!("unable to remove the event listener: {e:?}");
+            }
+        }
+        self.pending_patches.borrow_mut().clear();
+        self.idle_callback_handles.borrow_mut().clear();
+        self.animation_frame_handles.borrow_mut().clear();
+        self.closures.borrow_mut().clear();
+    }
+
+    /// return the view of the app at its current state
+    pub fn view(&self) -> vdom::Node<MSG> {
+        self.app_context.view()
+    }
+
+    /// Set the prefix of the attributes which are kept on the server rendered elements when the
+    /// app is mounted with [`MountAction::Hydrate`], the default is
+    /// [`DEFAULT_PRESERVED_ATTRIBUTE_PREFIX`].
+    ///
+    /// The other attributes of the server rendered elements which are not in the view are
+    /// removed, an empty prefix keeps all of them.
+    pub fn with_preserved_attribute_prefix(mut self, prefix: &'static str) -> Self {
+        self.mount_procedure.preserved_attribute_prefix = prefix;
+        self
+    }
+
+    /// check the validity of the form with this element id, which is in the view of the app,
+    /// and return its fields which fail their constraints, see [`events::invalid_fields`].
+    ///
+    /// The form is also looked up in the shadow root when the app is mounted in one.
+    ///
+    /// [`events::invalid_fields`]: crate::dom::events::invalid_fields
+    pub fn form_validity(&self, form_id: &str) -> Result<Vec<InvalidField>, JsValue> {
+        let mount_node = self.mount_node.borrow();
+        let form = if let Some(shadow_root) = mount_node.dyn_ref::<web_sys::ShadowRoot>() {
+            shadow_root.get_element_by_id(form_id)
+        } else {
+            document().get_element_by_id(form_id)
+        };
+        let form: web_sys::HtmlFormElement = form
+            .ok_or_else(|| JsValue::from_str(&format!("no element with id: {form_id}")))?
+            .dyn_into()
+            .map_err(|_| JsValue::from_str(&format!("`{form_id}` is not a form element")))?;
+        Ok(invalid_fields(&form))
+    }
+
+    /// scroll the `target` into view, ie: to reveal a newly added chat message.
+    /// The selector is matched against the elements in the view of the app, including the ones
+    /// in its shadow root when the app is mounted in one.
+    ///
+    /// Use [`Cmd::scroll_into_view`] to scroll an element of the view which is not rendered yet.
+    /// # Example
+    /// ```rust,ignore
+    /// program.scroll_into_view(
+    ///     "#messages > li:last-child",
+    ///     ScrollOptions::default().with_behavior(ScrollBehavior::Smooth),
+    /// )?;
+    /// ```
+    ///
+    /// [`Cmd::scroll_into_view`]: crate::dom::Cmd::scroll_into_view
+    pub fn scroll_into_view(
+        &self,
+        target: impl Into<ScrollTarget>,
+        options: ScrollOptions,
+    ) -> Result<(), JsValue> {
+        let element = match target.into() {
+            ScrollTarget::Element(element) => element,
+            ScrollTarget::Selector(selector) => {
+                self.query_selector(&selector)?.ok_or_else(|| {
+                    JsValue::from_str(&format!("no element matches the selector: {selector}"))
+                })?
+            }
+        };
+        element.scroll_into_view_with_scroll_into_view_options(&options.into());
+        Ok(())
+    }
+
+    /// the first element in the view of the app which matches this css selector,
+    /// including the ones in its shadow root when the app is mounted in one
+    pub(crate) fn query_selector(&self, selector: &str) -> Result<Option<Element>, JsValue> {
+        let mount_node = self.mount_node.borrow();
+        if let Some(element) = mount_node.dyn_ref::<Element>() {
+            element.query_selector(selector)
+        } else if let Some(fragment) = mount_node.dyn_ref::<web_sys::DocumentFragment>() {
+            fragment.query_selector(selector)
+        } else {
+            document().query_selector(selector)
         }
     }
 
@@ -387,6 +831,19 @@ where
         ManuallyDrop::new(program)
     }
 
+    /// adopt the server rendered html in the mount node as the view of the app,
+    /// see [`MountAction::Hydrate`]
+    pub fn hydrate_mount(app: APP, mount_node: &web_sys::Node) -> ManuallyDrop<Self> {
+        let mut program = Self::new(
+            app,
+            mount_node,
+            MountAction::Hydrate,
+            MountTarget::MountNode,
+        );
+        program.mount();
+        ManuallyDrop::new(program)
+    }
+
     /// clear the existing children of the mount before mounting the app
     pub fn clear_mount(app: APP, mount_node: &web_sys::Node) -> ManuallyDrop<Self> {
         Self::clear_append_to_mount(app, mount_node)
@@ -423,11 +880,10 @@ where
     }
 
     /// each element and it's descendant in the vdom is created into
-    /// an actual DOM node.
+    /// an actual DOM node, or adopted from the server rendered html when hydrating.
+    /// The first element with `autofocus` in the view is focused once it is mounted.
     pub fn mount(&mut self) {
         self.pre_mount();
-        let created_node = self.create_dom_node(&self.app_context.current_vdom());
-
         let mount_node: web_sys::Node = match self.mount_procedure.target {
             MountTarget::MountNode => self.mount_node.borrow().clone(),
             MountTarget::ShadowRoot => {
@@ -443,6 +899,15 @@ where
             }
         };
 
+        let created_node = match self.mount_procedure.action {
+            MountAction::Hydrate => {
+                self.hydrate_mount_node(&mount_node, &self.app_context.current_vdom())
+            }
+            _ => self.create_dom_node(&self.app_context.current_vdom()),
+        };
+        // look it up before it is mounted, since the children of a fragment are moved out of it
+        let autofocus = find_autofocus(&created_node);
+
         match self.mount_procedure.action {
             MountAction::Append => {
                 Self::append_child_and_dispatch_mount_event(&mount_node, &created_node);
@@ -459,8 +924,16 @@ where
                 Self::dispatch_mount_event(&created_node);
                 *self.mount_node.borrow_mut() = created_node.clone()
             }
+            // the hydrated nodes are already in the mount node
+            MountAction::Hydrate => (),
         }
         *self.root_node.borrow_mut() = Some(created_node);
+        // only the first autofocus element is focused, the same as the browser does
+        if let Some(autofocus) = autofocus {
+            if let Err(e) = autofocus.focus() {
+                log::error!("unable to focus the autofocus element: {e:?}");
+            }
+        }
         self.after_mounted();
     }
 
@@ -506,9 +979,15 @@ where
     /// execute DOM changes in order to reflect the APP's view into the browser representation
     fn dispatch_dom_changes(&mut self, modifier: &Modifier, treepath: Option<Vec<TreePath>>) {
         #[allow(unused_variables)]
-        let measurements = self
-            .update_dom(modifier, treepath)
-            .expect("must update dom");
+        let measurements = match self.update_dom(modifier, treepath) {
+            Ok(measurements) => measurements,
+            Err(e) => {
+                // the DOM has drifted from the vdom, ie: it is modified outside of the program.
+                // Log it and keep the app running instead of aborting the wasm module.
+                log::error!("unable to update the dom: {e:?}");
+                return;
+            }
+        };
 
         #[cfg(feature = "with-measure")]
         // tell the app about the performance measurement and only if there was patches applied
@@ -527,14 +1006,17 @@ where
         let t1 = now();
         // a new view is created due to the app update
         let view = self.app_context.view();
+        // the error boundary queues its error msg when the view panicked
+        if self.app_context.has_pending_msgs() {
+            self.dispatch_inner_with_priority_ric();
+        }
         let t2 = now();
 
-        let node_count = view.node_count();
+        // counted with an explicit stack, so a very deep view doesn't overflow the stack
+        let node_count = view.count_nodes();
 
         // update the last DOM node tree with this new view
-        let total_patches = self
-            .update_dom_with_vdom(view, treepath)
-            .expect("must not error");
+        let total_patches = self.update_dom_with_vdom(view, treepath)?;
         let t3 = now();
         if let Some(last_update) = self.last_update.borrow().as_ref() {
             let frame_time = (1000_f64 / 60_f64).floor(); // 1s in 60 frames
@@ -577,8 +1059,20 @@ where
         &mut self,
         new_vdom: vdom::Node<MSG>,
         treepath: Option<Vec<TreePath>>,
-    ) -> Result<usize, JsValue> {
-        let dom_patches = self.create_dom_patch(&new_vdom, treepath);
+    ) -> Result<usize, PatchError> {
+        let new_vdom = self.normalize_vdom(new_vdom);
+        // the paths are of the view before its text nodes are merged
+        let treepath = if self.normalize_text.get() {
+            None
+        } else {
+            treepath
+        };
+        if self.validate_vdom.get() {
+            for warning in vdom::validate(&new_vdom) {
+                log::warn!("{warning}");
+            }
+        }
+        let dom_patches = self.create_dom_patch(&new_vdom, treepath)?;
         let total_patches = dom_patches.len();
         self.pending_patches.borrow_mut().extend(dom_patches);
 
@@ -586,17 +1080,134 @@ where
         self.apply_pending_patches_with_raf().expect("raf");
 
         #[cfg(not(feature = "with-raf"))]
-        self.apply_pending_patches().expect("raf");
+        self.apply_pending_patches()?;
 
         self.app_context.set_current_dom(new_vdom);
         Ok(total_patches)
     }
 
+    /// replace the view of the app with `new_view` which is not from the `view` of the app,
+    /// ie: for imperative screen transitions.
+    ///
+    /// The `new_view` is diffed against the current view and the patches are applied right
+    /// away, the listeners of the `new_view` are attached and the listeners of the removed
+    /// elements are dropped.
+    ///
+    /// Note: the next update of the app renders its `view` again, which replaces the `new_view`.
+    pub fn replace_view(&mut self, new_view: vdom::Node<MSG>) -> Result<usize, PatchError> {
+        let total_patches = self.update_dom_with_vdom(new_view, None)?;
+        // apply the patches now instead of on the next animation frame
+        self.apply_pending_patches()?;
+        Ok(total_patches)
+    }
+
+    /// patch only the subtree of the element with this `id` to reflect the `new_subtree`,
+    /// instead of diffing the whole view of the app.
+    /// This is useful for large apps where only a small part of the view changes frequently.
+    ///
+    /// The `new_subtree` is diffed against the old subtree only, so the paths of the patches
+    /// are relative to the subtree root and are then rebased to the path of the subtree root.
+    /// The siblings and ancestors of the subtree are not touched.
+    ///
+    /// Note: the view of the app must also reflect the `new_subtree`, otherwise the next
+    /// full update will revert it.
+    pub fn patch_subtree(
+        &mut self,
+        id: &str,
+        new_subtree: vdom::Node<MSG>,
+    ) -> Result<usize, PatchError> {
+        let subtree_path = vdom::path_of_id(&self.app_context.current_vdom(), id)
+            .ok_or_else(|| PatchError::MissingSubtree { id: id.to_string() })?;
+        let dom_patches = {
+            let current_vdom = self.app_context.current_vdom();
+            let old_subtree =
+                vdom::node_at_path(&current_vdom, &subtree_path).expect("must have the subtree");
+            let rebase = |path: &mut TreePath| {
+                path.path.splice(0..0, subtree_path.iter().copied());
+            };
+            let patches: Vec<vdom::Patch<MSG>> = diff(old_subtree, &new_subtree)
+                .into_iter()
+                .map(|mut patch| {
+                    rebase(&mut patch.patch_path);
+                    match &mut patch.patch_type {
+                        vdom::PatchType::MoveBeforeNode { nodes_path }
+                        | vdom::PatchType::MoveAfterNode { nodes_path } => {
+                            nodes_path.iter_mut().for_each(rebase);
+                        }
+                        _ => (),
+                    }
+                    patch
+                })
+                .collect();
+            self.notify_patch_listener(&patches);
+            self.convert_patches(&patches)?
+        };
+        let total_patches = dom_patches.len();
+        self.pending_patches.borrow_mut().extend(dom_patches);
+        self.apply_pending_patches()?;
+
+        let mut current_vdom = self.app_context.current_vdom.borrow_mut();
+        *vdom::node_at_path_mut(&mut current_vdom, &subtree_path).expect("must have the subtree") =
+            new_subtree;
+        Ok(total_patches)
+    }
+
+    /// patch the DOM to reflect the new vdom, the same as [`Program::update_dom_with_vdom`]
+    /// except the patches are applied in chunks of `chunk_size` and yields to the event loop
+    /// in between chunks, so a huge update will not block the main thread.
+    ///
+    /// The patches are still applied in order. The app should not be updated while the chunked
+    /// update is in progress, since the patches of the next update is created from the DOM
+    /// which is not fully patched yet.
+    pub async fn update_dom_with_vdom_chunked(
+        &mut self,
+        new_vdom: vdom::Node<MSG>,
+        chunk_size: usize,
+    ) -> Result<usize, PatchError> {
+        assert!(chunk_size > 0, "chunk_size must be greater than 0");
+        let new_vdom = self.normalize_vdom(new_vdom);
+        if self.validate_vdom.get() {
+            for warning in vdom::validate(&new_vdom) {
+                log::warn!("{warning}");
+            }
+        }
+        let dom_patches = self.create_dom_patch(&new_vdom, None)?;
+        let total_patches = dom_patches.len();
+        self.pending_patches.borrow_mut().extend(dom_patches);
+
+        loop {
+            let chunk: Vec<DomPatch<MSG>> = {
+                let mut pending_patches = self.pending_patches.borrow_mut();
+                let chunk_len = chunk_size.min(pending_patches.len());
+                pending_patches.drain(..chunk_len).collect()
+            };
+            if chunk.is_empty() {
+                break;
+            }
+            for dom_patch in chunk {
+                self.apply_dom_patch(dom_patch)?;
+            }
+            // yield to the event loop
+            delay(0).await;
+        }
+        // the new vdom is only the current vdom once the DOM is fully patched to it
+        self.app_context.set_current_dom(new_vdom);
+        Ok(total_patches)
+    }
+
+    fn normalize_vdom(&self, new_vdom: vdom::Node<MSG>) -> vdom::Node<MSG> {
+        if self.normalize_text.get() {
+            vdom::normalize_text(new_vdom)
+        } else {
+            new_vdom
+        }
+    }
+
     fn create_dom_patch(
         &self,
         new_vdom: &vdom::Node<MSG>,
         treepath: Option<Vec<TreePath>>,
-    ) -> Vec<DomPatch<MSG>> {
+    ) -> Result<Vec<DomPatch<MSG>>, PatchError> {
         let current_vdom = self.app_context.current_vdom();
         let patches = if let Some(treepath) = treepath {
             log::debug!("using treepath from pre_eval: {treepath:?}");
@@ -628,10 +1239,8 @@ where
             log::debug!("There are {} patches", patches.len());
             log::debug!("patches: {patches:#?}");
         }
-        let dom_patches = self
-            .convert_patches(&patches)
-            .expect("must convert patches");
-        dom_patches
+        self.notify_patch_listener(&patches);
+        self.convert_patches(&patches)
     }
 
     #[cfg(feature = "with-raf")]
@@ -639,7 +1248,9 @@ where
         let program = Program::downgrade(&self);
         let handle = request_animation_frame(move || {
             let mut program = program.upgrade().expect("must upgrade");
-            program.apply_pending_patches().expect("must not error");
+            if let Err(e) = program.apply_pending_patches() {
+                log::error!("unable to apply the pending patches: {e}");
+            }
         })
         .expect("must execute");
         self.animation_frame_handles.borrow_mut().push(handle);
@@ -647,14 +1258,13 @@ where
     }
 
     /// apply the pending patches into the DOM
-    fn apply_pending_patches(&mut self) -> Result<(), JsValue> {
+    fn apply_pending_patches(&mut self) -> Result<(), PatchError> {
         if self.pending_patches.borrow().is_empty() {
             return Ok(());
         }
         let dom_patches: Vec<DomPatch<MSG>> = self.pending_patches.borrow_mut().drain(..).collect();
         for dom_patch in dom_patches {
-            self.apply_dom_patch(dom_patch)
-                .expect("must apply dom patch");
+            self.apply_dom_patch(dom_patch)?;
         }
         Ok(())
     }
@@ -773,8 +1383,9 @@ where
 
         // Ensure all pending patches are applied before emiting the Cmd from update
         if !self.pending_patches.borrow().is_empty() {
-            self.apply_pending_patches()
-                .expect("applying pending patches..");
+            if let Err(e) = self.apply_pending_patches() {
+                log::error!("unable to apply the pending patches: {e}");
+            }
         }
 
         if !self.pending_patches.borrow().is_empty() {
@@ -821,4 +1432,124 @@ where
     pub fn dispatch(&mut self, msg: MSG) {
         self.dispatch_multiple([msg])
     }
+
+    /// dispatch the msgs in animation frames, for apps which receive a flood of msgs
+    /// such as from a websocket.
+    ///
+    /// In each frame, the msgs are dispatched to the app until the frame budget set with
+    /// [`Program::with_frame_budget`] is used up, then the view is rendered once for all of
+    /// them. The remaining msgs are deferred to the next frames, so the page stays responsive.
+    /// At least one msg is dispatched in each frame, so all of the msgs are eventually
+    /// dispatched even when a single update takes longer than the budget.
+    /// # Example
+    /// ```rust,ignore
+    /// program.batched_update(ticks.into_iter().map(Msg::Tick));
+    /// ```
+    pub fn batched_update(&mut self, msgs: impl IntoIterator<Item = MSG>) {
+        self.batched_msgs.borrow_mut().extend(msgs);
+        self.request_batched_frame();
+    }
+
+    /// request an animation frame to dispatch the batched msgs, if not yet requested
+    fn request_batched_frame(&self) {
+        let mut frame_batch = self.frame_batch.get();
+        if frame_batch.scheduled {
+            return;
+        }
+        let program = Program::downgrade(self);
+        let handle = request_animation_frame(move || {
+            if let Some(mut program) = program.upgrade() {
+                program.dispatch_batched_frame();
+            }
+        });
+        match handle {
+            Ok(handle) => {
+                self.animation_frame_handles.borrow_mut().push(handle);
+                frame_batch.scheduled = true;
+                self.frame_batch.set(frame_batch);
+            }
+            Err(e) => log::error!("unable to request animation frame: {e:?}"),
+        }
+    }
+
+    /// dispatch the batched msgs within the frame budget and render the view once
+    fn dispatch_batched_frame(&mut self) {
+        let mut frame_batch = self.frame_batch.get();
+        frame_batch.scheduled = false;
+        self.frame_batch.set(frame_batch);
+
+        let start = now();
+        loop {
+            let Some(msg) = self.batched_msgs.borrow_mut().pop_front() else {
+                break;
+            };
+            let cmd = self.app_context.update_app(msg);
+            self.app_context.pending_cmds.borrow_mut().push_back(cmd);
+            if now() - start >= frame_batch.budget {
+                break;
+            }
+        }
+
+        let cmd = self.app_context.batch_pending_cmds();
+        if cmd.modifier.should_update_view {
+            self.dispatch_dom_changes(&cmd.modifier, None);
+        }
+        if let Err(e) = self.apply_pending_patches() {
+            log::error!("unable to apply the pending patches: {e}");
+        }
+        cmd.emit(self.clone());
+
+        if !self.batched_msgs.borrow().is_empty() {
+            self.request_batched_frame();
+        }
+    }
+
+    /// dispatch the msg after `timeout` milliseconds, without creating a `Cmd` or `Task`.
+    /// The msg is dropped if the program has been shut down or dropped in the meantime.
+    /// # Example
+    /// ```rust,ignore
+    /// program.dispatch_after(2000, Msg::HideToast);
+    /// ```
+    pub fn dispatch_after(&self, timeout: i32, msg: MSG) {
+        let program = Program::downgrade(self);
+        spawn_local(async move {
+            delay(timeout).await;
+            let Some(mut program) = program.upgrade() else {
+                return;
+            };
+            // the root node is taken out when the program is shut down
+            if program.root_node.borrow().is_none() {
+                log::debug!("the program has been shut down, the delayed msg is dropped");
+                return;
+            }
+            program.dispatch(msg);
+        });
+    }
+
+    /// execute the function in a microtask, which runs as soon as the current update is done
+    /// but before the browser paints, unlike the animation frame used in rendering.
+    /// The pending patches are applied first, so the DOM reflects the latest view of the app,
+    /// ie: to measure the elements or to move the focus before the user sees the change.
+    /// The function is not called if the program has been dropped in the meantime.
+    /// # Example
+    /// ```rust,ignore
+    /// program.dispatch(Msg::OpenDialog);
+    /// program.queue_microtask(|program| focus_first_field(&program.mount_node()));
+    /// ```
+    pub fn queue_microtask<F>(&self, f: F)
+    where
+        F: FnOnce(Program<APP, MSG>) + 'static,
+    {
+        let program = Program::downgrade(self);
+        let callback = Closure::once_into_js(move || {
+            let Some(mut program) = program.upgrade() else {
+                return;
+            };
+            if let Err(e) = program.apply_pending_patches() {
+                log::error!("unable to apply the pending patches: {e}");
+            }
+            f(program);
+        });
+        crate::dom::window().queue_microtask(callback.unchecked_ref());
+    }
 }
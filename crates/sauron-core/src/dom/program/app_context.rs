@@ -1,9 +1,12 @@
+use super::error_boundary::ErrorBoundary;
+use super::error_handler::ErrorHandlers;
 #[cfg(feature = "with-measure")]
 use crate::dom::Measurements;
 use crate::dom::{Application, Cmd};
+use crate::render::FIRST_VDOM_ID;
 use crate::vdom;
 use std::{
-    cell::{Ref, RefCell},
+    cell::{Cell, Ref, RefCell},
     collections::VecDeque,
     rc::Rc,
     rc::Weak,
@@ -29,6 +32,15 @@ where
 
     /// pending cmds that hasn't been emited yet
     pub(crate) pending_cmds: Rc<RefCell<VecDeque<Cmd<APP, MSG>>>>,
+
+    /// catches the panics in the view and update of the app, when set
+    pub(crate) error_boundary: Rc<RefCell<Option<ErrorBoundary<MSG>>>>,
+
+    /// maps the errors of the commands into msgs
+    pub(crate) error_handlers: Rc<RefCell<ErrorHandlers<MSG>>>,
+
+    /// assigns the `data-vdom-id` of the elements with event listeners
+    pub(crate) node_ids: Rc<NodeIds>,
 }
 
 pub(crate) struct WeakContext<APP, MSG>
@@ -39,6 +51,52 @@ where
     pub(crate) current_vdom: Weak<RefCell<vdom::Node<MSG>>>,
     pub(crate) pending_msgs: Weak<RefCell<VecDeque<MSG>>>,
     pub(crate) pending_cmds: Weak<RefCell<VecDeque<Cmd<APP, MSG>>>>,
+    pub(crate) error_boundary: Weak<RefCell<Option<ErrorBoundary<MSG>>>>,
+    pub(crate) error_handlers: Weak<RefCell<ErrorHandlers<MSG>>>,
+    pub(crate) node_ids: Weak<NodeIds>,
+}
+
+thread_local!(static PROGRAM_ID_COUNTER: Cell<usize> = const { Cell::new(0) });
+
+/// The `data-vdom-id` of the elements are assigned per program, starting from the same
+/// [`FIRST_VDOM_ID`] as the server render, so the apps on the same page can have the same ids.
+/// Each program is also numbered, the elements are marked with the number of the program which
+/// assigned their id, so an app nested in the DOM of another app never has its closures
+/// looked up or detached by the outer app.
+pub(crate) struct NodeIds {
+    /// the number of the program which owns these ids
+    pub(crate) program_id: usize,
+    /// the id assigned to the next element
+    next_vdom_id: Cell<usize>,
+}
+
+impl NodeIds {
+    fn new() -> Self {
+        let program_id = PROGRAM_ID_COUNTER.with(|x| {
+            let tmp = x.get();
+            x.set(tmp + 1);
+            tmp
+        });
+        Self {
+            program_id,
+            next_vdom_id: Cell::new(FIRST_VDOM_ID),
+        }
+    }
+
+    /// assign a new id
+    pub(crate) fn create(&self) -> usize {
+        let vdom_id = self.next_vdom_id.get();
+        self.next_vdom_id.set(vdom_id + 1);
+        vdom_id
+    }
+
+    /// reserve an id which is already in the DOM, ie: from a server rendered html,
+    /// so that it will not be assigned again to the elements created afterwards
+    pub(crate) fn reserve(&self, vdom_id: usize) {
+        if self.next_vdom_id.get() <= vdom_id {
+            self.next_vdom_id.set(vdom_id + 1);
+        }
+    }
 }
 
 impl<APP, MSG> WeakContext<APP, MSG>
@@ -46,21 +104,15 @@ where
     MSG: 'static,
 {
     pub(crate) fn upgrade(&self) -> Option<AppContext<APP, MSG>> {
-        if let Some(app) = self.app.upgrade() {
-            if let Some(current_vdom) = self.current_vdom.upgrade() {
-                if let Some(pending_msgs) = self.pending_msgs.upgrade() {
-                    if let Some(pending_cmds) = self.pending_cmds.upgrade() {
-                        return Some(AppContext {
-                            app,
-                            current_vdom,
-                            pending_msgs,
-                            pending_cmds,
-                        });
-                    }
-                }
-            }
-        }
-        None
+        Some(AppContext {
+            app: self.app.upgrade()?,
+            current_vdom: self.current_vdom.upgrade()?,
+            pending_msgs: self.pending_msgs.upgrade()?,
+            pending_cmds: self.pending_cmds.upgrade()?,
+            error_boundary: self.error_boundary.upgrade()?,
+            error_handlers: self.error_handlers.upgrade()?,
+            node_ids: self.node_ids.upgrade()?,
+        })
     }
 }
 
@@ -74,6 +126,9 @@ where
             current_vdom: Weak::clone(&self.current_vdom),
             pending_msgs: Weak::clone(&self.pending_msgs),
             pending_cmds: Weak::clone(&self.pending_cmds),
+            error_boundary: Weak::clone(&self.error_boundary),
+            error_handlers: Weak::clone(&self.error_handlers),
+            node_ids: Weak::clone(&self.node_ids),
         }
     }
 }
@@ -88,6 +143,9 @@ where
             current_vdom: Rc::downgrade(&this.current_vdom),
             pending_msgs: Rc::downgrade(&this.pending_msgs),
             pending_cmds: Rc::downgrade(&this.pending_cmds),
+            error_boundary: Rc::downgrade(&this.error_boundary),
+            error_handlers: Rc::downgrade(&this.error_handlers),
+            node_ids: Rc::downgrade(&this.node_ids),
         }
     }
     pub fn strong_count(&self) -> usize {
@@ -114,6 +172,9 @@ where
             current_vdom: Rc::clone(&self.current_vdom),
             pending_msgs: Rc::clone(&self.pending_msgs),
             pending_cmds: Rc::clone(&self.pending_cmds),
+            error_boundary: Rc::clone(&self.error_boundary),
+            error_handlers: Rc::clone(&self.error_handlers),
+            node_ids: Rc::clone(&self.node_ids),
         }
     }
 }
@@ -130,14 +191,36 @@ where
             current_vdom: Rc::new(RefCell::new(view)),
             pending_msgs: Rc::new(RefCell::new(VecDeque::new())),
             pending_cmds: Rc::new(RefCell::new(VecDeque::new())),
+            error_boundary: Rc::new(RefCell::new(None)),
+            error_handlers: Rc::new(RefCell::new(ErrorHandlers::default())),
+            node_ids: Rc::new(NodeIds::new()),
         }
     }
     pub fn init_app(&self) -> Cmd<APP, MSG> {
         self.app.borrow_mut().init()
     }
 
+    /// the view of the app, or the fallback view of the error boundary when the view panics.
+    /// The error msg is queued in the pending msgs when the view panics.
     pub fn view(&self) -> vdom::Node<MSG> {
-        self.app.borrow().view()
+        let error_boundary = self.error_boundary.borrow();
+        let Some(error_boundary) = error_boundary.as_ref() else {
+            return self.app.borrow().view();
+        };
+        match ErrorBoundary::catch(|| self.app.borrow().view()) {
+            Ok(view) => {
+                error_boundary.recovered();
+                view
+            }
+            Err(message) => {
+                log::error!("the view of the app panicked: {message}");
+                let fallback = error_boundary.fallback(&message);
+                if let Some(error_msg) = error_boundary.error_msg(message) {
+                    self.pending_msgs.borrow_mut().push_back(error_msg);
+                }
+                fallback
+            }
+        }
     }
     pub fn dynamic_style(&self) -> String {
         self.app.borrow().style().join("")
@@ -148,7 +231,8 @@ where
     }
 
     pub fn set_current_dom(&mut self, new_vdom: vdom::Node<MSG>) {
-        *self.current_vdom.borrow_mut() = new_vdom;
+        let old_vdom = std::mem::replace(&mut *self.current_vdom.borrow_mut(), new_vdom);
+        vdom::drop_without_recursion(old_vdom);
     }
 
     pub fn current_vdom(&self) -> Ref<'_, vdom::Node<MSG>> {
@@ -165,7 +249,17 @@ where
     }
 
     pub fn update_app(&mut self, msg: MSG) -> Cmd<APP, MSG> {
-        self.app.borrow_mut().update(msg)
+        let error_boundary = self.error_boundary.borrow();
+        let Some(error_boundary) = error_boundary.as_ref() else {
+            return self.app.borrow_mut().update(msg);
+        };
+        match ErrorBoundary::catch(|| self.app.borrow_mut().update(msg)) {
+            Ok(cmd) => cmd,
+            Err(message) => {
+                log::error!("the update of the app panicked: {message}");
+                Cmd::batch_msg(error_boundary.error_msg(message))
+            }
+        }
     }
 
     /// return true if there are still pending msgs
@@ -204,3 +298,89 @@ where
         Cmd::batch(self.pending_cmds.borrow_mut().drain(..))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::html::*;
+    use crate::render::Render;
+
+    enum Msg {
+        Break,
+        Fix,
+        Failed(String),
+    }
+
+    #[derive(Default)]
+    struct App {
+        broken: bool,
+        errors: Vec<String>,
+    }
+
+    impl Application<Msg> for App {
+        fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+            match msg {
+                Msg::Break => self.broken = true,
+                Msg::Fix => self.broken = false,
+                Msg::Failed(message) => self.errors.push(message),
+            }
+            Cmd::none()
+        }
+
+        fn view(&self) -> vdom::Node<Msg> {
+            if self.broken {
+                panic!("the view is broken");
+            }
+            p([], [text("all good")])
+        }
+    }
+
+    fn app_context() -> AppContext<App, Msg> {
+        let app_context = AppContext::new(App::default());
+        *app_context.error_boundary.borrow_mut() = Some(ErrorBoundary::new(
+            |message| p([], [text(format!("fallback: {message}"))]),
+            Msg::Failed,
+        ));
+        app_context
+    }
+
+    #[test]
+    fn fallback_is_rendered_when_the_view_panics() {
+        let mut app_context = app_context();
+        let _ = app_context.update_app(Msg::Break);
+
+        let view = app_context.view();
+        assert_eq!(
+            view.render_to_string(),
+            "<p>fallback: the view is broken</p>"
+        );
+        assert_eq!(app_context.pending_msgs_count(), 1);
+
+        // the error msg is only queued once until the view recovers
+        let _ = app_context.view();
+        assert_eq!(app_context.pending_msgs_count(), 1);
+
+        assert!(app_context.dispatch_pending_msg());
+        assert_eq!(app_context.app.borrow().errors, ["the view is broken"]);
+
+        let _ = app_context.update_app(Msg::Fix);
+        assert_eq!(app_context.view().render_to_string(), "<p>all good</p>");
+    }
+
+    #[test]
+    fn node_ids_are_assigned_per_program() {
+        let first = app_context();
+        let second = app_context();
+        assert_ne!(first.node_ids.program_id, second.node_ids.program_id);
+
+        assert_eq!(first.node_ids.create(), FIRST_VDOM_ID);
+        assert_eq!(second.node_ids.create(), FIRST_VDOM_ID);
+
+        // the ids of the server rendered html are not assigned again
+        first.node_ids.reserve(5);
+        assert_eq!(first.node_ids.create(), 6);
+        first.node_ids.reserve(2);
+        assert_eq!(first.node_ids.create(), 7);
+        assert_eq!(second.node_ids.create(), FIRST_VDOM_ID + 1);
+    }
+}
@@ -0,0 +1,69 @@
+//! catch the panics in the `view` and `update` of the app, see [`Program::with_error_boundary`]
+//!
+//! [`Program::with_error_boundary`]: crate::dom::Program::with_error_boundary
+use crate::vdom;
+use std::any::Any;
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+
+/// renders a fallback view and creates a msg when the `view` or `update` of the app panics
+pub(crate) struct ErrorBoundary<MSG> {
+    /// the view which is displayed instead of the view of the app which panicked
+    fallback: Box<dyn Fn(&str) -> vdom::Node<MSG>>,
+    /// the msg which is dispatched to the app with the panic message
+    on_error: Box<dyn Fn(String) -> MSG>,
+    /// set when the app panicked and is cleared when the view of the app is rendered again,
+    /// the error msg is not created again in the meantime, otherwise an app which still
+    /// panics would be stuck dispatching error msgs
+    failed: Cell<bool>,
+}
+
+impl<MSG> ErrorBoundary<MSG> {
+    pub(crate) fn new<V, E>(fallback: V, on_error: E) -> Self
+    where
+        V: Fn(&str) -> vdom::Node<MSG> + 'static,
+        E: Fn(String) -> MSG + 'static,
+    {
+        Self {
+            fallback: Box::new(fallback),
+            on_error: Box::new(on_error),
+            failed: Cell::new(false),
+        }
+    }
+
+    /// call `f`, if it panics the panic message is returned instead
+    pub(crate) fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+        panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message)
+    }
+
+    /// the fallback view for this panic message
+    pub(crate) fn fallback(&self, message: &str) -> vdom::Node<MSG> {
+        (self.fallback)(message)
+    }
+
+    /// the msg which tells the app about this panic message,
+    /// None if the app has already been told about the previous panic and has not recovered yet
+    pub(crate) fn error_msg(&self, message: String) -> Option<MSG> {
+        if self.failed.replace(true) {
+            None
+        } else {
+            Some((self.on_error)(message))
+        }
+    }
+
+    /// the view of the app is rendered successfully
+    pub(crate) fn recovered(&self) {
+        self.failed.set(false);
+    }
+}
+
+/// the message of the panic, which is either a `&str` or a `String`
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
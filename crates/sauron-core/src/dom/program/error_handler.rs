@@ -0,0 +1,72 @@
+//! map the errors of the commands into msgs, see [`Program::with_error_handler`]
+//!
+//! [`Program::with_error_handler`]: crate::dom::Program::with_error_handler
+use std::any::{self, Any};
+
+/// a handler which maps an error of a specific type into a msg,
+/// the error is given back when it is not of that type
+type Handler<MSG> = Box<dyn Fn(Box<dyn Any>) -> Result<MSG, Box<dyn Any>>>;
+
+/// the error handlers of the program, one for each error type
+pub(crate) struct ErrorHandlers<MSG> {
+    handlers: Vec<Handler<MSG>>,
+}
+
+impl<MSG> Default for ErrorHandlers<MSG> {
+    fn default() -> Self {
+        Self { handlers: vec![] }
+    }
+}
+
+impl<MSG> ErrorHandlers<MSG> {
+    /// add a handler for the errors of type `E`
+    pub(crate) fn push<E, F>(&mut self, f: F)
+    where
+        E: 'static,
+        F: Fn(E) -> MSG + 'static,
+    {
+        self.handlers.push(Box::new(move |error: Box<dyn Any>| {
+            error.downcast::<E>().map(|error| f(*error))
+        }));
+    }
+
+    /// map the error into a msg with the handler of its type,
+    /// None if there is no handler for this error type
+    pub(crate) fn handle<E: 'static>(&self, error: E) -> Option<MSG> {
+        let mut error: Box<dyn Any> = Box::new(error);
+        for handler in self.handlers.iter() {
+            match handler(error) {
+                Ok(msg) => return Some(msg),
+                Err(unhandled) => error = unhandled,
+            }
+        }
+        log::error!("unhandled error of type: {}", any::type_name::<E>());
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Msg {
+        Parse(String),
+        Io(&'static str),
+    }
+
+    #[test]
+    fn errors_are_mapped_by_their_type() {
+        let mut handlers = ErrorHandlers::default();
+        handlers.push(|e: std::num::ParseIntError| Msg::Parse(e.to_string()));
+        handlers.push(|e: &'static str| Msg::Io(e));
+
+        let parse_error = "x".parse::<i32>().unwrap_err();
+        assert_eq!(
+            handlers.handle(parse_error),
+            Some(Msg::Parse("invalid digit found in string".to_string()))
+        );
+        assert_eq!(handlers.handle("disk full"), Some(Msg::Io("disk full")));
+        assert_eq!(handlers.handle(42_u8), None);
+    }
+}
@@ -1,5 +1,10 @@
-use crate::dom::window;
-use std::rc::Rc;
+use crate::dom::task::RecurringTask;
+use crate::dom::{window, Task};
+use futures::channel::mpsc;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, JsValue};
 
@@ -29,3 +34,67 @@ where
         _closure: Rc::new(closure),
     })
 }
+
+/// handle of the loop created by [`animation_frames`],
+/// the loop is cancelled when this is dropped
+pub struct AnimationFramesHandle {
+    handle: Rc<Cell<i32>>,
+    closure: Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>>,
+}
+
+impl Drop for AnimationFramesHandle {
+    fn drop(&mut self) {
+        window()
+            .cancel_animation_frame(self.handle.get())
+            .expect("cancel animation handle");
+        // this also drops the sender, which ends the task
+        self.closure.borrow_mut().take();
+    }
+}
+
+/// create a recurring task which yields the high resolution timestamp of each animation frame,
+/// ie: to drive the simulation of a game loop in the `update` of the app.
+///
+/// The loop is cancelled when the returned handle is dropped, the task then ends.
+/// The loop also stops when the task is dropped.
+/// # Example
+/// ```rust,ignore
+/// fn init(&mut self) -> Cmd<Self, Msg> {
+///     let (frames, handle) = animation_frames();
+///     self.frames_handle = Some(handle);
+///     Cmd::from(frames.map_msg(Msg::Tick))
+/// }
+/// ```
+pub fn animation_frames() -> (Task<f64>, AnimationFramesHandle) {
+    let (tx, rx) = mpsc::unbounded();
+    let handle = Rc::new(Cell::new(0));
+    let closure: Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>> = Rc::new(RefCell::new(None));
+
+    let handle_clone = Rc::clone(&handle);
+    let weak_closure = Rc::downgrade(&closure);
+    *closure.borrow_mut() = Some(Closure::new(move |timestamp: f64| {
+        if tx.unbounded_send(timestamp).is_err() {
+            // the task is dropped, stop requesting the next frame
+            return;
+        }
+        if let Some(closure) = weak_closure.upgrade() {
+            if let Some(closure) = closure.borrow().as_ref() {
+                handle_clone.set(request_frame(closure));
+            }
+        }
+    }));
+    handle.set(request_frame(
+        closure.borrow().as_ref().expect("must have the closure"),
+    ));
+
+    (
+        Task::Recurring(RecurringTask { receiver: rx }),
+        AnimationFramesHandle { handle, closure },
+    )
+}
+
+fn request_frame(closure: &Closure<dyn FnMut(f64)>) -> i32 {
+    window()
+        .request_animation_frame(closure.as_ref().unchecked_ref())
+        .expect("must request animation frame")
+}
@@ -1,4 +1,7 @@
-use crate::dom::spawn_local;
+use crate::dom::{
+    delay, fonts_ready, request_timeout_callback, spawn_local, stylesheet_loaded,
+    TimeoutCallbackHandle,
+};
 use futures::channel::mpsc;
 use futures::channel::mpsc::UnboundedReceiver;
 use futures::StreamExt;
@@ -25,6 +28,56 @@ where
         Self::Single(SingleTask::new(f))
     }
 
+    /// a task which results to `msg` after `timeout` milliseconds
+    pub fn delayed(timeout: i32, msg: MSG) -> Self {
+        Self::single(async move {
+            delay(timeout).await;
+            msg
+        })
+    }
+
+    /// the same as [`Task::delayed`], but also returns a handle which cancels the task
+    /// when it is dropped, before the `timeout` has elapsed.
+    pub fn delayed_cancellable(timeout: i32, msg: MSG) -> (Self, TimeoutCallbackHandle) {
+        let (tx, rx) = mpsc::unbounded();
+        let mut msg = Some(msg);
+        let handle = request_timeout_callback(
+            move || {
+                if let Some(msg) = msg.take() {
+                    tx.unbounded_send(msg).expect("must send");
+                }
+                tx.close_channel();
+            },
+            timeout,
+        )
+        .expect("must set timeout");
+        (Self::Recurring(RecurringTask { receiver: rx }), handle)
+    }
+
+    /// a task which results to `msg` once the stylesheet of this `<link rel="stylesheet">`
+    /// is loaded, ie: to only show the content once it is styled.
+    /// The `msg` is also emitted when the stylesheet fails to load, so the content is not
+    /// hidden forever.
+    pub fn stylesheet_loaded(link: web_sys::HtmlLinkElement, msg: MSG) -> Self {
+        Self::single(async move {
+            if let Err(e) = stylesheet_loaded(&link).await {
+                log::error!("unable to load the stylesheet {}: {:?}", link.href(), e);
+            }
+            msg
+        })
+    }
+
+    /// a task which results to `msg` once the fonts of the document are loaded,
+    /// ie: to measure the text only after the web fonts are applied.
+    pub fn fonts_ready(msg: MSG) -> Self {
+        Self::single(async move {
+            if let Err(e) = fonts_ready().await {
+                log::error!("unable to wait for the fonts: {:?}", e);
+            }
+            msg
+        })
+    }
+
     /// apply a function to the msg to create a different task which has a different msg
     pub fn map_msg<F, MSG2>(self, f: F) -> Task<MSG2>
     where
@@ -37,7 +90,12 @@ where
         }
     }
 
-    /// return the next value
+    /// return the next value.
+    ///
+    /// A single task returns its msg once and `None` afterwards,
+    /// a recurring task returns `None` once all of its msgs are emitted.
+    /// Dropping the returned future before it completes does not lose the msg,
+    /// it is returned on the next call instead.
     pub async fn next(&mut self) -> Option<MSG> {
         match self {
             Self::Single(task) => task.next().await,
@@ -48,7 +106,8 @@ where
 
 /// SingleTask is used to do asynchronous operations
 pub struct SingleTask<MSG> {
-    task: Pin<Box<dyn Future<Output = MSG>>>,
+    /// the future of the task, this is None once it has completed
+    task: Option<Pin<Box<dyn Future<Output = MSG>>>>,
 }
 
 impl<MSG> SingleTask<MSG>
@@ -60,7 +119,9 @@ where
     where
         F: Future<Output = MSG> + 'static,
     {
-        Self { task: Box::pin(f) }
+        Self {
+            task: Some(Box::pin(f)),
+        }
     }
 
     /// apply a function to the msg to create a different task which has a different msg
@@ -69,16 +130,22 @@ where
         F: Fn(MSG) -> MSG2 + 'static,
         MSG2: 'static,
     {
-        let task = self.task;
-        SingleTask::new(async move {
-            let msg = task.await;
-            f(msg)
-        })
+        match self.task {
+            Some(task) => SingleTask::new(async move {
+                let msg = task.await;
+                f(msg)
+            }),
+            // the task has already completed
+            None => SingleTask { task: None },
+        }
     }
 
-    /// get the next value
+    /// get the next value, the completed future is never polled again
+    /// so the subsequent calls returns None
     async fn next(&mut self) -> Option<MSG> {
-        let msg = self.task.as_mut().await;
+        let task = self.task.as_mut()?;
+        let msg = task.as_mut().await;
+        self.task = None;
         Some(msg)
     }
 }
@@ -120,3 +187,23 @@ where
         RecurringTask { receiver: rx }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::executor::block_on;
+
+    #[test]
+    fn single_task_is_fused() {
+        let mut task = Task::single(async { 1 });
+        assert_eq!(block_on(task.next()), Some(1));
+        assert_eq!(block_on(task.next()), None);
+    }
+
+    #[test]
+    fn mapped_single_task_is_fused() {
+        let mut task = Task::single(async { 1 }).map_msg(|n| n * 10);
+        assert_eq!(block_on(task.next()), Some(10));
+        assert_eq!(block_on(task.next()), None);
+    }
+}
@@ -0,0 +1,109 @@
+//! compute the characters which changed in a text, so only these characters are updated
+//! in the text node, see [`Program::with_incremental_text`]
+//!
+//! [`Program::with_incremental_text`]: crate::dom::Program::with_incremental_text
+
+/// the change of the old text into the new text,
+/// as a range of the old text which is replaced with the inserted text
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct TextEdit {
+    /// where the change starts, in UTF-16 code units as used by the DOM
+    pub(crate) offset: u32,
+    /// the number of UTF-16 code units which are removed from the old text
+    pub(crate) delete_count: u32,
+    /// the text which is inserted at the offset
+    pub(crate) insert: String,
+}
+
+/// the smallest single edit which changes the old text into the new text,
+/// the common characters at the start and at the end of the texts are left untouched.
+/// None if the texts are the same.
+pub(crate) fn text_edit(old: &str, new: &str) -> Option<TextEdit> {
+    if old == new {
+        return None;
+    }
+    let prefix_len: usize = old
+        .chars()
+        .zip(new.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+    let (old_rest, new_rest) = (&old[prefix_len..], &new[prefix_len..]);
+    // the suffix is taken from what remains, so it does not overlap with the prefix
+    let suffix_len: usize = old_rest
+        .chars()
+        .rev()
+        .zip(new_rest.chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+    let removed = &old_rest[..old_rest.len() - suffix_len];
+    let inserted = &new_rest[..new_rest.len() - suffix_len];
+    Some(TextEdit {
+        offset: utf16_len(&old[..prefix_len]),
+        delete_count: utf16_len(removed),
+        insert: inserted.to_string(),
+    })
+}
+
+fn utf16_len(s: &str) -> u32 {
+    s.encode_utf16().count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// apply the edit the same way `CharacterData.replaceData` does
+    fn apply(old: &str, edit: &TextEdit) -> String {
+        let mut units: Vec<u16> = old.encode_utf16().collect();
+        let start = edit.offset as usize;
+        let end = start + edit.delete_count as usize;
+        units.splice(start..end, edit.insert.encode_utf16());
+        String::from_utf16(&units).expect("must be valid")
+    }
+
+    #[test]
+    fn same_text_has_no_edit() {
+        assert_eq!(text_edit("hello", "hello"), None);
+    }
+
+    #[test]
+    fn only_the_changed_characters_are_replaced() {
+        let old = "the quick brown fox jumps over the lazy dog";
+        let new = "the quick red fox jumps over the lazy dog";
+        let edit = text_edit(old, new).expect("must have an edit");
+        assert_eq!(
+            edit,
+            TextEdit {
+                offset: 10,
+                delete_count: 5,
+                insert: "red".to_string(),
+            }
+        );
+        assert_eq!(apply(old, &edit), new);
+    }
+
+    #[test]
+    fn insert_and_delete() {
+        for (old, new) in [
+            ("", "typed"),
+            ("typed", ""),
+            ("aaa", "aaaa"),
+            ("abc", "abXbc"),
+            ("hello world", "hello"),
+            ("héllo 👋 wörld", "héllo 🌍 wörld!"),
+            ("👋👋", "👋"),
+        ] {
+            let edit = text_edit(old, new).expect("must have an edit");
+            assert_eq!(apply(old, &edit), new, "{old:?} -> {new:?}");
+        }
+    }
+
+    #[test]
+    fn offset_is_counted_in_utf16() {
+        let edit = text_edit("👋 a", "👋 b").expect("must have an edit");
+        assert_eq!(edit.offset, 3);
+        assert_eq!(edit.delete_count, 1);
+    }
+}
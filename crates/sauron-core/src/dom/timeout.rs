@@ -12,6 +12,13 @@ pub struct TimeoutCallbackHandle {
     _closure: Rc<Closure<dyn FnMut()>>,
 }
 
+impl TimeoutCallbackHandle {
+    /// clear the timeout, the callback will not be called if it hasn't been called yet
+    pub fn cancel(self) {
+        drop(self);
+    }
+}
+
 impl Drop for TimeoutCallbackHandle {
     fn drop(&mut self) {
         window().clear_timeout_with_handle(self.handle);
@@ -21,7 +28,7 @@ impl Drop for TimeoutCallbackHandle {
 /// request and idle callback
 pub fn request_timeout_callback<F>(f: F, timeout: i32) -> Result<TimeoutCallbackHandle, JsValue>
 where
-    F: FnMut() + 'static,
+    F: FnOnce() + 'static,
 {
     let closure = Closure::once(f);
     let handle = window().set_timeout_with_callback_and_timeout_and_arguments_0(
@@ -34,6 +41,23 @@ where
     })
 }
 
+/// call `f` after `timeout` milliseconds.
+/// The timeout is cleared when the returned handle is dropped or cancelled,
+/// so the handle must be kept for as long as the callback is still wanted.
+/// # Examples
+/// ```rust,ignore
+/// use sauron::dom::set_timeout;
+///
+/// let handle = set_timeout(|| log::info!("never called"), 500);
+/// handle.cancel();
+/// ```
+pub fn set_timeout<F>(f: F, timeout: i32) -> TimeoutCallbackHandle
+where
+    F: FnOnce() + 'static,
+{
+    request_timeout_callback(f, timeout).expect("must be able to set timeout")
+}
+
 /// simulate a delay using promise in js
 pub(crate) async fn async_delay(timeout: i32) -> Result<TimeoutCallbackHandle, JsValue> {
     let mut result = Err(JsValue::NULL);
@@ -1,8 +1,13 @@
 //! utility functions
 //!
 use crate::dom;
+use crate::dom::dom_node::intern;
+use wasm_bindgen::{JsCast, JsValue};
 pub use wasm_bindgen_futures::spawn_local;
-use web_sys::ScrollToOptions;
+use web_sys::{
+    HtmlDialogElement, HtmlLinkElement, HtmlMediaElement, ScrollIntoViewOptions, ScrollToOptions,
+};
+pub use web_sys::{ScrollBehavior, ScrollLogicalPosition};
 
 //TODO: feature gate this with `use-cached-windows`
 thread_local!(static WINDOW: web_sys::Window = web_sys::window().expect("no global `window` exists"));
@@ -61,6 +66,82 @@ pub fn scroll_window_to_top() {
     window().scroll_to_with_scroll_to_options(&options);
 }
 
+/// the element to be scrolled into view, see [`Program::scroll_into_view`]
+///
+/// [`Program::scroll_into_view`]: crate::dom::Program::scroll_into_view
+#[derive(Debug, Clone)]
+pub enum ScrollTarget {
+    /// the first element in the view of the app which matches this css selector
+    Selector(String),
+    /// this element
+    Element(web_sys::Element),
+}
+
+impl From<&str> for ScrollTarget {
+    fn from(selector: &str) -> Self {
+        Self::Selector(selector.to_string())
+    }
+}
+
+impl From<String> for ScrollTarget {
+    fn from(selector: String) -> Self {
+        Self::Selector(selector)
+    }
+}
+
+impl From<web_sys::Element> for ScrollTarget {
+    fn from(element: web_sys::Element) -> Self {
+        Self::Element(element)
+    }
+}
+
+/// how the element is scrolled into view, the browser defaults are used for the unset options
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScrollOptions {
+    /// whether the scrolling is instant or animates smoothly
+    pub behavior: Option<ScrollBehavior>,
+    /// the vertical alignment of the element in its scroll container
+    pub block: Option<ScrollLogicalPosition>,
+    /// the horizontal alignment of the element in its scroll container
+    pub inline: Option<ScrollLogicalPosition>,
+}
+
+impl ScrollOptions {
+    /// set whether the scrolling is instant or animates smoothly
+    pub fn with_behavior(mut self, behavior: ScrollBehavior) -> Self {
+        self.behavior = Some(behavior);
+        self
+    }
+
+    /// set the vertical alignment of the element in its scroll container
+    pub fn with_block(mut self, block: ScrollLogicalPosition) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// set the horizontal alignment of the element in its scroll container
+    pub fn with_inline(mut self, inline: ScrollLogicalPosition) -> Self {
+        self.inline = Some(inline);
+        self
+    }
+}
+
+impl From<ScrollOptions> for ScrollIntoViewOptions {
+    fn from(options: ScrollOptions) -> Self {
+        let web_options = ScrollIntoViewOptions::new();
+        if let Some(behavior) = options.behavior {
+            web_options.set_behavior(behavior);
+        }
+        if let Some(block) = options.block {
+            web_options.set_block(block);
+        }
+        if let Some(inline) = options.inline {
+            web_options.set_inline(inline);
+        }
+        web_options
+    }
+}
+
 /// set the browser location hash
 pub fn set_location_hash(hash: &str) {
     let location = window().location();
@@ -93,3 +174,84 @@ pub fn get_window_size() -> (i32, i32) {
 pub fn set_window_title(title: &str) {
     document().set_title(title);
 }
+
+/// open the `<dialog>` with this element id as a modal, which is displayed on top of the page
+/// and makes the rest of the page inert.
+/// This is a method of the dialog element and can not be expressed with the `open` attribute.
+pub fn show_modal_dialog(element_id: &str) -> Result<(), JsValue> {
+    find_dialog(element_id)?.show_modal()
+}
+
+/// close the `<dialog>` with this element id, which dispatches the `close` event of the dialog
+pub fn close_dialog(element_id: &str) -> Result<(), JsValue> {
+    find_dialog(element_id)?.close();
+    Ok(())
+}
+
+/// start the playback of the `<audio>` or `<video>` with this element id.
+/// The browser may reject the playback, ie: autoplay without a user interaction,
+/// which is logged as an error.
+pub fn play_media(element_id: &str) -> Result<(), JsValue> {
+    let promise = find_media(element_id)?.play()?;
+    spawn_local(async move {
+        if let Err(e) = wasm_bindgen_futures::JsFuture::from(promise).await {
+            log::error!("unable to play the media: {:?}", e);
+        }
+    });
+    Ok(())
+}
+
+/// pause the playback of the `<audio>` or `<video>` with this element id
+pub fn pause_media(element_id: &str) -> Result<(), JsValue> {
+    find_media(element_id)?.pause()
+}
+
+/// resolves once the stylesheet of this `<link rel="stylesheet">` is loaded,
+/// or right away if it is already loaded.
+/// This is an error if the stylesheet fails to load.
+pub async fn stylesheet_loaded(link: &HtmlLinkElement) -> Result<(), JsValue> {
+    if link.sheet().is_some() {
+        return Ok(());
+    }
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let options = web_sys::AddEventListenerOptions::new();
+        options.set_once(true);
+        link.add_event_listener_with_callback_and_add_event_listener_options(
+            intern("load"),
+            &resolve,
+            &options,
+        )
+        .expect("add load listener");
+        link.add_event_listener_with_callback_and_add_event_listener_options(
+            intern("error"),
+            &reject,
+            &options,
+        )
+        .expect("add error listener");
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await?;
+    Ok(())
+}
+
+/// resolves once the fonts of the document are loaded, ie: `document.fonts.ready`
+pub async fn fonts_ready() -> Result<(), JsValue> {
+    let promise = document().fonts().ready()?;
+    wasm_bindgen_futures::JsFuture::from(promise).await?;
+    Ok(())
+}
+
+fn find_media(element_id: &str) -> Result<HtmlMediaElement, JsValue> {
+    document()
+        .get_element_by_id(element_id)
+        .ok_or_else(|| JsValue::from_str(&format!("no element with id: {element_id}")))?
+        .dyn_into::<HtmlMediaElement>()
+        .map_err(|_| JsValue::from_str(&format!("`{element_id}` is not a media element")))
+}
+
+fn find_dialog(element_id: &str) -> Result<HtmlDialogElement, JsValue> {
+    document()
+        .get_element_by_id(element_id)
+        .ok_or_else(|| JsValue::from_str(&format!("no element with id: {element_id}")))?
+        .dyn_into::<HtmlDialogElement>()
+        .map_err(|_| JsValue::from_str(&format!("`{element_id}` is not a dialog element")))
+}
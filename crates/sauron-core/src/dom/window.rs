@@ -1,15 +1,55 @@
 use crate::dom::task::RecurringTask;
 use crate::{
-    dom::{document, dom_node::intern, util, window, Application, Program, Task},
+    dom::{
+        document, dom_node::intern, dom_node::ListenerClosure, util, window, Application, Program,
+        Task,
+    },
     vdom::Attribute,
 };
 use futures::channel::mpsc;
+use std::{cell::RefCell, rc::Rc};
 use wasm_bindgen::{prelude::*, JsCast};
 
 /// Provides function for window related functions
 #[derive(Clone, Copy)]
 pub struct Window;
 
+/// An uncaught error or an unhandled promise rejection in the window
+#[derive(Debug, Clone)]
+pub struct WindowError {
+    /// the message of the error
+    pub message: String,
+    /// the thrown value, ie: the `Error` object or the reason of the rejected promise
+    pub error: JsValue,
+}
+
+impl WindowError {
+    fn from_event(event: &web_sys::Event) -> Self {
+        if let Some(error_event) = event.dyn_ref::<web_sys::ErrorEvent>() {
+            WindowError {
+                message: error_event.message(),
+                error: error_event.error(),
+            }
+        } else if let Some(rejection) = event.dyn_ref::<web_sys::PromiseRejectionEvent>() {
+            let reason = rejection.reason();
+            let message = reason
+                .dyn_ref::<js_sys::Error>()
+                .map(|error| String::from(error.message()))
+                .or_else(|| reason.as_string())
+                .unwrap_or_else(|| format!("{reason:?}"));
+            WindowError {
+                message,
+                error: reason,
+            }
+        } else {
+            WindowError {
+                message: event.type_(),
+                error: JsValue::UNDEFINED,
+            }
+        }
+    }
+}
+
 impl Window {
     /// Create a recurring Task which will be triggered
     /// everytime the window is resized
@@ -36,6 +76,145 @@ impl Window {
 
         Task::Recurring(RecurringTask { receiver: rx })
     }
+
+    /// Create a recurring Task which will be triggered everytime the hash portion of the
+    /// window location url is changed, with the new hash, ie: for hash based routing.
+    ///
+    /// The listener is removed from the window in the next hash change after the task is dropped.
+    /// # Example
+    /// ```rust,ignore
+    /// fn init(&mut self) -> Cmd<Self, Msg> {
+    ///     Cmd::from(Window::on_hashchange(Msg::UrlChanged))
+    /// }
+    /// ```
+    pub fn on_hashchange<F, MSG>(mut cb: F) -> Task<MSG>
+    where
+        F: FnMut(String) -> MSG + 'static,
+        MSG: 'static,
+    {
+        let (tx, rx) = mpsc::unbounded();
+        let listener: Rc<RefCell<Option<js_sys::Function>>> = Rc::new(RefCell::new(None));
+        let listener_clone = Rc::clone(&listener);
+        let hashchange_callback: Closure<dyn FnMut(web_sys::Event)> =
+            Closure::new(move |_e: web_sys::Event| {
+                let msg = cb(util::get_location_hash());
+                if tx.unbounded_send(msg).is_err() {
+                    // the task is dropped, no one is listening anymore
+                    if let Some(listener) = listener_clone.borrow_mut().take() {
+                        window()
+                            .remove_event_listener_with_callback(intern("hashchange"), &listener)
+                            .expect("remove event callback");
+                    }
+                }
+            });
+        let function: &js_sys::Function = hashchange_callback.as_ref().unchecked_ref();
+        window()
+            .add_event_listener_with_callback(intern("hashchange"), function)
+            .expect("add event callback");
+        *listener.borrow_mut() = Some(function.clone());
+        hashchange_callback.forget();
+
+        Task::Recurring(RecurringTask { receiver: rx })
+    }
+
+    /// Create a recurring Task which will be triggered everytime there is an uncaught error
+    /// or an unhandled promise rejection in the window, ie: to show an error boundary in the app.
+    ///
+    /// The listeners are removed from the window in the next error after the task is dropped.
+    /// # Example
+    /// ```rust,ignore
+    /// fn init(&mut self) -> Cmd<Self, Msg> {
+    ///     Cmd::from(Window::on_error(|error| Msg::ShowError(error.message)))
+    /// }
+    /// ```
+    pub fn on_error<F, MSG>(mut cb: F) -> Task<MSG>
+    where
+        F: FnMut(WindowError) -> MSG + 'static,
+        MSG: 'static,
+    {
+        const EVENTS: [&str; 2] = ["error", "unhandledrejection"];
+        let (tx, rx) = mpsc::unbounded();
+        let listener: Rc<RefCell<Option<js_sys::Function>>> = Rc::new(RefCell::new(None));
+        let listener_clone = Rc::clone(&listener);
+        let error_callback: Closure<dyn FnMut(web_sys::Event)> =
+            Closure::new(move |e: web_sys::Event| {
+                let msg = cb(WindowError::from_event(&e));
+                if tx.unbounded_send(msg).is_err() {
+                    // the task is dropped, no one is listening anymore
+                    if let Some(listener) = listener_clone.borrow_mut().take() {
+                        for event_name in EVENTS {
+                            window()
+                                .remove_event_listener_with_callback(intern(event_name), &listener)
+                                .expect("remove event callback");
+                        }
+                    }
+                }
+            });
+        let function: &js_sys::Function = error_callback.as_ref().unchecked_ref();
+        for event_name in EVENTS {
+            window()
+                .add_event_listener_with_callback(intern(event_name), function)
+                .expect("add event callback");
+        }
+        *listener.borrow_mut() = Some(function.clone());
+        error_callback.forget();
+
+        Task::Recurring(RecurringTask { receiver: rx })
+    }
+
+    /// set the hash portion of the window location url, which triggers the hashchange event
+    /// when it is different from the current hash
+    pub fn set_hash(hash: &str) {
+        util::set_location_hash(hash);
+    }
+}
+
+/// Create a recurring Task which will be triggered everytime the visibility of the document
+/// changes, with true when the page is visible and false when it is hidden,
+/// ie: when the tab is switched or the browser is minimized. Apps can use this to pause
+/// the work such as polling or animations which no one can see.
+///
+/// The listener is removed from the document in the next visibility change after the task
+/// is dropped.
+/// # Example
+/// ```rust,ignore
+/// fn init(&mut self) -> Cmd<Self, Msg> {
+///     Cmd::from(on_visibility_change(Msg::VisibilityChanged))
+/// }
+/// ```
+pub fn on_visibility_change<F, MSG>(mut cb: F) -> Task<MSG>
+where
+    F: FnMut(bool) -> MSG + 'static,
+    MSG: 'static,
+{
+    let (tx, rx) = mpsc::unbounded();
+    let listener: Rc<RefCell<Option<js_sys::Function>>> = Rc::new(RefCell::new(None));
+    let listener_clone = Rc::clone(&listener);
+    let visibility_callback: Closure<dyn FnMut(web_sys::Event)> =
+        Closure::new(move |_e: web_sys::Event| {
+            let msg = cb(is_document_visible());
+            if tx.unbounded_send(msg).is_err() {
+                // the task is dropped, no one is listening anymore
+                if let Some(listener) = listener_clone.borrow_mut().take() {
+                    document()
+                        .remove_event_listener_with_callback(intern("visibilitychange"), &listener)
+                        .expect("remove event callback");
+                }
+            }
+        });
+    let function: &js_sys::Function = visibility_callback.as_ref().unchecked_ref();
+    document()
+        .add_event_listener_with_callback(intern("visibilitychange"), function)
+        .expect("add event callback");
+    *listener.borrow_mut() = Some(function.clone());
+    visibility_callback.forget();
+
+    Task::Recurring(RecurringTask { receiver: rx })
+}
+
+/// returns true if the `visibilityState` of the document is `visible`
+pub fn is_document_visible() -> bool {
+    document().visibility_state() == web_sys::VisibilityState::Visible
 }
 
 impl<APP, MSG> Program<APP, MSG>
@@ -43,13 +222,23 @@ where
     MSG: 'static,
     APP: Application<MSG>,
 {
-    /// attach event listeners to the window object
+    /// attach event listeners to the window object, ie: for resize or global shortcuts.
+    ///
+    /// The closures of the listeners are kept by the program, the listeners are removed from
+    /// the window when the program is shut down or dropped.
     pub fn add_window_event_listeners(&self, event_listeners: Vec<Attribute<MSG>>) {
         self.add_event_listeners(&window(), event_listeners)
             .expect("must add to event listener");
     }
 
-    /// attach event listeners to the document object
+    /// attach event listeners to the document object, ie: for global keyboard shortcuts.
+    ///
+    /// The closures of the listeners are kept by the program, the listeners are removed from
+    /// the document when the program is shut down or dropped.
+    /// # Example
+    /// ```rust,ignore
+    /// program.add_document_event_listeners(vec![on_keydown(|ke| Msg::Shortcut(ke.key()))]);
+    /// ```
     pub fn add_document_event_listeners(&self, event_listeners: Vec<Attribute<MSG>>) {
         self.add_event_listeners(&document(), event_listeners)
             .expect("must add to event listener");
@@ -68,7 +257,18 @@ where
             let mut program = program.upgrade().expect("must upgrade");
             program.dispatch(msg);
         });
-        window().set_onhashchange(Some(closure.as_ref().unchecked_ref()));
-        self.event_closures.borrow_mut().push(closure);
+        window()
+            .add_event_listener_with_callback(
+                intern("hashchange"),
+                closure.as_ref().unchecked_ref(),
+            )
+            .expect("add event callback");
+        let closure = ListenerClosure {
+            closure,
+            capture: false,
+        };
+        self.event_closures
+            .borrow_mut()
+            .push((window().into(), "hashchange", closure));
     }
 }
@@ -93,6 +93,8 @@ pub fn html_element<MSG>(
 }
 
 /// creates a text node using a formatter
+/// The formatted text is escaped when rendered, so it is safe to interpolate user supplied
+/// values.
 /// # Examples
 /// ```rust
 /// use sauron::*;
@@ -147,16 +149,55 @@ pub fn comment<MSG>(s: impl Into<Cow<'static, str>>) -> Node<MSG>
 }
 
 /// fragment is a list of nodes
+///
+/// A fragment has no attributes, therefore it can not be keyed.
+/// When rendering a collection of multi-root items, put the `key` on each of the root nodes
+/// of the fragment instead, so each of them can be matched in the keyed diff.
 /// # Example
 /// ```rust
-/// use sauron::{*, html::*};
+/// use sauron::{*, html::*, html::attributes::*};
 ///
 /// let node: Node<()> = fragment([div([],[]), span([],[])]);
+///
+/// let items = [(1, "one"), (2, "two")];
+/// let list: Node<()> = div([], items.iter().map(|(id, label)| {
+///     fragment([
+///         dt([key!("term-{id}")], [text(label)]),
+///         dd([key!("desc-{id}")], [text(id)]),
+///     ])
+/// }));
 /// ```
 pub fn fragment<MSG>(nodes: impl IntoIterator<Item = Node<MSG>>) -> Node<MSG> {
     mt_dom::fragment(nodes)
 }
 
+/// create an `iframe` which displays the `html` document, set in its `srcdoc` attribute.
+///
+/// The html is escaped when rendered into the `srcdoc` attribute, so it can contain quotes.
+/// Combine it with [`attributes::sandbox_allow`] to restrict what the document can do.
+/// # Example
+/// ```rust
+/// use sauron::{*, html::*, html::attributes::*};
+///
+/// let preview: Node<()> = iframe_srcdoc(
+///     [sandbox_allow(["allow-scripts"])],
+///     r#"<p class="greeting">Hello</p>"#,
+/// );
+/// assert_eq!(
+///     preview.render_to_string(),
+///     r#"<iframe sandbox="allow-scripts" srcdoc="<p class=&quot;greeting&quot;>Hello</p>"></iframe>"#
+/// );
+/// ```
+pub fn iframe_srcdoc<MSG>(
+    attrs: impl IntoIterator<Item = Attribute<MSG>>,
+    html: impl Into<String>,
+) -> Node<MSG> {
+    let attrs = attrs
+        .into_iter()
+        .chain([attributes::attr("srcdoc", html.into())]);
+    iframe(attrs, [])
+}
+
 /// create a doctype
 pub fn doctype<MSG>(s: impl Into<Cow<'static, str>>) -> Node<MSG> {
     Node::Leaf(Leaf::DocType(s.into()))
@@ -166,3 +207,40 @@ pub fn doctype<MSG>(s: impl Into<Cow<'static, str>>) -> Node<MSG> {
 pub fn node_list<MSG>(nodes: impl IntoIterator<Item = Node<MSG>>) -> Node<MSG> {
     Node::NodeList(nodes.into_iter().collect())
 }
+
+/// Convert a value into a node which can be used directly as a child of an element.
+///
+/// `Option<Node>` and `Vec<Node>` are converted into a node list, which is unrolled in place
+/// into the children of the parent element, so `None` produces no node at all.
+/// This is also used by the `node!` macro for the `{expr}` blocks.
+/// # Example
+/// ```rust
+/// use sauron::{*, html::*};
+///
+/// let badge: Option<Node<()>> = None;
+/// let items: Vec<Node<()>> = vec![li([], [text("one")]), li([], [text("two")])];
+/// let node: Node<()> = ul([], [badge.into_node(), items.into_node()]);
+/// assert_eq!(node.render_to_string(), "<ul><li>one</li><li>two</li></ul>");
+/// ```
+pub trait IntoNode<MSG> {
+    /// convert this value into a node
+    fn into_node(self) -> Node<MSG>;
+}
+
+impl<MSG> IntoNode<MSG> for Node<MSG> {
+    fn into_node(self) -> Node<MSG> {
+        self
+    }
+}
+
+impl<MSG> IntoNode<MSG> for Option<Node<MSG>> {
+    fn into_node(self) -> Node<MSG> {
+        node_list(self)
+    }
+}
+
+impl<MSG> IntoNode<MSG> for Vec<Node<MSG>> {
+    fn into_node(self) -> Node<MSG> {
+        node_list(self)
+    }
+}
@@ -8,8 +8,9 @@ pub use crate::{dom::Event, vdom::Attribute};
 pub use attribute_macros::commons::*;
 pub use attribute_macros::*;
 pub use attribute_value::AttributeValue;
-pub use listener::Listener;
-pub use special::{key, replace, skip, skip_criteria, Special};
+pub use listener::{Listener, ListenerOptions};
+pub use special::{key, merge_class, replace, skip, skip_criteria, Special};
+pub(crate) use special::VDOM_ATTRIBUTES;
 pub use style::Style;
 pub use value::Value;
 
@@ -131,6 +132,116 @@ pub fn classes<MSG>(class_list: impl IntoIterator<Item = impl Into<Value>>) -> A
     Attribute::with_multiple_values(None, "class", class_values)
 }
 
+/// A type which maps into a list of class names, ie: the states of a component
+/// which are styled differently.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::attributes::AsClasses};
+///
+/// #[derive(Clone, Copy)]
+/// enum Status {
+///     Idle,
+///     Failed,
+/// }
+///
+/// impl AsClasses for Status {
+///     fn as_classes(&self) -> Vec<&'static str> {
+///         match self {
+///             Status::Idle => vec!["status"],
+///             Status::Failed => vec!["status", "error"],
+///         }
+///     }
+/// }
+///
+/// let html: Node<()> = div(vec![classes_from(Status::Failed)], vec![]);
+/// assert_eq!(html.render_to_string(), r#"<div class="status error"></div>"#);
+/// ```
+pub trait AsClasses {
+    /// the class names of this value
+    fn as_classes(&self) -> Vec<&'static str>;
+}
+
+impl<T> AsClasses for &T
+where
+    T: AsClasses + ?Sized,
+{
+    fn as_classes(&self) -> Vec<&'static str> {
+        (*self).as_classes()
+    }
+}
+
+/// a helper function to create a class attribute from the class names of this value,
+/// see [`AsClasses`]
+pub fn classes_from<MSG>(value: impl AsClasses) -> Attribute<MSG> {
+    classes(value.as_classes())
+}
+
+/// A condition of whether a class is present, see [`classes_when`].
+///  - `bool` is present when it is true
+///  - `Option` is present when it is `Some`, regardless of the value
+///  - `Result` is present when it is `Ok`, regardless of the value
+///  - a reference follows the rule of the referenced value
+pub trait ClassCondition {
+    /// returns true if the class is present
+    fn is_present(&self) -> bool;
+}
+
+impl ClassCondition for bool {
+    fn is_present(&self) -> bool {
+        *self
+    }
+}
+
+impl<T> ClassCondition for Option<T> {
+    fn is_present(&self) -> bool {
+        self.is_some()
+    }
+}
+
+impl<T, E> ClassCondition for Result<T, E> {
+    fn is_present(&self) -> bool {
+        self.is_ok()
+    }
+}
+
+impl<T> ClassCondition for &T
+where
+    T: ClassCondition + ?Sized,
+{
+    fn is_present(&self) -> bool {
+        (*self).is_present()
+    }
+}
+
+/// A helper function which takes pairs of class and a condition, similar to [`classes_flag`]
+/// but the condition can also be an `Option` or `Result`, see [`ClassCondition`] for the rules.
+/// A class which is present more than once is only added once.
+/// # Examples
+/// ```rust
+/// use sauron::*;
+///
+/// let selected: Option<usize> = Some(2);
+/// let hovered: Option<usize> = None;
+///
+/// let html: Node<()> = div(
+///     vec![class("item"), classes_when([("selected", selected), ("hovered", hovered)])],
+///     vec![],
+/// );
+/// assert_eq!(html.render_to_string(), r#"<div class="item selected"></div>"#);
+/// ```
+pub fn classes_when<MSG>(
+    pairs: impl IntoIterator<Item = (impl Into<Value>, impl ClassCondition)>,
+) -> Attribute<MSG> {
+    let mut class_list: Vec<Value> = vec![];
+    for (class, condition) in pairs {
+        let class = class.into();
+        if condition.is_present() && !class_list.contains(&class) {
+            class_list.push(class);
+        }
+    }
+    classes(class_list)
+}
+
 /// A helper function for setting attributes with no values such as checked
 /// in checkbox input type
 /// This is best called to be appended to the node since this
@@ -224,6 +335,29 @@ pub fn disabled<MSG>(is_disabled: bool) -> Attribute<MSG> {
     }
 }
 
+/// hide the element with the `hidden` attribute when `is_hidden` is true.
+///
+/// Unlike conditionally rendering the element, the element and its children stay in the DOM
+/// while hidden, so their state such as the text typed in an input, the scroll position and
+/// the event listeners are preserved, and nothing is recreated when it is shown again.
+///
+/// Note: a `display` set in the css of the element overrides the `hidden` attribute,
+/// use `styles_flag([("display", "none", is_hidden)])` for such elements.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::*, html::attributes::*};
+///
+/// let is_collapsed = true;
+/// let panel: Node<()> = div(vec![hidden_when(is_collapsed)], vec![input(vec![], vec![])]);
+/// ```
+pub fn hidden_when<MSG>(is_hidden: bool) -> Attribute<MSG> {
+    if is_hidden {
+        attr("hidden", true)
+    } else {
+        empty_attr()
+    }
+}
+
 /// set whether an element, ie: details, that is the contents of the
 /// details are currently visible
 pub fn open<MSG>(is_open: bool) -> Attribute<MSG> {
@@ -234,6 +368,36 @@ pub fn open<MSG>(is_open: bool) -> Attribute<MSG> {
     }
 }
 
+/// set whether the content of the element is editable by the user.
+/// Since `contenteditable` is an enumerated attribute and not a boolean attribute,
+/// this is rendered as `"true"` or `"false"` instead of being omitted.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::*, html::attributes::*};
+///
+/// let editor: Node<()> = div(vec![contenteditable(true)], vec![]);
+/// assert_eq!(editor.render_to_string(), r#"<div contenteditable="true"></div>"#);
+/// ```
+pub fn contenteditable<MSG>(is_editable: bool) -> Attribute<MSG> {
+    attr(
+        "contenteditable",
+        if is_editable { "true" } else { "false" },
+    )
+}
+
+/// set the tab order of the element, a negative value makes the element
+/// focusable but not reachable with sequential keyboard navigation
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::*, html::attributes::*};
+///
+/// let item: Node<()> = div(vec![tabindex(-1)], vec![]);
+/// assert_eq!(item.render_to_string(), r#"<div tabindex="-1"></div>"#);
+/// ```
+pub fn tabindex<MSG>(index: i32) -> Attribute<MSG> {
+    attr("tabindex", index)
+}
+
 /// set the inner html of this element without comparing in the diff
 /// this always sets the value
 /// This is for optimization purposes
@@ -274,6 +438,26 @@ pub fn attr<MSG>(att: &'static str, v: impl Into<Value>) -> Attribute<MSG> {
     mt_dom::attr(att, AttributeValue::from(v.into()))
 }
 
+/// set a javascript property on the element object instead of an html attribute.
+///
+/// Custom elements, such as the ones made with Lit or Stencil, often take rich data such as
+/// objects and arrays as properties, which can not be expressed as a string attribute.
+/// Properties are not included when rendering the node into html.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::{html_element, attributes::property}};
+///
+/// let items = js_sys::Array::of2(&"milk".into(), &"eggs".into());
+/// let list: Node<()> = html_element(None, "todo-list", [property("items", items)], [], false);
+/// ```
+#[cfg(feature = "with-dom")]
+pub fn property<MSG>(
+    name: &'static str,
+    value: impl Into<wasm_bindgen::JsValue>,
+) -> Attribute<MSG> {
+    mt_dom::attr(name, AttributeValue::Property(value.into()))
+}
+
 /// a utility function to return create an empty attr, useful for cases where branch expression
 /// need to return an attribute which otherwise it can not produce
 /// example:
@@ -294,10 +478,103 @@ pub fn empty_attr<MSG>() -> Attribute<MSG> {
     mt_dom::attr("", AttributeValue::Empty)
 }
 
+/// attributes where the multiple values are separated by comma instead of space
+const COMMA_SEPARATED_ATTRIBUTES: [&str; 3] = ["srcset", "sizes", "exportparts"];
+
+/// returns the separator used when merging the multiple values of this attribute
+#[doc(hidden)]
+pub(crate) fn value_separator(attr_name: &str) -> &'static str {
+    if COMMA_SEPARATED_ATTRIBUTES.contains(&attr_name) {
+        ", "
+    } else {
+        " "
+    }
+}
+
+/// set the image candidates of an `img` or `source` for responsive images.
+/// The candidates are separated by comma.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::*, html::attributes::*};
+///
+/// let image: Node<()> = img(vec![src("small.png"), srcset(["small.png 1x", "large.png 2x"])], vec![]);
+/// assert_eq!(
+///     image.render_to_string(),
+///     r#"<img src="small.png" srcset="small.png 1x, large.png 2x"/>"#
+/// );
+/// ```
+pub fn srcset<MSG>(candidates: impl IntoIterator<Item = impl ToString>) -> Attribute<MSG> {
+    let candidates: Vec<String> = candidates.into_iter().map(|c| c.to_string()).collect();
+    attr("srcset", candidates.join(value_separator("srcset")))
+}
+
+/// sandbox the content of an `iframe`, only the `permissions` such as `"allow-scripts"`
+/// and `"allow-forms"` are lifted from the restrictions.
+/// No permissions applies all of the restrictions.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::*, html::attributes::*};
+///
+/// let frame: Node<()> = iframe(vec![sandbox_allow(["allow-scripts", "allow-forms"])], vec![]);
+/// assert_eq!(
+///     frame.render_to_string(),
+///     r#"<iframe sandbox="allow-scripts allow-forms"></iframe>"#
+/// );
+///
+/// let frame: Node<()> = iframe(vec![sandbox_allow([] as [&str; 0])], vec![]);
+/// assert_eq!(frame.render_to_string(), r#"<iframe sandbox=""></iframe>"#);
+/// ```
+pub fn sandbox_allow<MSG>(permissions: impl IntoIterator<Item = impl ToString>) -> Attribute<MSG> {
+    let permissions: Vec<String> = permissions.into_iter().map(|p| p.to_string()).collect();
+    attr("sandbox", permissions.join(" "))
+}
+
+/// name an element in the shadow tree of a web component as a part,
+/// which can then be styled from outside of the shadow tree with the `::part(name)` selector.
+/// Multiple part names are separated by space.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::*, html::attributes::*};
+///
+/// let card_header: Node<()> = header(vec![part("header"), part("sticky")], vec![]);
+/// assert_eq!(card_header.render_to_string(), r#"<header part="header sticky"></header>"#);
+/// ```
+pub fn part<MSG>(name: impl ToString) -> Attribute<MSG> {
+    attr("part", name.to_string())
+}
+
+/// forward the parts of the nested web component to the outer shadow tree, so they can be
+/// styled from outside of the outer web component.
+/// Each part is either the name of the part or `"inner:outer"` to forward it with another name.
+/// The parts are separated by comma.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::*, html::attributes::*};
+///
+/// let picker: Node<()> = div(vec![exportparts(["button", "label:picker-label"])], vec![]);
+/// assert_eq!(
+///     picker.render_to_string(),
+///     r#"<div exportparts="button, label:picker-label"></div>"#
+/// );
+/// ```
+pub fn exportparts<MSG>(parts: impl IntoIterator<Item = impl ToString>) -> Attribute<MSG> {
+    let parts: Vec<String> = parts.into_iter().map(|p| p.to_string()).collect();
+    attr("exportparts", parts.join(value_separator("exportparts")))
+}
+
 /// merge the plain values
 #[doc(hidden)]
 pub(crate) fn merge_plain_attributes_values<MSG>(
     attr_values: &[&AttributeValue<MSG>],
+) -> Option<String> {
+    merge_plain_attributes_values_with_separator(attr_values, " ")
+}
+
+/// merge the plain values, joined with the separator
+#[doc(hidden)]
+pub(crate) fn merge_plain_attributes_values_with_separator<MSG>(
+    attr_values: &[&AttributeValue<MSG>],
+    separator: &str,
 ) -> Option<String> {
     let plain_values: Vec<String> = attr_values
         .iter()
@@ -308,7 +585,7 @@ pub(crate) fn merge_plain_attributes_values<MSG>(
         })
         .collect();
     if !plain_values.is_empty() {
-        Some(plain_values.join(" "))
+        Some(plain_values.join(separator))
     } else {
         None
     }
@@ -342,6 +619,75 @@ pub(crate) fn merge_styles_attributes_values<MSG>(
     }
 }
 
+/// merge the values of a `style` attribute which is supplied from multiple sources,
+/// such as a plain `attr("style", "color:red")` and a typed `styles([("color", "blue")])`,
+/// into a single declaration block.
+/// The declarations are ordered by their first appearance, and the last value wins
+/// for the property which is declared more than once.
+/// Returns None if the attribute has only a single value, which is then used as is.
+#[doc(hidden)]
+pub(crate) fn merge_style_declarations<MSG>(attr: &Attribute<MSG>) -> Option<String> {
+    if attr.value().len() < 2 {
+        return None;
+    }
+    let mut declarations: Vec<(String, String)> = vec![];
+    let mut declare = |name: &str, value: String| {
+        if let Some(existing) = declarations.iter_mut().find(|(n, _)| n == name) {
+            existing.1 = value;
+        } else {
+            declarations.push((name.to_string(), value));
+        }
+    };
+    for att_value in attr.value() {
+        match att_value {
+            AttributeValue::Simple(simple) => {
+                for declaration in split_style_declarations(&simple.to_string()) {
+                    if let Some((name, value)) = declaration.split_once(':') {
+                        declare(name.trim(), value.trim().to_string());
+                    }
+                }
+            }
+            AttributeValue::Style(styles) => {
+                for style in styles {
+                    declare(&style.name, style.value.to_string());
+                }
+            }
+            _ => (),
+        }
+    }
+    Some(
+        declarations
+            .iter()
+            .map(|(name, value)| format!("{name}:{value};"))
+            .collect(),
+    )
+}
+
+/// split the style declarations on the `;` which are not inside parentheses or quotes,
+/// such as in `url(data:image/png;base64,..)` or `content: "a;b"`
+fn split_style_declarations(styles: &str) -> Vec<&str> {
+    let mut declarations = vec![];
+    let mut depth: usize = 0;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in styles.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => (),
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ';') if depth == 0 => {
+                declarations.push(&styles[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    declarations.push(&styles[start..]);
+    declarations
+}
+
 /// The Attributes partition into 4 different types
 pub struct SegregatedAttributes<'a, MSG> {
     /// the listeners of the event listeners
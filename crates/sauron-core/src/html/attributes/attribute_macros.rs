@@ -131,7 +131,6 @@ pub mod commons {
         cols;
         colspan;
         content;
-        contenteditable;
         contextmenu;
         controls;
         coords;
@@ -208,10 +207,8 @@ pub mod commons {
         src;
         srcdoc;
         srclang;
-        srcset;
         start;
         step;
-        tabindex;
         target;
         title;
         translate;
@@ -14,6 +14,9 @@ pub enum AttributeValue<MSG> {
     Style(Vec<Style>),
     /// Event Listener
     EventListener(Listener<MSG>),
+    /// a javascript property which is set directly on the element object instead of an html attribute
+    #[cfg(feature = "with-dom")]
+    Property(wasm_bindgen::JsValue),
     /// no value
     Empty,
 }
@@ -27,6 +30,8 @@ impl<MSG> Clone for AttributeValue<MSG> {
             AttributeValue::Simple(this) => AttributeValue::Simple(this.clone()),
             AttributeValue::Style(this) => AttributeValue::Style(this.clone()),
             AttributeValue::EventListener(this) => AttributeValue::EventListener(this.clone()),
+            #[cfg(feature = "with-dom")]
+            AttributeValue::Property(this) => AttributeValue::Property(this.clone()),
             AttributeValue::Empty => AttributeValue::Empty,
         }
     }
@@ -41,6 +46,8 @@ impl<MSG> Debug for AttributeValue<MSG> {
             AttributeValue::Simple(this) => this.fmt(f),
             AttributeValue::Style(this) => this.fmt(f),
             AttributeValue::EventListener(this) => this.fmt(f),
+            #[cfg(feature = "with-dom")]
+            AttributeValue::Property(this) => this.fmt(f),
             AttributeValue::Empty => write!(f, "Empty"),
         }
     }
@@ -59,6 +66,8 @@ impl<MSG> PartialEq for AttributeValue<MSG> {
             (AttributeValue::EventListener(this), AttributeValue::EventListener(other)) => {
                 this == other
             }
+            #[cfg(feature = "with-dom")]
+            (AttributeValue::Property(this), AttributeValue::Property(other)) => this == other,
             (AttributeValue::Empty, AttributeValue::Empty) => true,
             (_, _) => false,
         }
@@ -116,6 +125,15 @@ impl<MSG> AttributeValue<MSG> {
         }
     }
 
+    /// return the javascript value if it is a Property variant
+    #[cfg(feature = "with-dom")]
+    pub fn as_property(&self) -> Option<&wasm_bindgen::JsValue> {
+        match self {
+            Self::Property(v) => Some(v),
+            _ => None,
+        }
+    }
+
     /// returns true if this attribute value is a style
     pub fn is_style(&self) -> bool {
         matches!(self, Self::Style(_))
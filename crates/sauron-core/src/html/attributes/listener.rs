@@ -2,6 +2,18 @@
 //! This is used in containing an event listener attached to an DOM element.
 use std::{any::TypeId, fmt, rc::Rc};
 
+/// The options used when the event listener is attached to the DOM
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListenerOptions {
+    /// the listener will never call `prevent_default`, this allows the browser to scroll
+    /// without waiting for the listener to finish
+    pub passive: bool,
+    /// the listener is called in the capture phase, before the listeners of the descendants
+    pub capture: bool,
+    /// the listener is removed after it is called once
+    pub once: bool,
+}
+
 /// A generic sized representation of a function that can be
 /// attached to a Node. The callback will essentially be owned by the element
 ///
@@ -24,14 +36,17 @@ use std::{any::TypeId, fmt, rc::Rc};
 ///         callback is necessary.
 ///
 pub struct Listener<IN, OUT> {
-    /// the function to be executed
-    func: Rc<dyn Fn(IN) -> OUT>,
+    /// the function to be executed,
+    /// a `None` output means there is nothing to be emitted for this input
+    func: Rc<dyn Fn(IN) -> Option<OUT>>,
     /// the type_id of the function
     func_type_id: TypeId,
     /// the type type_id of the event this callback will be attached to
     event_type_id: TypeId,
     /// the type_id of the return type of this callback when executed.
     msg_type_id: TypeId,
+    /// the options used when attaching this listener
+    options: ListenerOptions,
 }
 
 impl<IN, F, OUT> From<F> for Listener<IN, OUT>
@@ -42,10 +57,11 @@ where
 {
     fn from(func: F) -> Self {
         Self {
-            func: Rc::new(func),
+            func: Rc::new(move |input| Some(func(input))),
             func_type_id: TypeId::of::<F>(),
             event_type_id: TypeId::of::<IN>(),
             msg_type_id: TypeId::of::<OUT>(),
+            options: ListenerOptions::default(),
         }
     }
 }
@@ -70,8 +86,45 @@ where
     IN: 'static,
     OUT: 'static,
 {
+    /// create a listener from a function which may or may not produce an output.
+    /// This is used for events that needs to be filtered out such as input events
+    /// in the middle of an IME composition.
+    pub fn maybe<F>(func: F) -> Self
+    where
+        F: Fn(IN) -> Option<OUT> + 'static,
+    {
+        Self {
+            func: Rc::new(func),
+            func_type_id: TypeId::of::<F>(),
+            event_type_id: TypeId::of::<IN>(),
+            msg_type_id: TypeId::of::<OUT>(),
+            options: ListenerOptions::default(),
+        }
+    }
+
+    /// set the options used when attaching this listener
+    pub fn with_options(mut self, options: ListenerOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// return the options used when attaching this listener
+    pub fn options(&self) -> ListenerOptions {
+        self.options
+    }
+
     /// This method calls the actual callback.
+    ///
+    /// Note: this will panic if the listener is created with `Listener::maybe` and it
+    /// did not produce an output, use `emit_maybe` for those.
     pub fn emit(&self, input: IN) -> OUT {
+        self.emit_maybe(input)
+            .expect("listener did not produce an output")
+    }
+
+    /// calls the actual callback, returns None if the listener chose not
+    /// to emit an output for this input
+    pub fn emit_maybe(&self, input: IN) -> Option<OUT> {
         (self.func)(input)
     }
 
@@ -81,11 +134,9 @@ where
         F: Fn(OUT) -> MSG2 + Clone + 'static,
         MSG2: 'static,
     {
-        let cb = move |input| {
-            let out = self.emit(input);
-            cb2(out)
-        };
-        Listener::from(cb)
+        let options = self.options;
+        let cb = move |input| self.emit_maybe(input).map(|out| cb2(out));
+        Listener::maybe(cb).with_options(options)
     }
 }
 
@@ -102,6 +153,7 @@ impl<IN, OUT> Clone for Listener<IN, OUT> {
             func_type_id: self.func_type_id,
             event_type_id: self.event_type_id,
             msg_type_id: self.msg_type_id,
+            options: self.options,
         }
     }
 }
@@ -112,11 +164,13 @@ impl<IN, OUT> Clone for Listener<IN, OUT> {
 /// have the same logic.
 ///
 /// This is done by comparing the type_id of the input and type_id of the output.
+/// Listeners with different options are not equal, so they are attached again.
 ///
 impl<IN, OUT> PartialEq for Listener<IN, OUT> {
     fn eq(&self, other: &Self) -> bool {
         self.event_type_id == other.event_type_id
             && self.msg_type_id == other.msg_type_id
             && self.func_type_id == other.func_type_id
+            && self.options == other.options
     }
 }
@@ -4,6 +4,11 @@
 use super::{attr, Attribute, Value};
 use crate::vdom::{Element, Node};
 
+/// the attributes which are only used in diffing and patching the vdom,
+/// they are not rendered into the html string
+pub(crate) const VDOM_ATTRIBUTES: &[&str] =
+    &["key", "skip", "skip_criteria", "replace", "merge_class"];
+
 /// Special Node attributes that are treated differently
 /// such as key and skip which both greatly affects the diffing algorithm
 ///
@@ -50,6 +55,17 @@ macro_rules! key {
 
 /// key attributes is used to match
 /// old element and new element when diffing
+///
+/// The keyed children are matched by their key first before their position,
+/// so a keyed element such as a playing `video` is kept in the DOM even when the siblings
+/// around it are added, removed or changed into a different element.
+///
+/// When only some of the sibling elements have a key, the keyed siblings are matched by their
+/// key first, then the unkeyed siblings are matched by their position among the rest of the
+/// unkeyed siblings. [`validate`](crate::vdom::validate) warns about such siblings, since an
+/// unkeyed sibling which is inserted or removed shifts the matching of the ones after it.
+///
+/// The key is only used in diffing, it is not rendered into the html string.
 pub fn key<V, MSG>(v: V) -> Attribute<MSG>
 where
     V: Into<Value>,
@@ -77,3 +93,17 @@ where
 pub fn replace<MSG>(v: bool) -> Attribute<MSG> {
     attr("replace", v)
 }
+
+/// if the value is true, the classes of the element are merged with the classes which are
+/// added outside of the view, ie: by an external javascript library, instead of overwriting them.
+/// Only the classes which are previously set by the view are removed when they are no longer
+/// in the view.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::{*, attributes::*}};
+///
+/// let tab: Node<()> = div([merge_class(true), class("tab")], []);
+/// ```
+pub fn merge_class<MSG>(v: bool) -> Attribute<MSG> {
+    attr("merge_class", v)
+}
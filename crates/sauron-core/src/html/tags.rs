@@ -120,6 +120,7 @@ pub mod commons {
         hgroup;
         main;
         nav;
+        search;
         section;
         blockquote;
         dd;
@@ -20,23 +20,24 @@ pub mod prelude {
         attributes::commons::*,
         attributes::key,
         attributes::{
-            attr, checked, class, classes, classes_flag, disabled, empty_attr, r#type, styles_flag,
+            attr, checked, class, classes, classes_flag, classes_from, classes_when,
+            contenteditable, disabled, empty_attr, r#type, srcset, styles_flag, tabindex,
         },
         br, comment,
         commons::*,
         hr, img, input, lazy_view_if, safe_html, text,
         units::{ch, cm, deg, ex, grad, mm, ms, percent, pt, px, rad, rgb, rgba, s, turn, vh, vw},
-        view_if,
+        view_if, IntoNode,
     };
 
-    pub use crate::render::Render;
+    pub use crate::render::{render_to_string_with_node_offsets, Render, RenderOptions};
     pub use crate::svg;
     pub use crate::svg::attributes::commons::*;
     pub use crate::svg::attributes::special::*;
     pub use crate::svg::commons::*;
     pub use crate::svg::special::*;
     pub use crate::vdom::{
-        diff,
+        diff, diff_with,
         map_msg::{AttributeMapMsg, ElementMapMsg, NodeMapMsg},
         Attribute, AttributeValue, Element, Listener, Node, NodeTrait, Patch,
     };
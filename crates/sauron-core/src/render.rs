@@ -5,10 +5,100 @@ use crate::{
     html::{attributes, attributes::SegregatedAttributes},
     vdom::{Attribute, Element, Leaf, Node, NodeTrait},
 };
-use std::fmt;
+use std::{borrow::Cow, fmt};
 
 const DEFAULT_INDENT_SIZE: usize = 2;
 
+/// The text content of these elements are rendered as is, without escaping
+/// https://html.spec.whatwg.org/multipage/syntax.html#raw-text-elements
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+/// The whitespace in the content of these elements is displayed as is,
+/// so their children are rendered without the new lines and indents of the pretty render
+const WHITESPACE_SENSITIVE_ELEMENTS: [&str; 3] = ["pre", "textarea", "listing"];
+
+/// the attribute which holds the index of the node, when `emit_node_idx` is enabled
+pub const DATA_NODE_IDX: &str = "data-node-idx";
+
+/// the attribute which holds the id of the elements with event listeners,
+/// the closures of the listeners are looked up by this id in the client.
+pub const DATA_VDOM_ID: &str = "data-vdom-id";
+
+/// the first id assigned to the elements with event listeners,
+/// both the server render and the client starts counting from this value.
+pub(crate) const FIRST_VDOM_ID: usize = 1;
+
+/// Options which controls how the node is rendered into html
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    /// no new_lines, no indents
+    pub compressed: bool,
+    /// render the attributes sorted alphabetically by their name instead of the order they are
+    /// set in the view. This is useful for snapshot testing, where the output must be stable
+    /// even when the attributes are re-arranged.
+    /// Note: attributes of the same name such as `class` and `style` are merged first.
+    pub sort_attributes: bool,
+    /// emit the index of each element in a `data-node-idx` attribute, useful for debugging.
+    /// The index is counted depth-first, the same order the nodes are traversed in the diff.
+    /// This is disabled by default, opt in with [`RenderOptions::emit_node_idx`].
+    pub emit_node_idx: bool,
+    /// emit the `data-vdom-id` of each element which has event listeners, numbered the same way
+    /// the client assigns them when creating the same tree in a fresh page.
+    /// The client adopts these ids when hydrating with `MountAction::Hydrate`, so the ids of
+    /// the server rendered html are kept.
+    pub emit_vdom_id: bool,
+    /// the per-request nonce which is added as a `nonce` attribute to the `<script>` and
+    /// `<style>` elements, so the inline scripts and styles are allowed by a strict
+    /// Content-Security-Policy. Elements which already have a `nonce` keep theirs.
+    pub script_style_nonce: Option<String>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            compressed: false,
+            sort_attributes: false,
+            emit_node_idx: false,
+            emit_vdom_id: false,
+            script_style_nonce: None,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// render with no new_lines and no indents
+    pub fn compressed() -> Self {
+        Self {
+            compressed: true,
+            ..Default::default()
+        }
+    }
+
+    /// set whether to render the attributes sorted by name
+    pub fn sort_attributes(mut self, sort_attributes: bool) -> Self {
+        self.sort_attributes = sort_attributes;
+        self
+    }
+
+    /// set whether to emit the index of each element in a `data-node-idx` attribute
+    pub fn emit_node_idx(mut self, emit_node_idx: bool) -> Self {
+        self.emit_node_idx = emit_node_idx;
+        self
+    }
+
+    /// set whether to emit the `data-vdom-id` of the elements with event listeners
+    pub fn emit_vdom_id(mut self, emit_vdom_id: bool) -> Self {
+        self.emit_vdom_id = emit_vdom_id;
+        self
+    }
+
+    /// set the nonce which is added to the `<script>` and `<style>` elements
+    pub fn script_style_nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.script_style_nonce = Some(nonce.into());
+        self
+    }
+}
+
 /// render node, elements to a writable buffer
 pub trait Render {
     // ISSUE: sublte difference in `render` and `render_to_string`:
@@ -45,6 +135,26 @@ pub trait Render {
         compressed: bool,
     ) -> fmt::Result;
 
+    /// render instance to a writable buffer with indention and the supplied render options.
+    /// By default only the `compressed` option is used, the implementors which support the
+    /// other options override this.
+    fn render_with_options(
+        &self,
+        buffer: &mut dyn fmt::Write,
+        indent: usize,
+        options: &RenderOptions,
+    ) -> fmt::Result {
+        self.render_with_indent(buffer, indent, options.compressed)
+    }
+
+    /// render to string using the supplied render options
+    fn render_to_string_with_options(&self, options: &RenderOptions) -> String {
+        let mut buffer = String::new();
+        self.render_with_options(&mut buffer, 0, options)
+            .expect("must render");
+        buffer
+    }
+
     /// render compressed html to string
     fn render_to_string(&self) -> String {
         let mut buffer = String::new();
@@ -52,6 +162,15 @@ pub trait Render {
         buffer
     }
 
+    /// render compressed html to a string which is pre-allocated with `capacity` bytes.
+    /// Rendering a large page into a string of the right size upfront avoids the
+    /// reallocations of the buffer as it grows.
+    fn render_to_string_with_capacity(&self, capacity: usize) -> String {
+        let mut buffer = String::with_capacity(capacity);
+        self.render_compressed(&mut buffer).expect("must render");
+        buffer
+    }
+
     /// render to string with nice indention
     fn render_to_string_pretty(&self) -> String {
         let mut buffer = String::new();
@@ -77,6 +196,21 @@ pub trait Render {
     }
 }
 
+/// render with the default options except for `compressed`,
+/// the implementors which support the render options forward their `render_with_indent` here
+fn render_with_default_options(
+    render: &impl Render,
+    buffer: &mut dyn fmt::Write,
+    indent: usize,
+    compressed: bool,
+) -> fmt::Result {
+    let options = RenderOptions {
+        compressed,
+        ..Default::default()
+    };
+    render.render_with_options(buffer, indent, &options)
+}
+
 impl<MSG> Render for Node<MSG> {
     fn render_with_indent(
         &self,
@@ -84,35 +218,236 @@ impl<MSG> Render for Node<MSG> {
         indent: usize,
         compressed: bool,
     ) -> fmt::Result {
-        match self {
-            Node::Element(element) => element.render_with_indent(buffer, indent, compressed),
-            Node::Leaf(leaf) => leaf.render_with_indent(buffer, indent, compressed),
-            Node::Fragment(nodes) => {
-                for node in nodes {
-                    node.render_with_indent(buffer, indent, compressed)?;
+        render_with_default_options(self, buffer, indent, compressed)
+    }
+
+    fn render_with_options(
+        &self,
+        mut buffer: &mut dyn fmt::Write,
+        indent: usize,
+        options: &RenderOptions,
+    ) -> fmt::Result {
+        let first_step = RenderStep::Node {
+            node: self,
+            indent,
+            options,
+            is_raw_text_element: false,
+        };
+        render_steps(first_step, &mut buffer, options, |_node_idx, _buffer| {})
+    }
+}
+
+/// a step in rendering a node tree. The steps are kept in an explicit stack instead of
+/// recursing into the children, so a very deep tree doesn't overflow the stack.
+enum RenderStep<'a, MSG> {
+    /// render the node and its descendants
+    Node {
+        node: &'a Node<MSG>,
+        indent: usize,
+        options: &'a RenderOptions,
+        /// the text of raw text elements such as `script` and `style` are not escaped
+        is_raw_text_element: bool,
+    },
+    /// render the element and its descendants
+    Element {
+        element: &'a Element<MSG>,
+        indent: usize,
+        options: &'a RenderOptions,
+        node_idx: usize,
+    },
+    /// add a new line and an indent when not compressed
+    Indent { indent: usize, compressed: bool },
+    /// render the inner html and the closing tag of the element
+    Close {
+        element: &'a Element<MSG>,
+        inner_html: String,
+    },
+}
+
+/// render the steps starting from `first_step`, `on_node` is called right before each node is
+/// rendered with the index of the node, which is counted depth-first
+fn render_steps<MSG, W, F>(
+    first_step: RenderStep<'_, MSG>,
+    buffer: &mut W,
+    options: &RenderOptions,
+    mut on_node: F,
+) -> fmt::Result
+where
+    W: fmt::Write,
+    F: FnMut(usize, &W),
+{
+    let compressed_options = RenderOptions {
+        compressed: true,
+        ..options.clone()
+    };
+    // the index of the next node, an element which is rendered on its own is the first node
+    let mut node_idx = match first_step {
+        RenderStep::Element { node_idx, .. } => node_idx + 1,
+        _ => 0,
+    };
+    let mut vdom_id = FIRST_VDOM_ID;
+    let mut stack = vec![first_step];
+    while let Some(step) = stack.pop() {
+        match step {
+            RenderStep::Node {
+                node,
+                indent,
+                options,
+                is_raw_text_element,
+            } => {
+                on_node(node_idx, &*buffer);
+                match node {
+                    Node::Element(element) => stack.push(RenderStep::Element {
+                        element,
+                        indent,
+                        options,
+                        node_idx,
+                    }),
+                    Node::Fragment(nodes) | Node::NodeList(nodes) => {
+                        stack.extend(nodes.iter().rev().map(|node| RenderStep::Node {
+                            node,
+                            indent,
+                            options,
+                            is_raw_text_element: false,
+                        }))
+                    }
+                    Node::Leaf(leaf) => match node.as_text() {
+                        Some(text) if is_raw_text_element => write!(buffer, "{text}")?,
+                        _ => leaf.render_with_options(buffer, indent, options)?,
+                    },
+                }
+                node_idx += 1;
+            }
+            RenderStep::Element {
+                element,
+                indent,
+                options,
+                node_idx: element_idx,
+            } => {
+                // the children of these elements are rendered without the new lines and indents
+                let options = if !options.compressed
+                    && WHITESPACE_SENSITIVE_ELEMENTS.contains(element.tag())
+                {
+                    &compressed_options
+                } else {
+                    options
+                };
+                let mut emitted_attrs = vec![];
+                if options.emit_node_idx {
+                    emitted_attrs.push(attributes::attr(DATA_NODE_IDX, element_idx));
+                }
+                // in the same depth-first order the client creates the elements and assigns the ids
+                if options.emit_vdom_id && has_listeners(element) {
+                    emitted_attrs.push(attributes::attr(DATA_VDOM_ID, vdom_id));
+                    vdom_id += 1;
+                }
+                let inner_html =
+                    render_opening_tag(element, buffer, indent, options, &emitted_attrs)?;
+
+                let children = element.children();
+                let first_child = children.first();
+                let is_first_child_text_node =
+                    first_child.map(|node| node.is_text()).unwrap_or(false);
+
+                // the html parser drops the first new line of these elements,
+                // so an extra one is added to keep the new line of the text
+                if WHITESPACE_SENSITIVE_ELEMENTS.contains(element.tag())
+                    && first_child
+                        .and_then(|node| node.as_text())
+                        .is_some_and(|text| text.starts_with('\n'))
+                {
+                    writeln!(buffer)?;
+                }
+
+                let is_raw_text_element = RAW_TEXT_ELEMENTS.contains(element.tag());
+                let is_lone_child_text_node = children.len() == 1 && is_first_child_text_node;
+
+                stack.push(RenderStep::Close {
+                    element,
+                    inner_html,
+                });
+                // do not indent if it is only text child node
+                if is_lone_child_text_node {
+                    stack.push(RenderStep::Node {
+                        node: &children[0],
+                        indent,
+                        options,
+                        is_raw_text_element,
+                    });
+                } else if !children.is_empty() {
+                    // otherwise print all child nodes with each line and indented,
+                    // pushed in reverse so they are rendered in order
+                    stack.push(RenderStep::Indent {
+                        indent,
+                        compressed: options.compressed,
+                    });
+                    for child in children.iter().rev() {
+                        stack.push(RenderStep::Node {
+                            node: child,
+                            indent: indent + 1,
+                            options,
+                            is_raw_text_element,
+                        });
+                        stack.push(RenderStep::Indent {
+                            indent: indent + 1,
+                            compressed: options.compressed,
+                        });
+                    }
+                }
+            }
+            RenderStep::Indent { indent, compressed } => {
+                if !compressed {
+                    write!(
+                        buffer,
+                        "\n{}",
+                        " ".repeat(DEFAULT_INDENT_SIZE).repeat(indent)
+                    )?;
                 }
-                Ok(())
             }
-            Node::NodeList(node_list) => {
-                for node in node_list {
-                    node.render_with_indent(buffer, indent, compressed)?;
+            RenderStep::Close {
+                element,
+                inner_html,
+            } => {
+                if !inner_html.is_empty() {
+                    write!(buffer, "{inner_html}")?;
+                }
+                if !element.self_closing {
+                    write!(buffer, "</{}>", element.tag())?;
                 }
-                Ok(())
             }
         }
     }
+    Ok(())
+}
+
+/// returns true if the element has event listeners, which are assigned a `data-vdom-id`
+fn has_listeners<MSG>(element: &Element<MSG>) -> bool {
+    element.attrs.iter().any(|attr| {
+        !attributes::partition_callbacks_from_plain_styles_and_func_calls(attr)
+            .listeners
+            .is_empty()
+    })
 }
 
 impl Render for Leaf {
     fn render_with_indent(
+        &self,
+        buffer: &mut dyn fmt::Write,
+        indent: usize,
+        compressed: bool,
+    ) -> fmt::Result {
+        render_with_default_options(self, buffer, indent, compressed)
+    }
+
+    fn render_with_options(
         &self,
         buffer: &mut dyn fmt::Write,
         _indent: usize,
-        _compressed: bool,
+        _options: &RenderOptions,
     ) -> fmt::Result {
         match self {
             Leaf::Text(text) => {
-                write!(buffer, "{text}")
+                write!(buffer, "{}", escape_html_text(text))
             }
             Leaf::SafeHtml(html) => {
                 //TODO: html escape this one
@@ -128,6 +463,39 @@ impl Render for Leaf {
     }
 }
 
+/// escape the characters of the text content such that it will not be interpreted as html markup
+fn escape_html_text(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>']) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            _ => escaped.push(ch),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// escape the characters of the attribute value such that it won't end the quoted value
+fn escape_attribute_value(value: &str) -> Cow<'_, str> {
+    if !value.contains(['&', '"']) {
+        return Cow::Borrowed(value);
+    }
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
 fn extract_inner_html<MSG>(merged_attributes: &[Attribute<MSG>]) -> String {
     merged_attributes
         .iter()
@@ -156,70 +524,135 @@ impl<MSG> Render for Element<MSG> {
         indent: usize,
         compressed: bool,
     ) -> fmt::Result {
-        write!(buffer, "<{}", self.tag())?;
-
-        let ref_attrs: Vec<&Attribute<MSG>> = self.attributes().iter().collect();
-        let merged_attributes: Vec<Attribute<MSG>> =
-            mt_dom::merge_attributes_of_same_name(&ref_attrs);
-
-        for attr in &merged_attributes {
-            // dont render empty attribute
-            // TODO: must check the attribute value for empty value
-            if !attr.name().is_empty() {
-                write!(buffer, " ")?;
-                attr.render_with_indent(buffer, indent, compressed)?;
-            }
-        }
+        render_with_default_options(self, buffer, indent, compressed)
+    }
 
-        if self.self_closing {
-            write!(buffer, "/>")?;
-        } else {
-            write!(buffer, ">")?;
-        }
+    fn render_with_options(
+        &self,
+        mut buffer: &mut dyn fmt::Write,
+        indent: usize,
+        options: &RenderOptions,
+    ) -> fmt::Result {
+        let first_step = RenderStep::Element {
+            element: self,
+            indent,
+            options,
+            node_idx: 0,
+        };
+        render_steps(first_step, &mut buffer, options, |_node_idx, _buffer| {})
+    }
+}
 
-        let children = self.children();
-        let first_child = children.get(0);
-        let is_first_child_text_node = first_child.map(|node| node.is_text()).unwrap_or(false);
-
-        let is_lone_child_text_node = children.len() == 1 && is_first_child_text_node;
-
-        // do not indent if it is only text child node
-        if is_lone_child_text_node {
-            first_child
-                .unwrap()
-                .render_with_indent(buffer, indent, compressed)?;
-        } else {
-            // otherwise print all child nodes with each line and indented
-            for child in self.children() {
-                self.maybe_indent(buffer, indent + 1, compressed)?;
-                child.render_with_indent(buffer, indent + 1, compressed)?;
-            }
-        }
+/// render the opening tag of the element along with its attributes and the `emitted_attrs`,
+/// returns the inner html which is rendered before the closing tag
+fn render_opening_tag<MSG, W>(
+    element: &Element<MSG>,
+    buffer: &mut W,
+    indent: usize,
+    options: &RenderOptions,
+    emitted_attrs: &[Attribute<MSG>],
+) -> Result<String, fmt::Error>
+where
+    W: fmt::Write,
+{
+    write!(buffer, "<{}", element.tag())?;
+
+    let ref_attrs: Vec<&Attribute<MSG>> =
+        element.attributes().iter().chain(emitted_attrs).collect();
+    let mut merged_attributes: Vec<Attribute<MSG>> =
+        mt_dom::merge_attributes_of_same_name(&ref_attrs);
+
+    if options.sort_attributes {
+        merged_attributes.sort_by(|a, b| a.name().cmp(b.name()));
+    }
 
-        // do not make a new line it if is only a text child node or it has no child nodes
-        if !is_lone_child_text_node && !children.is_empty() {
-            self.maybe_indent(buffer, indent, compressed)?;
+    for attr in &merged_attributes {
+        // dont render empty attribute and the attributes which are only used in diffing
+        // TODO: must check the attribute value for empty value
+        if !attr.name().is_empty() && !attributes::VDOM_ATTRIBUTES.contains(attr.name()) {
+            write!(buffer, " ")?;
+            attr.render_with_options(buffer, indent, options)?;
         }
+    }
 
-        let inner_html = extract_inner_html(&merged_attributes);
-        if !inner_html.is_empty() {
-            write!(buffer, "{inner_html}")?;
+    if let Some(nonce) = &options.script_style_nonce {
+        let has_nonce = merged_attributes.iter().any(|attr| *attr.name() == "nonce");
+        if RAW_TEXT_ELEMENTS.contains(element.tag()) && !has_nonce {
+            write!(buffer, " nonce=\"{}\"", escape_attribute_value(nonce))?;
         }
+    }
 
-        if !self.self_closing {
-            write!(buffer, "</{}>", self.tag())?;
-        }
-        Ok(())
+    if element.self_closing {
+        write!(buffer, "/>")?;
+    } else {
+        write!(buffer, ">")?;
     }
+    Ok(extract_inner_html(&merged_attributes))
+}
+
+/// render the node compressed, along with the byte offset in the html where each node starts,
+/// ie: for tooling which hydrates parts of the server rendered html.
+///
+/// Each item is the `(node_idx, offset)` of the node, where the `node_idx` is counted
+/// depth-first the same way the nodes are traversed in the diff, and as emitted in the
+/// `data-node-idx` attribute. The offset of an element points at its `<`, the offset of
+/// a text node points at its first character. Fragments have the same offset as their first
+/// child since they are not rendered themselves.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::*};
+///
+/// let view: Node<()> = ul([], [li([], [text("one")])]);
+/// let (html, offsets) = render_to_string_with_node_offsets(&view);
+/// assert_eq!(html, "<ul><li>one</li></ul>");
+/// assert_eq!(offsets, [(0, 0), (1, 4), (2, 8)]);
+/// ```
+pub fn render_to_string_with_node_offsets<MSG>(node: &Node<MSG>) -> (String, Vec<(usize, usize)>) {
+    let mut buffer = String::new();
+    let mut offsets = vec![];
+    let options = RenderOptions::compressed();
+    let first_step = RenderStep::Node {
+        node,
+        indent: 0,
+        options: &options,
+        is_raw_text_element: false,
+    };
+    render_steps(
+        first_step,
+        &mut buffer,
+        &options,
+        |node_idx, buffer: &String| offsets.push((node_idx, buffer.len())),
+    )
+    .expect("must render");
+    (buffer, offsets)
 }
 
 impl<MSG> Render for Attribute<MSG> {
     fn render_with_indent(
+        &self,
+        buffer: &mut dyn fmt::Write,
+        indent: usize,
+        compressed: bool,
+    ) -> fmt::Result {
+        render_with_default_options(self, buffer, indent, compressed)
+    }
+
+    fn render_with_options(
         &self,
         buffer: &mut dyn fmt::Write,
         _indent: usize,
-        _compressed: bool,
+        _options: &RenderOptions,
     ) -> fmt::Result {
+        if *self.name() == "style" {
+            if let Some(merged_styles) = attributes::merge_style_declarations(self) {
+                return write!(
+                    buffer,
+                    "{}=\"{}\"",
+                    self.name(),
+                    escape_attribute_value(&merged_styles)
+                );
+            }
+        }
         let SegregatedAttributes {
             listeners: _,
             plain_values,
@@ -246,9 +679,17 @@ impl<MSG> Render for Attribute<MSG> {
 
         if !should_skip_attribute {
             if let Some(merged_plain_values) =
-                attributes::merge_plain_attributes_values(&plain_values)
+                attributes::merge_plain_attributes_values_with_separator(
+                    &plain_values,
+                    attributes::value_separator(self.name()),
+                )
             {
-                write!(buffer, "{}=\"{}\"", self.name(), merged_plain_values)?;
+                write!(
+                    buffer,
+                    "{}=\"{}\"",
+                    self.name(),
+                    escape_attribute_value(&merged_plain_values)
+                )?;
             }
             if let Some(merged_styles) = attributes::merge_styles_attributes_values(&styles) {
                 write!(buffer, "{}=\"{}\"", self.name(), merged_styles)?;
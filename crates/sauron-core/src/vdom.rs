@@ -8,11 +8,27 @@
 pub use crate::html::attributes::AttributeValue;
 use crate::{dom::Event, html::attributes};
 pub use leaf::Leaf;
+pub(crate) use node_trait::child_nodes;
 pub use node_trait::NodeTrait;
+pub use normalize::normalize_text;
+pub use sanitize::SanitizePolicy;
+#[cfg(feature = "with-serde")]
+pub use deserialize::DeserializeNode;
+#[cfg(feature = "with-serde")]
+pub use serialize::SerializeNode;
+pub use validate::{validate, ValidationWarning};
 
 pub mod leaf;
+#[cfg(feature = "with-serde")]
+mod deserialize;
+mod diff_walk;
 pub(crate) mod map_msg;
 mod node_trait;
+mod normalize;
+mod sanitize;
+#[cfg(feature = "with-serde")]
+mod serialize;
+mod validate;
 
 /// namespace type in node, which could be change to an enum
 pub type Namespace = &'static str;
@@ -49,9 +65,49 @@ pub static KEY: &str = "key";
 
 /// This is a sauron html specific functionality
 /// diff 2 nodes with attribute using `&'static str` instead of generic ATT
+///
+/// The order of the attributes with different names doesn't matter, reordering them
+/// doesn't create any patch. The order of the values of the same attribute name is significant,
+/// ie: the later style overrides the former and the listeners are called in order.
+///
+/// The levels of a very deep tree are diffed with an explicit stack, so the diffing doesn't
+/// overflow the stack.
 pub fn diff<'a, MSG>(old: &'a Node<MSG>, new: &'a Node<MSG>) -> Vec<Patch<'a, MSG>>
 where
     MSG: 'static,
+{
+    diff_with(old, new, |_old, _new| false)
+}
+
+/// diff 2 nodes, where `is_equal` is a user supplied comparison which is called on each pair of
+/// old and new node before diffing them. If it returns true the node and its descendants
+/// are considered equal and no patches are created for them.
+///
+/// This is useful for skipping the diffing of big subtrees by cheaply comparing only
+/// an `id` or a version stored in an attribute.
+///
+/// Note: `is_equal` must only return true when the old and new node renders the same
+/// html, including its descendants and event listeners, otherwise the DOM will not reflect
+/// the new view.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::*, html::attributes::*, vdom::diff_with};
+///
+/// let old: Node<()> = div([attr("data-version", 1)], [text("old content")]);
+/// let new: Node<()> = div([attr("data-version", 1)], [text("new content")]);
+/// let same_version = |old: &Node<()>, new: &Node<()>| {
+///     old.attribute_value(&"data-version") == new.attribute_value(&"data-version")
+/// };
+/// assert!(diff_with(&old, &new, same_version).is_empty());
+/// ```
+pub fn diff_with<'a, MSG, F>(
+    old: &'a Node<MSG>,
+    new: &'a Node<MSG>,
+    is_equal: F,
+) -> Vec<Patch<'a, MSG>>
+where
+    MSG: 'static,
+    F: Fn(&'a Node<MSG>, &'a Node<MSG>) -> bool,
 {
     use crate::html::attributes::Special;
     use map_msg::NodeMapMsg;
@@ -62,13 +118,14 @@ where
         let new_skip_criteria = new_node.attribute_value(&"skip_criteria");
         let old_skip_criteria = old_node.attribute_value(&"skip_criteria");
         // if old and new skip_criteria didn't change skip diffing this nodes
-        match (new_skip_criteria, old_skip_criteria) {
+        let skip = match (new_skip_criteria, old_skip_criteria) {
             (Some(new), Some(old)) => new == old,
             _ => new_node
                 .get_value("skip")
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false),
-        }
+        };
+        skip || is_equal(old_node, new_node)
     };
 
     // check if the replace attribute evaluates to true,
@@ -87,5 +144,90 @@ where
 
         explicit_replace_attr || forbid_recycle
     };
-    mt_dom::diff::diff_with_functions(old, new, &KEY, &skip, &replace)
+    diff_walk::diff_walk(old, new, &skip, &replace)
+        .into_iter()
+        .filter(|patch| !is_reordered_attributes(old, patch))
+        .collect()
+}
+
+/// drop the node with an explicit stack, since the drop of a node recurses on each level of
+/// the tree, which overflows the stack on a very deep tree
+#[cfg(feature = "with-dom")]
+pub(crate) fn drop_without_recursion<MSG>(node: Node<MSG>) {
+    let mut stack = vec![node];
+    while let Some(mut node) = stack.pop() {
+        match &mut node {
+            Node::Element(element) => stack.append(&mut element.children),
+            Node::Fragment(nodes) | Node::NodeList(nodes) => stack.append(nodes),
+            Node::Leaf(_) => (),
+        }
+    }
+}
+
+/// returns true if this is an `AddAttributes` patch where the values of each attribute
+/// are the same with the old element, only the order of the attribute names has changed
+fn is_reordered_attributes<MSG>(old: &Node<MSG>, patch: &Patch<MSG>) -> bool {
+    let PatchType::AddAttributes { attrs } = &patch.patch_type else {
+        return false;
+    };
+    let Some(Node::Element(old_element)) = node_at_path(old, &patch.patch_path.path) else {
+        return false;
+    };
+    let values_of = |attrs: &[&'_ Attribute<MSG>], name: &AttributeName| {
+        attrs
+            .iter()
+            .filter(|att| att.name() == name)
+            .flat_map(|att| att.value())
+            .collect::<Vec<_>>()
+    };
+    let old_attrs: Vec<&Attribute<MSG>> = old_element.attributes().iter().collect();
+    attrs
+        .iter()
+        .all(|att| values_of(&old_attrs, att.name()) == values_of(attrs, att.name()))
+}
+
+/// the node at this path, where each item in the path is the index of the child
+pub(crate) fn node_at_path<'a, MSG>(node: &'a Node<MSG>, path: &[usize]) -> Option<&'a Node<MSG>> {
+    // iterative, so a very deep path doesn't overflow the stack
+    path.iter()
+        .try_fold(node, |node, idx| child_nodes(node).get(*idx))
+}
+
+/// the mutable node at this path, where each item in the path is the index of the child
+#[cfg(feature = "with-dom")]
+pub(crate) fn node_at_path_mut<'a, MSG>(
+    node: &'a mut Node<MSG>,
+    path: &[usize],
+) -> Option<&'a mut Node<MSG>> {
+    let mut node = node;
+    for idx in path {
+        let children = match node {
+            Node::Element(element) => &mut element.children,
+            Node::Fragment(nodes) | Node::NodeList(nodes) => nodes,
+            Node::Leaf(_) => return None,
+        };
+        node = children.get_mut(*idx)?;
+    }
+    Some(node)
+}
+
+/// the path of the first element in this tree, including this node, with a matching `id`
+#[cfg(feature = "with-dom")]
+pub(crate) fn path_of_id<MSG>(node: &Node<MSG>, id: &str) -> Option<Vec<usize>> {
+    use crate::html::attributes::Special;
+
+    // an explicit stack instead of recursion, so a very deep tree doesn't overflow the stack
+    let mut stack = vec![(node, vec![])];
+    while let Some((node, path)) = stack.pop() {
+        if node.get_value("id").and_then(|v| v.as_str()) == Some(id) {
+            return Some(path);
+        }
+        // push in reverse so the children are visited in document order
+        for (idx, child) in child_nodes(node).iter().enumerate().rev() {
+            let mut child_path = path.clone();
+            child_path.push(idx);
+            stack.push((child, child_path));
+        }
+    }
+    None
 }
@@ -0,0 +1,194 @@
+//! rebuild the vdom from the canonical json tree of [`SerializeNode`](crate::vdom::SerializeNode)
+use crate::html::{
+    attributes::{AttributeValue, Listener, Value},
+    element_ns, lookup,
+};
+use crate::vdom::{Attribute, Leaf, Node};
+use serde::{Deserialize, Deserializer};
+use std::{borrow::Cow, cell::RefCell, collections::BTreeMap, collections::BTreeSet};
+
+thread_local! {
+    /// the tag and attribute names are `&'static str`, the names are leaked once
+    /// and reused in the subsequent deserializations
+    static NAMES: RefCell<BTreeSet<&'static str>> = RefCell::new(BTreeSet::new());
+}
+
+/// A vdom node which is deserialized from the canonical json tree, ie: for snapshots and fixtures.
+///
+/// Event listeners can not be rebuilt from json, so the `{"type": "listener"}` markers are
+/// deserialized as inert listeners which never dispatch a msg.
+/// The namespaces of the attributes, such as `xlink`, are not part of the json and are not
+/// restored.
+/// # Examples
+/// ```rust,ignore
+/// use sauron::{*, vdom::{DeserializeNode, SerializeNode}};
+///
+/// let json = serde_json::to_string(&SerializeNode(&view)).unwrap();
+/// let DeserializeNode(node) = serde_json::from_str::<DeserializeNode<Msg>>(&json).unwrap();
+/// assert_eq!(node.render_to_string(), view.render_to_string());
+/// ```
+pub struct DeserializeNode<MSG>(pub Node<MSG>);
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum JsonNode {
+    Element {
+        tag: String,
+        #[serde(default)]
+        namespace: Option<String>,
+        #[serde(default)]
+        self_closing: bool,
+        #[serde(default)]
+        attributes: BTreeMap<String, Vec<JsonValue>>,
+        #[serde(default)]
+        children: Vec<JsonNode>,
+    },
+    Text {
+        value: String,
+    },
+    SafeHtml {
+        value: String,
+    },
+    Comment {
+        value: String,
+    },
+    Doctype {
+        value: String,
+    },
+    Fragment {
+        children: Vec<JsonNode>,
+    },
+    NodeList {
+        children: Vec<JsonNode>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonValue {
+    Bool(bool),
+    String(String),
+    Marker {
+        #[serde(rename = "type")]
+        marker: String,
+    },
+}
+
+impl<'de, MSG> Deserialize<'de> for DeserializeNode<MSG>
+where
+    MSG: 'static,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        JsonNode::deserialize(deserializer).map(|node| DeserializeNode(node.into_node()))
+    }
+}
+
+impl JsonNode {
+    fn into_node<MSG: 'static>(self) -> Node<MSG> {
+        let into_nodes = |children: Vec<JsonNode>| -> Vec<Node<MSG>> {
+            children.into_iter().map(JsonNode::into_node).collect()
+        };
+        match self {
+            JsonNode::Element {
+                tag,
+                namespace,
+                self_closing,
+                attributes,
+                children,
+            } => {
+                let attributes = attributes.into_iter().map(|(name, values)| {
+                    let values = values.into_iter().filter_map(JsonValue::into_value);
+                    Attribute::with_multiple_values(None, intern(name), values.collect())
+                });
+                element_ns(
+                    namespace.map(intern),
+                    intern(tag),
+                    attributes,
+                    into_nodes(children),
+                    self_closing,
+                )
+            }
+            JsonNode::Text { value } => Node::Leaf(Leaf::Text(Cow::from(value))),
+            JsonNode::SafeHtml { value } => Node::Leaf(Leaf::SafeHtml(Cow::from(value))),
+            JsonNode::Comment { value } => Node::Leaf(Leaf::Comment(Cow::from(value))),
+            JsonNode::Doctype { value } => Node::Leaf(Leaf::DocType(Cow::from(value))),
+            JsonNode::Fragment { children } => Node::Fragment(into_nodes(children)),
+            JsonNode::NodeList { children } => Node::NodeList(into_nodes(children)),
+        }
+    }
+}
+
+impl JsonValue {
+    fn into_value<MSG: 'static>(self) -> Option<AttributeValue<MSG>> {
+        match self {
+            JsonValue::Bool(value) => Some(AttributeValue::Simple(Value::from(value))),
+            JsonValue::String(value) => Some(AttributeValue::Simple(Value::from(value))),
+            JsonValue::Marker { marker } if marker == "listener" => {
+                Some(AttributeValue::EventListener(Listener::maybe(|_| None)))
+            }
+            JsonValue::Marker { .. } => None,
+        }
+    }
+}
+
+/// the static str of this name, the known html and svg tags and attributes are looked up,
+/// the other names are leaked only the first time they are seen
+fn intern(name: String) -> &'static str {
+    if let Some(known) = lookup::match_tag(&name).or_else(|| lookup::match_attribute(&name)) {
+        return known;
+    }
+    NAMES.with(|names| {
+        let mut names = names.borrow_mut();
+        if let Some(existing) = names.get(name.as_str()) {
+            *existing
+        } else {
+            let leaked: &'static str = Box::leak(name.into_boxed_str());
+            names.insert(leaked);
+            leaked
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::html::{attributes::*, events::*, *};
+    use crate::render::Render;
+    use crate::vdom::SerializeNode;
+
+    #[test]
+    fn round_trip_renders_the_same() {
+        let view: Node<()> = div(
+            [class("app"), key(1)],
+            [
+                input([r#type("checkbox"), checked(true), disabled(false)], []),
+                button([class("btn"), on_click(|_| ())], [text("Click")]),
+                p([styles([("color", "red")])], [text("one"), text("two")]),
+                comment("end"),
+            ],
+        );
+        let json = serde_json::to_string(&SerializeNode(&view)).expect("must serialize");
+        let DeserializeNode(node) =
+            serde_json::from_str::<DeserializeNode<()>>(&json).expect("must deserialize");
+
+        assert_eq!(node.render_to_string(), view.render_to_string());
+        assert_eq!(
+            serde_json::to_string(&SerializeNode(&node)).expect("must serialize"),
+            json
+        );
+    }
+
+    #[test]
+    fn only_the_unknown_names_are_leaked() {
+        assert_eq!(intern("div".to_string()), "div");
+        assert_eq!(intern("class".to_string()), "class");
+        assert_eq!(intern("my-widget".to_string()), "my-widget");
+        assert_eq!(intern("my-widget".to_string()), "my-widget");
+        NAMES.with(|names| {
+            let names = names.borrow();
+            assert!(names.contains("my-widget"));
+            assert!(!names.contains("div"));
+            assert!(!names.contains("class"));
+        });
+    }
+}
@@ -0,0 +1,379 @@
+//! diffing with an explicit stack for the levels of the trees which can not be diffed by mt-dom,
+//! which are the levels of a very deep tree, since mt-dom recurses on each level of the tree,
+//! and the elements whose children mix keyed elements with unkeyed elements or text nodes,
+//! since mt-dom doesn't define how they are matched. The rest of the subtrees are diffed by mt-dom.
+use crate::vdom::{
+    child_nodes, Attribute, AttributeName, Element, Node, NodeTrait, Patch, Tag, KEY,
+};
+use mt_dom::{diff_recursive, TreePath};
+use std::collections::HashSet;
+
+/// the subtrees which are at most this many levels deep are diffed by mt-dom,
+/// the levels above them are walked with an explicit stack so a very deep tree doesn't
+/// overflow the stack, especially in wasm where the stack is small.
+const MAX_RECURSION_DEPTH: usize = 64;
+
+/// the old and new node which are diffed next, along with the path of the old node
+type Pair<'a, MSG> = (&'a Node<MSG>, &'a Node<MSG>, TreePath);
+
+/// diff the trees with the same `skip` and `replace` functions which are passed to mt-dom.
+///
+/// The pairs of nodes are handed to mt-dom as long as it can diff them, otherwise they are
+/// walked here and their children are checked again, so the walk only goes as far down as the
+/// levels which mt-dom can not diff.
+pub(crate) fn diff_walk<'a, MSG, Skip, Rep>(
+    old: &'a Node<MSG>,
+    new: &'a Node<MSG>,
+    skip: &Skip,
+    replace: &Rep,
+) -> Vec<Patch<'a, MSG>>
+where
+    MSG: 'static,
+    Skip: Fn(&'a Node<MSG>, &'a Node<MSG>) -> bool,
+    Rep: Fn(&'a Node<MSG>, &'a Node<MSG>) -> bool,
+{
+    let mut patches = vec![];
+    let mut stack: Vec<Pair<'a, MSG>> = vec![(old, new, TreePath::root())];
+    while let Some((old, new, path)) = stack.pop() {
+        if is_diffed_by_mt_dom(old) && is_diffed_by_mt_dom(new) {
+            patches.extend(diff_recursive(old, new, &path, &KEY, skip, replace));
+            continue;
+        }
+        if skip(old, new) {
+            continue;
+        }
+        match (old, new) {
+            (Node::Element(old_element), Node::Element(new_element))
+                if old_element.tag == new_element.tag
+                    && old_element.namespace == new_element.namespace
+                    && !replace(old, new) =>
+            {
+                patches.extend(diff_attributes(old_element, new_element, &path));
+                let (children_patches, pairs) = diff_children(old_element, new_element, &path);
+                patches.extend(children_patches);
+                // pushed in reverse so the children are diffed in order
+                stack.extend(pairs.into_iter().rev());
+            }
+            // the fragments which are not too deep are left to mt-dom, along with their mixed
+            // keyed descendants
+            _ if is_within_max_depth(old, |_| true) && is_within_max_depth(new, |_| true) => {
+                patches.extend(diff_recursive(old, new, &path, &KEY, skip, replace));
+            }
+            // the deep fragments and the nodes of different types are replaced as a whole
+            _ => patches.push(Patch::replace_node(tag_of(old), path, vec![new])),
+        }
+    }
+    patches
+}
+
+/// returns true if this subtree is at most [`MAX_RECURSION_DEPTH`] levels deep and has no
+/// mixed keyed children, so it can be diffed by mt-dom
+fn is_diffed_by_mt_dom<MSG>(root: &Node<MSG>) -> bool {
+    is_within_max_depth(root, |node| !has_mixed_keyed_children(node))
+}
+
+/// returns true if this subtree is at most [`MAX_RECURSION_DEPTH`] levels deep and all of its
+/// nodes are allowed.
+/// Only the first [`MAX_RECURSION_DEPTH`] levels are visited, and the visit stops at the first
+/// node which is not allowed, so the deep trees are not traversed as a whole on each level.
+fn is_within_max_depth<MSG>(root: &Node<MSG>, is_allowed: impl Fn(&Node<MSG>) -> bool) -> bool {
+    let mut stack = vec![(root, 1)];
+    while let Some((node, level)) = stack.pop() {
+        if !is_allowed(node) {
+            return false;
+        }
+        let children = child_nodes(node);
+        if children.is_empty() {
+            continue;
+        }
+        if level == MAX_RECURSION_DEPTH {
+            return false;
+        }
+        stack.extend(children.iter().map(|child| (child, level + 1)));
+    }
+    true
+}
+
+/// returns true if the children of this element are keyed but can not be matched by mt-dom,
+/// which is when some of the child elements have a key while the others don't, or when the
+/// keyed elements are mixed with text nodes, since mt-dom replaces the text nodes which are
+/// reordered along with the keyed elements instead of moving them
+fn has_mixed_keyed_children<MSG>(node: &Node<MSG>) -> bool {
+    let Node::Element(element) = node else {
+        return false;
+    };
+    let children = element.children();
+    let has_keyed = children.iter().any(is_keyed);
+    let has_unkeyed = children
+        .iter()
+        .any(|child| (matches!(child, Node::Element(_)) && !is_keyed(child)) || child.is_text());
+    has_keyed && has_unkeyed
+}
+
+fn is_keyed<MSG>(node: &Node<MSG>) -> bool {
+    node.attribute_value(&KEY).is_some()
+}
+
+/// the path of the child at this index
+fn child_path(path: &TreePath, idx: usize) -> TreePath {
+    let mut child_path = path.path.clone();
+    child_path.push(idx);
+    TreePath::new(child_path)
+}
+
+/// the tag of the node, if it is an element
+fn tag_of<MSG>(node: &Node<MSG>) -> Option<&Tag> {
+    match node {
+        Node::Element(element) => Some(&element.tag),
+        _ => None,
+    }
+}
+
+/// the patches of the attributes of the same element.
+/// The values of the attributes of the same name are compared together,
+/// so reordering the attributes with different names doesn't create a patch.
+fn diff_attributes<'a, MSG>(
+    old: &'a Element<MSG>,
+    new: &'a Element<MSG>,
+    path: &TreePath,
+) -> Vec<Patch<'a, MSG>> {
+    let values_of = |element: &'a Element<MSG>, name: &AttributeName| {
+        element
+            .attributes()
+            .iter()
+            .filter(|att| att.name() == name)
+            .flat_map(|att| att.value())
+            .collect::<Vec<_>>()
+    };
+    let added: Vec<&'a Attribute<MSG>> = new
+        .attributes()
+        .iter()
+        .filter(|att| values_of(old, att.name()) != values_of(new, att.name()))
+        .collect();
+    let removed: Vec<&'a Attribute<MSG>> = old
+        .attributes()
+        .iter()
+        .filter(|att| values_of(new, att.name()).is_empty())
+        .collect();
+
+    let mut patches = vec![];
+    if !added.is_empty() {
+        patches.push(Patch::add_attributes(&new.tag, path.clone(), added));
+    }
+    if !removed.is_empty() {
+        patches.push(Patch::remove_attributes(&old.tag, path.clone(), removed));
+    }
+    patches
+}
+
+/// the patches which remove, move and insert the children of the same element, along with the
+/// pairs of the old and new children which are diffed next.
+///
+/// The keyed children are matched by their key first and the text nodes by their text, then
+/// the rest of the unkeyed children are matched by their position among each other.
+/// The matched children which are already in order stay in place, the rest of the matched
+/// children are moved and the unmatched new children are inserted around them.
+fn diff_children<'a, MSG>(
+    old: &'a Element<MSG>,
+    new: &'a Element<MSG>,
+    path: &TreePath,
+) -> (Vec<Patch<'a, MSG>>, Vec<Pair<'a, MSG>>) {
+    let old_children = old.children();
+    let new_children = new.children();
+
+    // the index of the old child which is matched to each new child
+    let mut matches: Vec<Option<usize>> = vec![None; new_children.len()];
+    let mut is_matched = vec![false; old_children.len()];
+    for (new_idx, new_child) in new_children.iter().enumerate() {
+        let Some(new_key) = new_child.attribute_value(&KEY) else {
+            continue;
+        };
+        let old_idx = (0..old_children.len()).find(|&old_idx| {
+            !is_matched[old_idx]
+                && old_children[old_idx].attribute_value(&KEY).as_ref() == Some(&new_key)
+        });
+        if let Some(old_idx) = old_idx {
+            is_matched[old_idx] = true;
+            matches[new_idx] = Some(old_idx);
+        }
+    }
+    // the text nodes are matched by their text, so the reordered text nodes are moved
+    for (new_idx, new_child) in new_children.iter().enumerate() {
+        let Some(new_text) = new_child.as_text() else {
+            continue;
+        };
+        let old_idx = (0..old_children.len()).find(|&old_idx| {
+            !is_matched[old_idx] && old_children[old_idx].as_text() == Some(new_text)
+        });
+        if let Some(old_idx) = old_idx {
+            is_matched[old_idx] = true;
+            matches[new_idx] = Some(old_idx);
+        }
+    }
+    let unkeyed_old: Vec<usize> = (0..old_children.len())
+        .filter(|&old_idx| !is_matched[old_idx] && !is_keyed(&old_children[old_idx]))
+        .collect();
+    let mut unkeyed_old = unkeyed_old.into_iter();
+    for (new_idx, new_child) in new_children.iter().enumerate() {
+        if !is_keyed(new_child) && matches[new_idx].is_none() {
+            if let Some(old_idx) = unkeyed_old.next() {
+                is_matched[old_idx] = true;
+                matches[new_idx] = Some(old_idx);
+            }
+        }
+    }
+
+    let mut patches: Vec<Patch<'a, MSG>> = old_children
+        .iter()
+        .enumerate()
+        .filter(|(old_idx, _)| !is_matched[*old_idx])
+        .map(|(old_idx, old_child)| {
+            Patch::remove_node(tag_of(old_child), child_path(path, old_idx))
+        })
+        .collect();
+
+    let placement = Placement {
+        old_children,
+        new_children,
+        matches: &matches,
+        path,
+    };
+    let in_order =
+        longest_increasing_subsequence(&matches.iter().flatten().copied().collect::<Vec<_>>());
+    // the new children which are not placed yet, they are placed before the next child which
+    // stays in place
+    let mut unplaced: Vec<usize> = vec![];
+    let mut last_in_order = None;
+    for (new_idx, old_idx) in matches.iter().enumerate() {
+        match old_idx {
+            Some(old_idx) if in_order.contains(old_idx) => {
+                patches.extend(placement.place_before(*old_idx, &unplaced));
+                unplaced.clear();
+                last_in_order = Some(*old_idx);
+            }
+            _ => unplaced.push(new_idx),
+        }
+    }
+    // the children after the last child which stays in place, the last moved child is moved
+    // after it and the rest are placed around the moved child
+    let last_moved = unplaced
+        .iter()
+        .rposition(|new_idx| matches[*new_idx].is_some());
+    let appended = if let Some(last_moved) = last_moved {
+        let moved_idx = matches[unplaced[last_moved]].expect("must be a moved child");
+        let target_idx =
+            last_in_order.expect("the moved children are moved around the children in order");
+        patches.push(Patch::move_after_node(
+            tag_of(&old_children[target_idx]),
+            child_path(path, target_idx),
+            vec![child_path(path, moved_idx)],
+        ));
+        patches.extend(placement.place_before(moved_idx, &unplaced[..last_moved]));
+        &unplaced[last_moved + 1..]
+    } else {
+        &unplaced[..]
+    };
+    if !appended.is_empty() {
+        patches.push(Patch::append_children(
+            Some(&old.tag),
+            path.clone(),
+            appended
+                .iter()
+                .map(|new_idx| &new_children[*new_idx])
+                .collect::<Vec<_>>(),
+        ));
+    }
+
+    let pairs = matches
+        .iter()
+        .enumerate()
+        .filter_map(|(new_idx, old_idx)| {
+            old_idx.map(|old_idx| {
+                (
+                    &old_children[old_idx],
+                    &new_children[new_idx],
+                    child_path(path, old_idx),
+                )
+            })
+        })
+        .collect();
+    (patches, pairs)
+}
+
+/// the children of the same element which are moved or inserted
+struct Placement<'a, 'p, MSG> {
+    old_children: &'a [Node<MSG>],
+    new_children: &'a [Node<MSG>],
+    matches: &'p [Option<usize>],
+    path: &'p TreePath,
+}
+
+impl<'a, MSG> Placement<'a, '_, MSG> {
+    /// the patches which place these new children right before the old child at `target_idx`,
+    /// the matched children are moved and the rest are inserted
+    fn place_before(&self, target_idx: usize, new_indexes: &[usize]) -> Vec<Patch<'a, MSG>> {
+        let (old_children, new_children) = (self.old_children, self.new_children);
+        let target_tag = tag_of(&old_children[target_idx]);
+        let target_path = child_path(self.path, target_idx);
+        let mut patches = vec![];
+        // the consecutive children which are all moved or all inserted are placed together
+        let mut new_indexes = new_indexes;
+        while let Some(first) = new_indexes.first() {
+            let is_moved = self.matches[*first].is_some();
+            let run_len = new_indexes
+                .iter()
+                .take_while(|new_idx| self.matches[**new_idx].is_some() == is_moved)
+                .count();
+            let (run, rest) = new_indexes.split_at(run_len);
+            if is_moved {
+                let nodes_path = run
+                    .iter()
+                    .flat_map(|new_idx| self.matches[*new_idx])
+                    .map(|old_idx| child_path(self.path, old_idx))
+                    .collect::<Vec<_>>();
+                patches.push(Patch::move_before_node(
+                    target_tag,
+                    target_path.clone(),
+                    nodes_path,
+                ));
+            } else {
+                let nodes = run
+                    .iter()
+                    .map(|new_idx| &new_children[*new_idx])
+                    .collect::<Vec<_>>();
+                patches.push(Patch::insert_before_node(
+                    target_tag,
+                    target_path.clone(),
+                    nodes,
+                ));
+            }
+            new_indexes = rest;
+        }
+        patches
+    }
+}
+
+/// the longest subsequence of the values which is in increasing order,
+/// the matched children at these old indexes don't need to be moved
+fn longest_increasing_subsequence(values: &[usize]) -> HashSet<usize> {
+    // the index of the smallest last value of the increasing subsequences of each length
+    let mut tails: Vec<usize> = vec![];
+    // the index of the value before each value in its increasing subsequence
+    let mut previous: Vec<Option<usize>> = vec![None; values.len()];
+    for (i, value) in values.iter().enumerate() {
+        let len = tails.partition_point(|tail| values[*tail] < *value);
+        previous[i] = len.checked_sub(1).map(|prev_len| tails[prev_len]);
+        if len == tails.len() {
+            tails.push(i);
+        } else {
+            tails[len] = i;
+        }
+    }
+    let mut subsequence = HashSet::with_capacity(tails.len());
+    let mut current = tails.last().copied();
+    while let Some(i) = current {
+        subsequence.insert(values[i]);
+        current = previous[i];
+    }
+    subsequence
+}
@@ -16,6 +16,20 @@ where
 
     /// Return the callbacks present on this node
     fn get_callbacks(&self) -> Vec<&Listener<MSG>>;
+
+    /// remove the event listeners of this node and its descendants, ie: to reuse the view
+    /// built for the client in server side rendering or to cache it.
+    /// The attributes which only have event listeners are removed,
+    /// so the resulting node renders the same html.
+    /// # Examples
+    /// ```rust
+    /// use sauron::{*, html::{*, attributes::*, events::*}};
+    ///
+    /// let view: Node<i32> = button([class("inc"), on_click(|_| 1)], [text("+")]);
+    /// let stripped: Node<()> = view.strip_event_handlers();
+    /// assert_eq!(stripped.render_to_string(), r#"<button class="inc">+</button>"#);
+    /// ```
+    fn strip_event_handlers(self) -> Node<()>;
 }
 
 /// Add mapping function for Element
@@ -72,6 +86,25 @@ where
         }
     }
 
+    fn strip_event_handlers(self) -> Node<()> {
+        match self {
+            Node::Element(element) => Node::Element(strip_element_event_handlers(element)),
+            Node::Leaf(leaf) => Node::Leaf(leaf),
+            Node::Fragment(nodes) => Node::Fragment(
+                nodes
+                    .into_iter()
+                    .map(|node| node.strip_event_handlers())
+                    .collect(),
+            ),
+            Node::NodeList(node_list) => Node::NodeList(
+                node_list
+                    .into_iter()
+                    .map(|node| node.strip_event_handlers())
+                    .collect(),
+            ),
+        }
+    }
+
     fn get_callbacks(&self) -> Vec<&Listener<MSG>> {
         if let Some(attributes) = self.attributes() {
             let callbacks = attributes
@@ -85,6 +118,44 @@ where
     }
 }
 
+/// remove the event listeners of this element and its descendants
+fn strip_element_event_handlers<MSG: 'static>(element: Element<MSG>) -> Element<()> {
+    Element {
+        namespace: element.namespace,
+        tag: element.tag,
+        attrs: element
+            .attrs
+            .into_iter()
+            .filter_map(|attr| {
+                let value: Vec<AttributeValue<()>> = attr
+                    .value
+                    .into_iter()
+                    .filter_map(|v| match v {
+                        AttributeValue::EventListener(_) => None,
+                        // the other values have no msg, so mapping them never calls this
+                        v => Some(v.map_msg(|_: MSG| ())),
+                    })
+                    .collect();
+                if value.is_empty() {
+                    None
+                } else {
+                    Some(Attribute {
+                        name: attr.name,
+                        value,
+                        namespace: attr.namespace,
+                    })
+                }
+            })
+            .collect(),
+        children: element
+            .children
+            .into_iter()
+            .map(|child| child.strip_event_handlers())
+            .collect(),
+        self_closing: element.self_closing,
+    }
+}
+
 impl<MSG> ElementMapMsg<MSG> for Element<MSG>
 where
     MSG: 'static,
@@ -156,6 +227,8 @@ where
             AttributeValue::Simple(this) => AttributeValue::Simple(this),
             AttributeValue::Style(this) => AttributeValue::Style(this),
             AttributeValue::EventListener(this) => AttributeValue::EventListener(this.map_msg(cb)),
+            #[cfg(feature = "with-dom")]
+            AttributeValue::Property(this) => AttributeValue::Property(this),
             AttributeValue::Empty => AttributeValue::Empty,
         }
     }
@@ -1,4 +1,5 @@
-use crate::vdom::Node;
+use crate::html::attributes::{Special, Value};
+use crate::vdom::{sanitize, Node, SanitizePolicy};
 
 /// additional traits for mt_dom::Node
 pub trait NodeTrait {
@@ -13,6 +14,45 @@ pub trait NodeTrait {
 
     /// returns the html text content of this node
     fn as_safe_html(&self) -> Option<&str>;
+
+    /// find the first node in this tree, including this node, which has a matching `key`.
+    /// Note: this traverses the virtual dom and not the actual DOM
+    fn find_by_key(&self, key: impl Into<Value>) -> Option<&Self>;
+
+    /// find all the element nodes in this tree, including this node, with a matching tag.
+    /// Note: this traverses the virtual dom and not the actual DOM
+    fn find_all_by_tag(&self, tag: &str) -> Vec<&Self>;
+
+    /// the number of nodes in this tree, including this node.
+    /// Each element, leaf, fragment and node list is counted once, the same way the nodes are
+    /// indexed in the diff, so the last node index in this tree is `count_nodes() - 1`.
+    ///
+    /// The default is for a node without children.
+    fn count_nodes(&self) -> usize {
+        1
+    }
+
+    /// the number of levels of this tree, a single node is 1 level deep.
+    ///
+    /// The default is for a node without children.
+    fn depth(&self) -> usize {
+        1
+    }
+
+    /// a copy of this node tree without the elements and attributes which are disallowed by
+    /// the `policy`, ie: to safely embed a user provided html parsed into a node.
+    /// # Examples
+    /// ```rust
+    /// use sauron::{*, html::{*, attributes::*}, vdom::SanitizePolicy};
+    ///
+    /// let user_html: Node<()> = div(
+    ///     [attr("onclick", "steal()")],
+    ///     [script([], [text("steal()")]), a([href("javascript:steal()")], [text("link")])],
+    /// );
+    /// let safe = user_html.sanitize(&SanitizePolicy::default());
+    /// assert_eq!(safe.render_to_string(), "<div><a>link</a></div>");
+    /// ```
+    fn sanitize(&self, policy: &SanitizePolicy) -> Self;
 }
 
 impl<MSG> NodeTrait for Node<MSG> {
@@ -45,4 +85,66 @@ impl<MSG> NodeTrait for Node<MSG> {
             _ => None,
         }
     }
+
+    fn find_by_key(&self, key: impl Into<Value>) -> Option<&Self> {
+        let key = key.into();
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            if node.get_key() == Some(&key) {
+                return Some(node);
+            }
+            // push in reverse so the children are visited in document order
+            stack.extend(child_nodes(node).iter().rev());
+        }
+        None
+    }
+
+    fn find_all_by_tag(&self, tag: &str) -> Vec<&Self> {
+        let mut found = vec![];
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            if let Self::Element(element) = node {
+                if *element.tag() == tag {
+                    found.push(node);
+                }
+            }
+            stack.extend(child_nodes(node).iter().rev());
+        }
+        found
+    }
+
+    fn count_nodes(&self) -> usize {
+        let mut count = 0;
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            count += 1;
+            stack.extend(child_nodes(node));
+        }
+        count
+    }
+
+    fn depth(&self) -> usize {
+        // an explicit stack, so it doesn't overflow on very deep trees
+        let mut max_depth = 0;
+        let mut stack = vec![(self, 1)];
+        while let Some((node, level)) = stack.pop() {
+            max_depth = max_depth.max(level);
+            stack.extend(child_nodes(node).iter().map(|child| (child, level + 1)));
+        }
+        max_depth
+    }
+
+    fn sanitize(&self, policy: &SanitizePolicy) -> Self {
+        sanitize::sanitize(self, policy)
+    }
+}
+
+/// the direct child nodes of this node, fragment and node list contents are treated as children
+pub(crate) fn child_nodes<MSG>(node: &Node<MSG>) -> &[Node<MSG>] {
+    match node {
+        Node::Element(element) => element.children(),
+        Node::Fragment(nodes) => nodes,
+        Node::NodeList(nodes) => nodes,
+        Node::Leaf(_) => &[],
+    }
 }
@@ -0,0 +1,41 @@
+//! merging of the adjacent text nodes in the vdom
+use crate::vdom::{Leaf, Node};
+
+/// merge the adjacent text siblings of each element into a single text node.
+///
+/// Adjacent text nodes are created as separate text nodes in the DOM, which increases the
+/// number of nodes to be diffed and patched in the subsequent updates.
+/// Since this is applied to the vdom, the paths of the patches still matches the DOM.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::*};
+///
+/// let view: Node<()> = div([], [text("Hello "), text("world"), br([], [])]);
+/// let expected: Node<()> = div([], [text("Hello world"), br([], [])]);
+/// assert_eq!(vdom::normalize_text(view), expected);
+/// ```
+pub fn normalize_text<MSG>(node: Node<MSG>) -> Node<MSG> {
+    match node {
+        Node::Element(mut element) => {
+            element.children = merge_text_siblings(element.children);
+            Node::Element(element)
+        }
+        Node::Fragment(nodes) => Node::Fragment(merge_text_siblings(nodes)),
+        Node::NodeList(nodes) => Node::NodeList(merge_text_siblings(nodes)),
+        Node::Leaf(_) => node,
+    }
+}
+
+fn merge_text_siblings<MSG>(nodes: Vec<Node<MSG>>) -> Vec<Node<MSG>> {
+    let mut merged: Vec<Node<MSG>> = Vec::with_capacity(nodes.len());
+    for node in nodes.into_iter().map(normalize_text) {
+        if let Node::Leaf(Leaf::Text(txt)) = &node {
+            if let Some(Node::Leaf(Leaf::Text(prev))) = merged.last_mut() {
+                prev.to_mut().push_str(txt);
+                continue;
+            }
+        }
+        merged.push(node);
+    }
+    merged
+}
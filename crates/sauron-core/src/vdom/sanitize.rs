@@ -0,0 +1,235 @@
+//! remove the elements and attributes which can run scripts from a node tree,
+//! ie: before embedding a user provided html, see [`NodeTrait::sanitize`]
+//!
+//! [`NodeTrait::sanitize`]: crate::vdom::NodeTrait::sanitize
+use crate::html::attributes::AttributeValue;
+use crate::vdom::{Attribute, Element, Leaf, Node};
+
+/// the elements which can run scripts, load external content or change the page,
+/// these are removed along with their children by default.
+/// The svg animations can set any attribute, ie: the `href` of a link to a `javascript:` url
+const DISALLOWED_TAGS: &[&str] = &[
+    "script",
+    "style",
+    "iframe",
+    "frame",
+    "frameset",
+    "object",
+    "embed",
+    "applet",
+    "base",
+    "link",
+    "meta",
+    "noscript",
+    "animate",
+    "animateMotion",
+    "animateTransform",
+    "set",
+];
+
+/// the attributes which holds an url, which may use the `javascript:` scheme
+const URL_ATTRIBUTES: &[&str] = &[
+    "href",
+    "src",
+    "action",
+    "formaction",
+    "poster",
+    "cite",
+    "background",
+    "xlink:href",
+    "srcset",
+];
+
+/// the url schemes which are allowed by default, relative urls are always allowed
+const ALLOWED_URL_SCHEMES: &[&str] = &["http", "https", "mailto", "tel"];
+
+/// The policy of which elements and attributes are kept by [`NodeTrait::sanitize`].
+///
+/// By default the script elements, the svg animations, the `on*` attributes, the urls with
+/// schemes other than `http`, `https`, `mailto` and `tel`, and the raw html are removed.
+/// The allowlists of tags and attributes are not set by default, everything else is kept.
+/// The tags and attribute names are compared case-insensitively.
+///
+/// [`NodeTrait::sanitize`]: crate::vdom::NodeTrait::sanitize
+#[derive(Debug, Clone)]
+pub struct SanitizePolicy {
+    /// the elements which are removed along with their children
+    pub disallowed_tags: Vec<&'static str>,
+    /// when set, only the elements with these tags are kept,
+    /// the other elements are replaced by their children
+    pub allowed_tags: Option<Vec<&'static str>>,
+    /// when set, only the attributes with these names are kept
+    pub allowed_attributes: Option<Vec<&'static str>>,
+    /// the attributes which holds an url, these are removed when the url has a scheme which
+    /// is not allowed
+    pub url_attributes: Vec<&'static str>,
+    /// the schemes which are allowed in the urls
+    pub allowed_url_schemes: Vec<&'static str>,
+}
+
+impl Default for SanitizePolicy {
+    fn default() -> Self {
+        Self {
+            disallowed_tags: DISALLOWED_TAGS.to_vec(),
+            allowed_tags: None,
+            allowed_attributes: None,
+            url_attributes: URL_ATTRIBUTES.to_vec(),
+            allowed_url_schemes: ALLOWED_URL_SCHEMES.to_vec(),
+        }
+    }
+}
+
+impl SanitizePolicy {
+    /// keep only the elements with these tags, the other elements are replaced by their children.
+    /// The disallowed tags are still removed even when they are in this list.
+    pub fn allow_tags(mut self, tags: impl IntoIterator<Item = &'static str>) -> Self {
+        self.allowed_tags = Some(tags.into_iter().collect());
+        self
+    }
+
+    /// keep only the attributes with these names.
+    /// The `on*` attributes and the urls with disallowed schemes are still removed.
+    pub fn allow_attributes(mut self, names: impl IntoIterator<Item = &'static str>) -> Self {
+        self.allowed_attributes = Some(names.into_iter().collect());
+        self
+    }
+
+    /// set the schemes which are allowed in the urls
+    pub fn allow_url_schemes(mut self, schemes: impl IntoIterator<Item = &'static str>) -> Self {
+        self.allowed_url_schemes = schemes.into_iter().collect();
+        self
+    }
+
+    fn is_allowed_attribute<MSG>(&self, attr: &Attribute<MSG>) -> bool {
+        let name = *attr.name();
+        // compared as bytes, since the name may start with a multibyte character
+        let is_inline_handler = name
+            .as_bytes()
+            .get(..2)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(b"on"));
+        let is_allowed_name = match &self.allowed_attributes {
+            Some(names) => contains_ignore_case(names, name),
+            None => true,
+        };
+        let has_allowed_urls = !contains_ignore_case(&self.url_attributes, name)
+            || attr
+                .value()
+                .iter()
+                .filter_map(|v| v.get_simple().and_then(|v| v.as_str()))
+                .flat_map(|value| urls_of(name, value))
+                .all(|url| self.is_allowed_url(url));
+        !is_inline_handler
+            && is_allowed_name
+            && has_allowed_urls
+            && !attr.value().iter().any(is_script_value)
+    }
+
+    /// relative urls are allowed, absolute urls must have an allowed scheme
+    fn is_allowed_url(&self, url: &str) -> bool {
+        // browsers ignore the whitespace and control characters in the url, ie: `java\tscript:`
+        let url: String = url
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace() && !c.is_control())
+            .collect();
+        match url.find([':', '/', '?', '#']) {
+            Some(idx) if url[idx..].starts_with(':') => {
+                let scheme = &url[..idx];
+                self.allowed_url_schemes
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+            }
+            _ => true,
+        }
+    }
+}
+
+fn contains_ignore_case(names: &[&str], name: &str) -> bool {
+    names.iter().any(|n| n.eq_ignore_ascii_case(name))
+}
+
+/// the urls in the value of the attribute, the `srcset` is a list of urls each followed by
+/// an optional size, ie: `small.png 1x, large.png 2x`
+fn urls_of<'v>(name: &str, value: &'v str) -> Vec<&'v str> {
+    if name.eq_ignore_ascii_case("srcset") {
+        value
+            .split(',')
+            .filter_map(|candidate| candidate.split_whitespace().next())
+            .collect()
+    } else {
+        vec![value]
+    }
+}
+
+/// the values which sets the raw html or properties of the element, ie: `inner_html`
+fn is_script_value<MSG>(value: &AttributeValue<MSG>) -> bool {
+    match value {
+        AttributeValue::FunctionCall(_) => true,
+        #[cfg(feature = "with-dom")]
+        AttributeValue::Property(_) => true,
+        _ => false,
+    }
+}
+
+/// the sanitized copy of this node, a node list is returned when the node itself is removed
+/// or replaced by its children
+pub(crate) fn sanitize<MSG>(node: &Node<MSG>, policy: &SanitizePolicy) -> Node<MSG> {
+    let mut sanitized = vec![];
+    sanitize_into(node, policy, &mut sanitized);
+    if sanitized.len() == 1 {
+        sanitized.remove(0)
+    } else {
+        Node::NodeList(sanitized)
+    }
+}
+
+fn sanitize_into<MSG>(node: &Node<MSG>, policy: &SanitizePolicy, sanitized: &mut Vec<Node<MSG>>) {
+    match node {
+        Node::Element(element) => {
+            let tag = *element.tag();
+            if contains_ignore_case(&policy.disallowed_tags, tag) {
+                return;
+            }
+            let mut children = vec![];
+            for child in element.children() {
+                sanitize_into(child, policy, &mut children);
+            }
+            let is_allowed_tag = match &policy.allowed_tags {
+                Some(tags) => contains_ignore_case(tags, tag),
+                None => true,
+            };
+            if is_allowed_tag {
+                sanitized.push(Node::Element(Element {
+                    namespace: element.namespace,
+                    tag: element.tag,
+                    attrs: element
+                        .attrs
+                        .iter()
+                        .filter(|attr| policy.is_allowed_attribute(attr))
+                        .cloned()
+                        .collect(),
+                    children,
+                    self_closing: element.self_closing,
+                }));
+            } else {
+                sanitized.extend(children);
+            }
+        }
+        // the raw html is not parsed, so it can not be sanitized
+        Node::Leaf(Leaf::SafeHtml(_)) => (),
+        Node::Leaf(leaf) => sanitized.push(Node::Leaf(leaf.clone())),
+        Node::Fragment(nodes) => {
+            let mut children = vec![];
+            for child in nodes {
+                sanitize_into(child, policy, &mut children);
+            }
+            sanitized.push(Node::Fragment(children));
+        }
+        Node::NodeList(nodes) => {
+            let mut children = vec![];
+            for child in nodes {
+                sanitize_into(child, policy, &mut children);
+            }
+            sanitized.push(Node::NodeList(children));
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! serialize the vdom into a canonical json tree, ie: for devtools to visualize the view
+use crate::html::attributes::{AttributeValue, Value};
+use crate::vdom::{Element, Leaf, Node};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::collections::BTreeMap;
+
+/// A serializable view of the vdom node, which can be converted into json for external tooling.
+///
+/// The tree is serialized as objects with a `type`:
+///  - `element` with its `tag`, the `namespace` if there is one, `self_closing` if it is,
+///    the `attributes` and `children`
+///  - `text`, `safe_html`, `comment` and `doctype` with their `value`
+///  - `fragment` and `node_list` with their `children`
+///
+/// The attributes are keyed by their name in sorted order, each with the list of its values.
+/// Plain, style and function call values are serialized as strings, except for the booleans,
+/// and the styles are serialized in the same way they are rendered. Event listeners are closures, so they are serialized as the opaque
+/// marker `{"type": "listener"}` which only tells that a listener is attached.
+///
+/// The tree can be rebuilt from the json with [`DeserializeNode`](crate::vdom::DeserializeNode).
+/// # Examples
+/// ```rust,ignore
+/// use sauron::{*, html::{*, attributes::*, events::*}, vdom::SerializeNode};
+///
+/// let view: Node<()> = button([class("btn"), on_click(|_| ())], [text("Click")]);
+/// let json = serde_json::to_string(&SerializeNode(&view)).unwrap();
+/// ```
+pub struct SerializeNode<'a, MSG>(pub &'a Node<MSG>);
+
+struct SerializeElement<'a, MSG>(&'a Element<MSG>);
+
+struct SerializeChildren<'a, MSG>(&'a [Node<MSG>]);
+
+struct SerializeAttributeValue<'a, MSG>(&'a AttributeValue<MSG>);
+
+impl<MSG> Serialize for SerializeNode<'_, MSG> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Node::Element(element) => SerializeElement(element).serialize(serializer),
+            Node::Leaf(Leaf::Text(text)) => serialize_leaf(serializer, "text", text),
+            Node::Leaf(Leaf::SafeHtml(html)) => serialize_leaf(serializer, "safe_html", html),
+            Node::Leaf(Leaf::Comment(comment)) => serialize_leaf(serializer, "comment", comment),
+            Node::Leaf(Leaf::DocType(doctype)) => serialize_leaf(serializer, "doctype", doctype),
+            Node::Fragment(nodes) | Node::NodeList(nodes) => {
+                let node_type = if matches!(self.0, Node::Fragment(_)) {
+                    "fragment"
+                } else {
+                    "node_list"
+                };
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("type", node_type)?;
+                map.serialize_entry("children", &SerializeChildren(nodes))?;
+                map.end()
+            }
+        }
+    }
+}
+
+fn serialize_leaf<S: Serializer>(
+    serializer: S,
+    leaf_type: &str,
+    value: &str,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(2))?;
+    map.serialize_entry("type", leaf_type)?;
+    map.serialize_entry("value", value)?;
+    map.end()
+}
+
+impl<MSG> Serialize for SerializeElement<'_, MSG> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let element = self.0;
+        let mut attributes: BTreeMap<&str, Vec<SerializeAttributeValue<MSG>>> = BTreeMap::new();
+        // the empty attributes, ie: `disabled(false)`, are not rendered and are skipped as well
+        for attr in element
+            .attributes()
+            .iter()
+            .filter(|attr| !attr.name().is_empty())
+        {
+            attributes.entry(attr.name()).or_default().extend(
+                attr.value()
+                    .iter()
+                    .filter(|value| !matches!(value, AttributeValue::Empty))
+                    .map(SerializeAttributeValue),
+            );
+        }
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", "element")?;
+        map.serialize_entry("tag", element.tag())?;
+        if let Some(namespace) = element.namespace() {
+            map.serialize_entry("namespace", namespace)?;
+        }
+        if element.self_closing {
+            map.serialize_entry("self_closing", &true)?;
+        }
+        map.serialize_entry("attributes", &attributes)?;
+        map.serialize_entry("children", &SerializeChildren(element.children()))?;
+        map.end()
+    }
+}
+
+impl<MSG> Serialize for SerializeChildren<'_, MSG> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(SerializeNode))
+    }
+}
+
+impl<MSG> Serialize for SerializeAttributeValue<'_, MSG> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            AttributeValue::Simple(Value::Bool(value)) => serializer.serialize_bool(*value),
+            AttributeValue::Simple(value) | AttributeValue::FunctionCall(value) => {
+                serializer.collect_str(value)
+            }
+            AttributeValue::Style(styles) => {
+                serializer.collect_str(&styles.iter().map(|s| format!("{s};")).collect::<String>())
+            }
+            AttributeValue::EventListener(_) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("type", "listener")?;
+                map.end()
+            }
+            #[cfg(feature = "with-dom")]
+            AttributeValue::Property(_) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("type", "property")?;
+                map.end()
+            }
+            AttributeValue::Empty => serializer.serialize_unit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::html::{attributes::*, events::*, *};
+    use serde_json::json;
+
+    #[test]
+    fn serialize_small_tree() {
+        let view: Node<()> = div(
+            [class("list"), id("main")],
+            [
+                button([class("btn"), on_click(|_| ())], [text("Click")]),
+                comment("end"),
+            ],
+        );
+        let json = serde_json::to_value(SerializeNode(&view)).expect("must serialize");
+        assert_eq!(
+            json,
+            json!({
+                "type": "element",
+                "tag": "div",
+                "attributes": { "class": ["list"], "id": ["main"] },
+                "children": [
+                    {
+                        "type": "element",
+                        "tag": "button",
+                        "attributes": {
+                            "class": ["btn"],
+                            "click": [{ "type": "listener" }]
+                        },
+                        "children": [{ "type": "text", "value": "Click" }]
+                    },
+                    { "type": "comment", "value": "end" }
+                ]
+            })
+        );
+    }
+}
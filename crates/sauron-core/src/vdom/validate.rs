@@ -0,0 +1,317 @@
+//! structural validation of the vdom, to catch malformed nodes which would otherwise
+//! cause confusing errors in the browser
+use crate::html::attributes::Special;
+use crate::vdom::{Node, NodeTrait};
+use std::fmt;
+
+/// table elements where text is not allowed as a direct child
+const TABLE_ELEMENTS: [&str; 5] = ["table", "thead", "tbody", "tfoot", "tr"];
+
+/// interactive elements which needs an accessible name for assistive technologies
+const INTERACTIVE_ELEMENTS: [&str; 2] = ["button", "a"];
+
+/// attributes which gives an element an accessible name
+const NAMING_ATTRIBUTES: [&str; 3] = ["aria-label", "aria-labelledby", "title"];
+
+/// the non-abstract roles defined in WAI-ARIA 1.2
+const ARIA_ROLES: &[&str] = &[
+    "alert",
+    "alertdialog",
+    "application",
+    "article",
+    "banner",
+    "blockquote",
+    "button",
+    "caption",
+    "cell",
+    "checkbox",
+    "code",
+    "columnheader",
+    "combobox",
+    "complementary",
+    "contentinfo",
+    "definition",
+    "deletion",
+    "dialog",
+    "document",
+    "emphasis",
+    "feed",
+    "figure",
+    "form",
+    "generic",
+    "grid",
+    "gridcell",
+    "group",
+    "heading",
+    "img",
+    "insertion",
+    "link",
+    "list",
+    "listbox",
+    "listitem",
+    "log",
+    "main",
+    "marquee",
+    "math",
+    "menu",
+    "menubar",
+    "menuitem",
+    "menuitemcheckbox",
+    "menuitemradio",
+    "meter",
+    "navigation",
+    "none",
+    "note",
+    "option",
+    "paragraph",
+    "presentation",
+    "progressbar",
+    "radio",
+    "radiogroup",
+    "region",
+    "row",
+    "rowgroup",
+    "rowheader",
+    "scrollbar",
+    "search",
+    "searchbox",
+    "separator",
+    "slider",
+    "spinbutton",
+    "status",
+    "strong",
+    "switch",
+    "tab",
+    "table",
+    "tablist",
+    "tabpanel",
+    "term",
+    "textbox",
+    "time",
+    "timer",
+    "toolbar",
+    "tooltip",
+    "tree",
+    "treegrid",
+    "treeitem",
+    "subscript",
+    "superscript",
+    "mark",
+    "comment",
+    "suggestion",
+];
+
+/// A warning of a malformed node found in the vdom
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationWarning {
+    /// a void element such as `input` or `img` has children
+    VoidElementWithChildren {
+        /// the tag of the void element
+        tag: &'static str,
+    },
+    /// siblings with the same key, which can not be matched correctly in the keyed diff
+    DuplicateKey {
+        /// the tag of the parent element
+        parent: &'static str,
+        /// the duplicated key
+        key: String,
+    },
+    /// some of the sibling elements have keys while the others don't.
+    /// The unkeyed siblings are matched by their position among the unkeyed siblings,
+    /// so inserting or removing one of them can match the wrong elements and lose their DOM state
+    MixedKeyedChildren {
+        /// the tag of the parent element
+        parent: &'static str,
+        /// the number of sibling elements which has a key
+        keyed: usize,
+        /// the number of sibling elements which has no key
+        unkeyed: usize,
+    },
+    /// a non-whitespace text directly inside a table element such as `tr`,
+    /// which the browser moves outside of the table
+    TextInTable {
+        /// the tag of the table element
+        tag: &'static str,
+        /// the text content
+        text: String,
+    },
+    /// an interactive element such as `button` has no text content nor `aria-label`,
+    /// and therefore has no name which can be announced by screen readers
+    MissingAccessibleName {
+        /// the tag of the interactive element
+        tag: &'static str,
+    },
+    /// the `role` attribute is not one of the roles defined in WAI-ARIA
+    InvalidRole {
+        /// the tag of the element
+        tag: &'static str,
+        /// the invalid role value
+        role: String,
+    },
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::VoidElementWithChildren { tag } => {
+                write!(f, "`<{tag}>` is a void element and can not have children")
+            }
+            Self::DuplicateKey { parent, key } => {
+                write!(f, "duplicate key `{key}` in the children of `<{parent}>`")
+            }
+            Self::MixedKeyedChildren {
+                parent,
+                keyed,
+                unkeyed,
+            } => {
+                write!(
+                    f,
+                    "`<{parent}>` has {keyed} keyed and {unkeyed} unkeyed child elements"
+                )
+            }
+            Self::TextInTable { tag, text } => {
+                write!(f, "text `{text}` is not allowed directly inside `<{tag}>`")
+            }
+            Self::MissingAccessibleName { tag } => {
+                write!(f, "`<{tag}>` has no text content nor `aria-label`")
+            }
+            Self::InvalidRole { tag, role } => {
+                write!(f, "`{role}` is not a valid role for `<{tag}>`")
+            }
+        }
+    }
+}
+
+/// validate the structure of this node tree and return the warnings of the malformed nodes.
+///
+/// This also checks for common accessibility mistakes, such as interactive elements
+/// without an accessible name and invalid `role` values.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::*, html::attributes::*, vdom::ValidationWarning};
+///
+/// let view: Node<()> = input([r#type("text")], [text("hello")]);
+/// assert_eq!(
+///     vdom::validate(&view),
+///     vec![ValidationWarning::VoidElementWithChildren { tag: "input" }]
+/// );
+/// ```
+pub fn validate<MSG>(node: &Node<MSG>) -> Vec<ValidationWarning> {
+    let mut warnings = vec![];
+    validate_node(node, &mut warnings);
+    warnings
+}
+
+fn validate_node<MSG>(node: &Node<MSG>, warnings: &mut Vec<ValidationWarning>) {
+    match node {
+        Node::Element(element) => {
+            let tag = *element.tag();
+            let children = flatten_children(element.children());
+            if element.self_closing && !children.is_empty() {
+                warnings.push(ValidationWarning::VoidElementWithChildren { tag });
+            }
+            if TABLE_ELEMENTS.contains(&tag) {
+                for child in children.iter() {
+                    if let Some(text) = child.as_text() {
+                        if !text.trim().is_empty() {
+                            warnings.push(ValidationWarning::TextInTable {
+                                tag,
+                                text: text.to_string(),
+                            });
+                        }
+                    }
+                }
+            }
+            if INTERACTIVE_ELEMENTS.contains(&tag) && !has_accessible_name(node) {
+                warnings.push(ValidationWarning::MissingAccessibleName { tag });
+            }
+            if let Some(roles) = element.get_value("role").and_then(|v| v.as_str()) {
+                for role in roles.split_whitespace() {
+                    if !ARIA_ROLES.contains(&role) {
+                        warnings.push(ValidationWarning::InvalidRole {
+                            tag,
+                            role: role.to_string(),
+                        });
+                    }
+                }
+            }
+            let mut keys = vec![];
+            for child in children.iter() {
+                if let Some(key) = child.get_key() {
+                    if keys.contains(&key) {
+                        warnings.push(ValidationWarning::DuplicateKey {
+                            parent: tag,
+                            key: key.to_string(),
+                        });
+                    } else {
+                        keys.push(key);
+                    }
+                }
+            }
+            // text and comments can not have keys, only the elements are checked
+            let keyed = children
+                .iter()
+                .filter(|child| child.get_key().is_some())
+                .count();
+            let unkeyed = children
+                .iter()
+                .filter(|child| matches!(child, Node::Element(_)) && child.get_key().is_none())
+                .count();
+            if keyed > 0 && unkeyed > 0 {
+                warnings.push(ValidationWarning::MixedKeyedChildren {
+                    parent: tag,
+                    keyed,
+                    unkeyed,
+                });
+            }
+            for child in children {
+                validate_node(child, warnings);
+            }
+        }
+        Node::Fragment(nodes) | Node::NodeList(nodes) => {
+            for child in nodes {
+                validate_node(child, warnings);
+            }
+        }
+        Node::Leaf(_) => (),
+    }
+}
+
+/// the children of fragments and node lists are unrolled as the children of the parent element
+fn flatten_children<MSG>(children: &[Node<MSG>]) -> Vec<&Node<MSG>> {
+    let mut flatten = vec![];
+    for child in children {
+        match child {
+            Node::Fragment(nodes) | Node::NodeList(nodes) => {
+                flatten.extend(flatten_children(nodes))
+            }
+            _ => flatten.push(child),
+        }
+    }
+    flatten
+}
+
+/// an element has an accessible name when it has a naming attribute, a non-whitespace text
+/// or an image with an `alt` text in its descendants
+fn has_accessible_name<MSG>(node: &Node<MSG>) -> bool {
+    match node {
+        Node::Element(element) => {
+            let has_naming_attribute = NAMING_ATTRIBUTES.iter().any(|att_name| {
+                element
+                    .get_value(*att_name)
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|v| !v.trim().is_empty())
+            });
+            let is_image_with_alt = *element.tag() == "img"
+                && element
+                    .get_value("alt")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|v| !v.trim().is_empty());
+            has_naming_attribute
+                || is_image_with_alt
+                || element.children().iter().any(has_accessible_name)
+        }
+        Node::Fragment(nodes) | Node::NodeList(nodes) => nodes.iter().any(has_accessible_name),
+        Node::Leaf(_) => node.as_text().is_some_and(|text| !text.trim().is_empty()),
+    }
+}
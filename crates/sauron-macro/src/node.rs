@@ -96,7 +96,7 @@ fn single_node(node: Node) -> TokenStream {
                 }
                 _ => {
                     quote! {
-                        #block
+                        sauron::html::IntoNode::into_node(#block)
                     }
                 }
             },
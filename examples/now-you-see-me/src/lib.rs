@@ -1,4 +1,3 @@
-use sauron::dom::{delay, spawn_local};
 use sauron::*;
 
 #[wasm_bindgen(start)]
@@ -28,12 +27,7 @@ impl Application<Msg> for App {
                 } else {
                     document().set_title("Now, you don't!");
                 }
-                Cmd::new(|mut program| {
-                    spawn_local(async move {
-                        delay(2000).await;
-                        program.dispatch(Msg::ToggleShow);
-                    })
-                })
+                Cmd::delay(2000, Msg::ToggleShow)
             }
         }
     }
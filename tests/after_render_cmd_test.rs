@@ -0,0 +1,56 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::attributes::*, html::*, *};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const ELEM_ID: &str = "after-render-measured";
+
+struct Measure {
+    width: Option<i32>,
+}
+
+enum Msg {
+    Measured(i32),
+}
+
+impl Application<Msg> for Measure {
+    fn init(&mut self) -> Cmd<Self, Msg> {
+        Cmd::after_render(|mut program| {
+            let element: web_sys::HtmlElement = sauron_core::dom::document()
+                .get_element_by_id(ELEM_ID)
+                .expect("element must already be rendered")
+                .unchecked_into();
+            program.dispatch(Msg::Measured(element.offset_width()));
+        })
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Measured(width) => self.width = Some(width),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            [
+                id(ELEM_ID),
+                styles([("width", "120px"), ("display", "block")]),
+            ],
+            [text("measure me")],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+async fn after_render_cmd_measures_element() {
+    console_error_panic_hook::set_once();
+    let program = Program::mount_to_body(Measure { width: None });
+
+    delay(100).await;
+
+    assert_eq!(Some(120), program.app().width);
+}
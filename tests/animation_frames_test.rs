@@ -0,0 +1,20 @@
+#![deny(warnings)]
+use sauron::dom::animation_frames;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn animation_frames_yields_timestamps_until_cancelled() {
+    console_error_panic_hook::set_once();
+    let (mut frames, handle) = animation_frames();
+
+    let mut timestamps = vec![];
+    for _ in 0..3 {
+        timestamps.push(frames.next().await.expect("must have a frame"));
+    }
+    assert!(timestamps.windows(2).all(|pair| pair[0] < pair[1]));
+
+    drop(handle);
+    assert_eq!(frames.next().await, None);
+}
@@ -1,5 +1,5 @@
 #![deny(warnings)]
-use sauron::html::attributes::styles;
+use sauron::html::attributes::{styles, AsClasses};
 use sauron::*;
 
 #[test]
@@ -77,6 +77,45 @@ fn test_classes_flag() {
     assert_eq!(actual_html, expected_html);
 }
 
+#[derive(Clone, Copy)]
+enum Status {
+    Idle,
+    Loading,
+    Failed,
+}
+
+impl AsClasses for Status {
+    fn as_classes(&self) -> Vec<&'static str> {
+        match self {
+            Status::Idle => vec!["status"],
+            Status::Loading => vec!["status", "spinner"],
+            Status::Failed => vec!["status", "error"],
+        }
+    }
+}
+
+#[test]
+fn test_classes_from() {
+    let status = Status::Loading;
+    let actual: Node<&'static str> = div(vec![classes_from(status)], vec![]);
+    assert_eq!(
+        actual.render_to_string(),
+        r#"<div class="status spinner"></div>"#
+    );
+
+    let actual: Node<&'static str> = div(vec![classes_from(&Status::Failed)], vec![]);
+    assert_eq!(
+        actual.render_to_string(),
+        r#"<div class="status error"></div>"#
+    );
+
+    let actual: Node<&'static str> = div(vec![class("panel"), classes_from(Status::Idle)], vec![]);
+    assert_eq!(
+        actual.render_to_string(),
+        r#"<div class="panel status"></div>"#
+    );
+}
+
 #[test]
 fn test_styles_flag() {
     let actual: Node<&'static str> = div(
@@ -122,6 +161,39 @@ fn test_styles_and_styles_flag() {
     assert_eq!(actual_html, expected_html);
 }
 
+#[test]
+fn plain_and_typed_styles_are_merged_into_one_declaration_block() {
+    let view: Node<()> = div(
+        vec![
+            attr("style", "color:red; margin:0"),
+            styles([("color", "blue"), ("display", "flex")]),
+        ],
+        vec![],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        r#"<div style="color:blue;margin:0;display:flex;"></div>"#
+    );
+}
+
+#[test]
+fn semicolons_inside_parentheses_and_quotes_do_not_split_the_declarations() {
+    let view: Node<()> = div(
+        vec![
+            attr(
+                "style",
+                "background:url(data:image/png;base64,AAAA); content:'a;b'",
+            ),
+            styles([("color", "blue")]),
+        ],
+        vec![],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        r#"<div style="background:url(data:image/png;base64,AAAA);content:'a;b';color:blue;"></div>"#
+    );
+}
+
 #[test]
 fn classes_test() {
     let html: Node<()> = div(vec![classes(["class1", "class2"])], vec![]);
@@ -178,3 +250,26 @@ fn should_merge_classes_flag() {
         )
     );
 }
+
+#[test]
+fn classes_when_renders_only_the_present_classes() {
+    let selected: Option<usize> = Some(1);
+    let failed: Result<(), String> = Err("not found".to_string());
+    let view: Node<()> = div(
+        vec![
+            class("item"),
+            classes_when([
+                ("selected", selected),
+                ("hovered", None),
+                ("selected", Some(2)),
+            ]),
+            classes_when([("error", &failed)]),
+            classes_when([("visible", true), ("hidden", false)]),
+        ],
+        vec![],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        r#"<div class="item selected visible"></div>"#
+    );
+}
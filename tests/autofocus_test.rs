@@ -0,0 +1,35 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Form;
+
+impl Application<()> for Form {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        div(
+            [],
+            [
+                input([id("autofocus-skipped"), autofocus(false)], []),
+                input([id("autofocus-name"), autofocus(true)], []),
+                input([id("autofocus-email"), autofocus(true)], []),
+            ],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+fn first_autofocus_element_is_focused_on_mount() {
+    console_error_panic_hook::set_once();
+    let _program = Program::mount_to_body(Form);
+
+    let active = sauron::dom::document()
+        .active_element()
+        .expect("must have an active element");
+    assert_eq!(active.id(), "autofocus-name");
+}
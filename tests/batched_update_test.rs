@@ -0,0 +1,86 @@
+#![deny(warnings)]
+use sauron::dom::{delay, now, MountAction, MountTarget};
+use sauron::{html::*, *};
+use std::cell::RefCell;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const TOTAL_MSGS: usize = 100;
+const FRAME_BUDGET: f64 = 5.0;
+/// the time each msg takes to be dispatched
+const UPDATE_TIME: f64 = 1.0;
+
+#[derive(Default)]
+struct Ticker {
+    ticks: usize,
+    /// the number of ticks each time the view is rendered
+    rendered_ticks: RefCell<Vec<usize>>,
+}
+
+struct Tick;
+
+impl Application<Tick> for Ticker {
+    fn update(&mut self, _tick: Tick) -> Cmd<Self, Tick> {
+        let start = now();
+        while now() - start < UPDATE_TIME {}
+        self.ticks += 1;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Tick> {
+        self.rendered_ticks.borrow_mut().push(self.ticks);
+        div([], [text(self.ticks)])
+    }
+}
+
+#[wasm_bindgen_test]
+async fn flood_of_msgs_is_dispatched_within_the_frame_budget() {
+    console_error_panic_hook::set_once();
+    let document = sauron::dom::document();
+    let mount = document.create_element("div").expect("must create");
+    document
+        .body()
+        .expect("must have body")
+        .append_child(&mount)
+        .expect("must append");
+
+    let mut program = Program::new(
+        Ticker::default(),
+        &mount,
+        MountAction::Append,
+        MountTarget::MountNode,
+    )
+    .with_frame_budget(FRAME_BUDGET);
+    program.mount();
+
+    program.batched_update((0..TOTAL_MSGS).map(|_| Tick));
+    // nothing is dispatched until the next animation frame
+    assert_eq!(program.app().ticks, 0);
+
+    for _ in 0..100 {
+        if program.app().ticks == TOTAL_MSGS {
+            break;
+        }
+        delay(20).await;
+    }
+    assert_eq!(program.app().ticks, TOTAL_MSGS);
+    assert_eq!(mount.text_content().as_deref(), Some("100"));
+
+    let rendered_ticks = program.app().rendered_ticks.borrow().clone();
+    let batches: Vec<usize> = rendered_ticks
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .filter(|batch| *batch > 0)
+        .collect();
+    // the msgs are spread in multiple frames and the view is rendered once per frame
+    assert!(batches.len() > 1, "batches: {batches:?}");
+    assert!(batches.len() < TOTAL_MSGS, "batches: {batches:?}");
+    // each frame dispatches about as many msgs as fits in the budget,
+    // the last msg of the frame may overrun the budget
+    let max_batch = (FRAME_BUDGET / UPDATE_TIME) as usize + 1;
+    assert!(
+        batches.iter().all(|batch| *batch <= max_batch),
+        "batches: {batches:?}"
+    );
+}
@@ -0,0 +1,38 @@
+#![deny(warnings)]
+use sauron::dom::on_beforeunload;
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// dispatch a cancelable `beforeunload` event, since navigating away would end the test,
+/// returns true if the listeners asked to confirm the unload
+fn dispatch_beforeunload() -> bool {
+    let event_init = web_sys::EventInit::new();
+    event_init.set_cancelable(true);
+    let event = web_sys::Event::new_with_event_init_dict("beforeunload", &event_init).unwrap();
+    sauron_core::dom::window().dispatch_event(&event).unwrap();
+    event.default_prevented()
+}
+
+#[wasm_bindgen_test]
+fn beforeunload_prompts_only_with_unsaved_changes() {
+    console_error_panic_hook::set_once();
+    let has_unsaved = Rc::new(Cell::new(false));
+    let has_unsaved_clone = Rc::clone(&has_unsaved);
+
+    let handle = on_beforeunload(move || {
+        has_unsaved_clone
+            .get()
+            .then(|| "You have unsaved changes".to_string())
+    });
+
+    assert!(!dispatch_beforeunload());
+
+    has_unsaved.set(true);
+    assert!(dispatch_beforeunload());
+
+    // the listener is removed when the handle is dropped
+    drop(handle);
+    assert!(!dispatch_beforeunload());
+}
@@ -0,0 +1,81 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn select_dispatches_chosen_value_on_change() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let values: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(vec![]));
+    let values_clone = Rc::clone(&values);
+
+    let elem_id = "change-event-select";
+    let view: Node<()> = select(
+        vec![
+            id(elem_id),
+            on_change_value(move |v| {
+                values_clone.borrow_mut().push(v);
+            }),
+        ],
+        vec![
+            option(vec![value("red")], vec![text("Red")]),
+            option(vec![value("green")], vec![text("Green")]),
+            option(vec![value("blue")], vec![text("Blue")]),
+        ],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let select_element: web_sys::HtmlSelectElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+
+    select_element.set_value("green");
+    select_element
+        .dispatch_event(&web_sys::Event::new("change").unwrap())
+        .unwrap();
+    assert_eq!(*values.borrow(), vec!["green".to_string()]);
+}
+
+#[wasm_bindgen_test]
+fn checkbox_dispatches_checked_on_change() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let values: Rc<RefCell<Vec<bool>>> = Rc::new(RefCell::new(vec![]));
+    let values_clone = Rc::clone(&values);
+
+    let elem_id = "change-event-checkbox";
+    let view: Node<()> = input(
+        vec![
+            id(elem_id),
+            r#type("checkbox"),
+            on_change_checked(move |checked| {
+                values_clone.borrow_mut().push(checked);
+            }),
+        ],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let input_element: web_sys::HtmlInputElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+
+    input_element.click();
+    input_element.click();
+    assert_eq!(*values.borrow(), vec![true, false]);
+}
@@ -0,0 +1,37 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::*, *};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn big_list(label: &str, count: usize) -> Node<()> {
+    ul(
+        [id("chunked-patches")],
+        (0..count).map(|i| li([class(format!("item-{i}"))], [text(format!("{label} {i}"))])),
+    )
+}
+
+#[wasm_bindgen_test]
+async fn chunked_patches_result_in_the_same_dom() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(big_list("old", 1000), None)
+        .expect("must not error");
+
+    let new = big_list("new", 1500);
+    let expected = new.render_to_string();
+    let total_patches = simple_program
+        .update_dom_with_vdom_chunked(new, 100)
+        .await
+        .expect("must not error");
+    assert!(total_patches > 100);
+
+    let container = sauron_core::dom::document()
+        .get_element_by_id("chunked-patches")
+        .unwrap();
+    assert_eq!(container.outer_html(), expected);
+}
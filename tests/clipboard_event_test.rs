@@ -0,0 +1,50 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn paste_event_has_clipboard_text() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let pasted = Rc::new(RefCell::new(None));
+    let pasted_clone = Rc::clone(&pasted);
+
+    let elem_id = "paste-clipboard-text";
+    let view: Node<()> = textarea(
+        vec![
+            id(elem_id),
+            on_paste(move |event: ClipboardEvent| {
+                event.prevent_default();
+                *pasted_clone.borrow_mut() = event.text();
+            }),
+        ],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let data_transfer = web_sys::DataTransfer::new().unwrap();
+    data_transfer.set_data("text/plain", "pasted text").unwrap();
+    let mut clipboard_init = web_sys::ClipboardEventInit::new();
+    clipboard_init.clipboard_data(Some(&data_transfer));
+    clipboard_init.cancelable(true);
+    let paste_event =
+        web_sys::ClipboardEvent::new_with_event_init_dict("paste", &clipboard_init).unwrap();
+
+    let element: web_sys::EventTarget = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .into();
+    element.dispatch_event(&paste_event).unwrap();
+
+    assert_eq!(*pasted.borrow(), Some("pasted text".to_string()));
+    assert!(paste_event.default_prevented());
+}
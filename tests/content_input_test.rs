@@ -0,0 +1,64 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn content_input_reads_the_text_or_html_of_the_element() {
+    console_error_panic_hook::set_once();
+    let texts: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(vec![]));
+    let htmls: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(vec![]));
+    let texts_clone = Rc::clone(&texts);
+    let htmls_clone = Rc::clone(&htmls);
+
+    let view: Node<()> = div(
+        vec![],
+        vec![
+            div(
+                vec![
+                    id("content-input-text"),
+                    contenteditable(true),
+                    on_content_input(ContentFormat::Text, move |content| {
+                        texts_clone.borrow_mut().push(content)
+                    }),
+                ],
+                vec![],
+            ),
+            div(
+                vec![
+                    id("content-input-html"),
+                    contenteditable(true),
+                    on_content_input(ContentFormat::Html, move |content| {
+                        htmls_clone.borrow_mut().push(content)
+                    }),
+                ],
+                vec![],
+            ),
+        ],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let document = sauron::dom::document();
+    for elem_id in ["content-input-text", "content-input-html"] {
+        let element = document.get_element_by_id(elem_id).unwrap();
+        // simulate typing a bold text into the editor
+        element.set_inner_html("hello <b>world</b>");
+        let init = web_sys::InputEventInit::new();
+        init.set_input_type("insertText");
+        init.set_data(Some("d"));
+        let event = web_sys::InputEvent::new_with_event_init_dict("input", &init).unwrap();
+        element.dispatch_event(&event).unwrap();
+    }
+
+    assert_eq!(*texts.borrow(), vec!["hello world".to_string()]);
+    assert_eq!(*htmls.borrow(), vec!["hello <b>world</b>".to_string()]);
+}
@@ -0,0 +1,34 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::*, *};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn contenteditable_element_is_editable() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let elem_id = "contenteditable-editable";
+    let view: Node<()> = div(vec![id(elem_id), contenteditable(true)], vec![]);
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let element: web_sys::HtmlElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+    assert!(element.is_content_editable());
+    assert_eq!(element.content_editable(), "true");
+
+    let update: Node<()> = div(vec![id(elem_id), contenteditable(false)], vec![]);
+    simple_program
+        .update_dom_with_vdom(update, None)
+        .expect("must not error");
+    assert!(!element.is_content_editable());
+}
@@ -0,0 +1,62 @@
+#![deny(warnings)]
+use sauron::{html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// deep enough to overflow the stack of wasm when the tree is created, diffed, patched
+/// or dropped with recursion
+const DEPTH: usize = 5000;
+
+/// `DEPTH` nested divs with the text as the innermost node
+fn nested_divs(content: &str) -> Node<()> {
+    (0..DEPTH).fold(text(content), |node, _| div([], [node]))
+}
+
+struct Deep;
+
+impl Application<()> for Deep {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        nested_divs("old")
+    }
+}
+
+/// the innermost descendant of this node and its depth
+fn innermost(node: &web_sys::Node) -> (web_sys::Node, usize) {
+    let mut node = node.clone();
+    let mut depth = 0;
+    while let Some(child) = node.first_child() {
+        node = child;
+        depth += 1;
+    }
+    (node, depth)
+}
+
+#[wasm_bindgen_test]
+fn very_deep_tree_is_created_diffed_and_patched() {
+    console_error_panic_hook::set_once();
+    let mount = sauron::dom::document().create_element("div").unwrap();
+    sauron_core::dom::util::body().append_child(&mount).unwrap();
+    let mut program = Program::append_to_mount(Deep, &mount);
+
+    let root = mount.first_child().expect("must have the root node");
+    let (text_node, depth) = innermost(&mount);
+    assert_eq!(depth, DEPTH + 1);
+    assert_eq!(text_node.text_content().as_deref(), Some("old"));
+
+    let total_patches = program
+        .replace_view(nested_divs("new"))
+        .expect("must patch");
+    assert_eq!(total_patches, 1);
+
+    // the innermost text is patched in place, the elements are not created again
+    let (patched_text_node, _depth) = innermost(&mount);
+    assert_eq!(patched_text_node.text_content().as_deref(), Some("new"));
+    assert!(mount
+        .first_child()
+        .is_some_and(|node| node.is_same_node(Some(&root))));
+}
@@ -0,0 +1,67 @@
+#![deny(warnings)]
+use sauron::dom::{delay, TimeoutCallbackHandle};
+use sauron::{html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Delayed {
+    shown: bool,
+    cancellable: bool,
+    handle: Option<TimeoutCallbackHandle>,
+}
+
+enum Msg {
+    Show,
+    Cancel,
+}
+
+impl Application<Msg> for Delayed {
+    fn init(&mut self) -> Cmd<Self, Msg> {
+        if self.cancellable {
+            let (cmd, handle) = Cmd::delay_cancellable(50, Msg::Show);
+            self.handle = Some(handle);
+            Cmd::batch([cmd, Cmd::delay(10, Msg::Cancel)])
+        } else {
+            Cmd::delay(50, Msg::Show)
+        }
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Show => self.shown = true,
+            Msg::Cancel => self.handle = None,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div([], [text(self.shown)])
+    }
+}
+
+#[wasm_bindgen_test]
+async fn delayed_msg_arrives_after_the_timeout() {
+    console_error_panic_hook::set_once();
+    let program = Program::mount_to_body(Delayed::default());
+
+    delay(20).await;
+    assert!(!program.app().shown);
+
+    delay(100).await;
+    assert!(program.app().shown);
+}
+
+#[wasm_bindgen_test]
+async fn dropping_the_handle_cancels_the_delayed_msg() {
+    console_error_panic_hook::set_once();
+    let program = Program::mount_to_body(Delayed {
+        cancellable: true,
+        ..Default::default()
+    });
+
+    delay(100).await;
+    assert!(program.app().handle.is_none());
+    assert!(!program.app().shown);
+}
@@ -0,0 +1,52 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::Cell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn detached_fragment_is_inserted_later_with_working_listeners() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let clicks = Rc::new(Cell::new(0));
+    let clicks_clone = Rc::clone(&clicks);
+
+    let elem_id = "detached-fragment-button";
+    let view: Node<()> = node_list([
+        button(
+            [
+                id(elem_id),
+                on_click(move |_| clicks_clone.set(clicks_clone.get() + 1)),
+            ],
+            [text("click me")],
+        ),
+        span([], [text("offscreen")]),
+    ]);
+
+    let simple_program = simple_program();
+    let detached = simple_program.create_detached_fragment(&view);
+
+    assert_eq!(detached.fragment.child_element_count(), 2);
+    assert!(!detached.fragment.is_connected());
+    assert!(sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .is_none());
+    assert_eq!(detached.closures.len(), 1);
+    assert!(simple_program.node_closures.borrow().is_empty());
+
+    sauron_core::dom::util::body()
+        .append_child(&detached.fragment)
+        .expect("must insert the fragment");
+
+    let button: web_sys::HtmlElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .expect("must be inserted")
+        .unchecked_into();
+    button.click();
+    button.click();
+    assert_eq!(clicks.get(), 2);
+}
@@ -0,0 +1,48 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn toggle_reports_open_state() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let states: Rc<RefCell<Vec<bool>>> = Rc::new(RefCell::new(vec![]));
+    let states_clone = Rc::clone(&states);
+
+    let elem_id = "details-toggle";
+    let view: Node<()> = details(
+        vec![
+            id(elem_id),
+            on_toggle(move |is_open| states_clone.borrow_mut().push(is_open)),
+        ],
+        vec![
+            html::summary(vec![], vec![text("more")]),
+            p(vec![], vec![text("content")]),
+        ],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let details_element: web_sys::HtmlDetailsElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+
+    // the toggle event is fired asynchronously
+    details_element.set_open(true);
+    delay(50).await;
+    details_element.set_open(false);
+    delay(50).await;
+
+    assert_eq!(*states.borrow(), vec![true, false]);
+}
@@ -0,0 +1,62 @@
+#![deny(warnings)]
+use sauron::dom::{close_dialog, delay, show_modal_dialog};
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::Cell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn show_modal_and_close_dialog() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let closed = Rc::new(Cell::new(0));
+    let closed_clone = Rc::clone(&closed);
+
+    let elem_id = "dialog-modal";
+    let view: Node<()> = dialog(
+        vec![
+            id(elem_id),
+            on_close(move |_| closed_clone.set(closed_clone.get() + 1)),
+        ],
+        vec![p(vec![], vec![text("Are you sure?")])],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let dialog_element: web_sys::HtmlDialogElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+    assert!(!dialog_element.open());
+
+    show_modal_dialog(elem_id).expect("must show the dialog");
+    assert!(dialog_element.open());
+
+    close_dialog(elem_id).expect("must close the dialog");
+    assert!(!dialog_element.open());
+
+    // the close event is fired asynchronously
+    delay(50).await;
+    assert_eq!(closed.get(), 1);
+}
+
+#[wasm_bindgen_test]
+fn show_modal_on_non_dialog_is_an_error() {
+    let elem_id = "dialog-modal-not-a-dialog";
+    let view: Node<()> = div(vec![id(elem_id)], vec![]);
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    assert!(show_modal_dialog(elem_id).is_err());
+    assert!(close_dialog("dialog-modal-missing").is_err());
+}
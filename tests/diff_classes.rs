@@ -46,3 +46,20 @@ fn parent_of_matching_keyed_are_ignored() {
         "Should add the new attributes"
     );
 }
+
+#[test]
+fn srcset_changes() {
+    let old: Node<()> = img(vec![srcset(["small.png 1x", "large.png 2x"])], vec![]);
+    let new: Node<()> = img(vec![srcset(["small.png 1x", "huge.png 2x"])], vec![]);
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::add_attributes(
+            &"img",
+            TreePath::new(vec![]),
+            vec![&srcset(["small.png 1x", "huge.png 2x"])]
+        )],
+    );
+
+    let same: Node<()> = img(vec![srcset(["small.png 1x", "large.png 2x"])], vec![]);
+    assert_eq!(diff(&old, &same), vec![]);
+}
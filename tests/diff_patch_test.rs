@@ -1,5 +1,6 @@
 #![deny(warnings)]
 use sauron::html::attributes::{styles, Style};
+use sauron::vdom::PatchType;
 use sauron::*;
 
 #[test]
@@ -415,3 +416,162 @@ fn multiple_style_calls() {
         )]
     );
 }
+
+#[test]
+fn reordered_attributes_creates_no_patches() {
+    let old: Node<()> = div([id("card"), class("a"), attr("data-x", 1)], [text("hi")]);
+    let new: Node<()> = div([attr("data-x", 1), class("a"), id("card")], [text("hi")]);
+    assert_eq!(diff(&old, &new), vec![]);
+}
+
+#[test]
+fn reordered_values_of_the_same_attribute_are_patched() {
+    let old: Node<()> = div(
+        [styles([("color", "red")]), styles([("color", "blue")])],
+        [],
+    );
+    let new: Node<()> = div(
+        [styles([("color", "blue")]), styles([("color", "red")])],
+        [],
+    );
+    assert_eq!(diff(&old, &new).len(), 1);
+}
+
+#[test]
+fn reordered_keyed_text_items_are_moved() {
+    let items = |order: [i32; 4]| -> Node<()> {
+        ul(
+            vec![],
+            order.map(|i| li(vec![key(i)], vec![text(format!("item{i}"))])),
+        )
+    };
+    let old = items([1, 2, 3, 4]);
+    let new = items([4, 1, 3, 2]);
+
+    let patches = diff(&old, &new);
+    dbg!(&patches);
+    assert!(!patches.is_empty());
+    assert!(patches.iter().all(|patch| matches!(
+        patch.patch_type,
+        PatchType::MoveBeforeNode { .. } | PatchType::MoveAfterNode { .. }
+    )));
+}
+
+/// the keyed items with the text nodes in between them
+fn items_with_separators(items: [(&str, &str); 2]) -> Node<()> {
+    ul(
+        vec![],
+        items
+            .into_iter()
+            .flat_map(|(k, separator)| [li(vec![key(k)], vec![text(k)]), text(separator)])
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[test]
+fn text_nodes_reordered_with_the_keyed_items_are_moved() {
+    let old = items_with_separators([("a", "x"), ("b", "y")]);
+    let new = items_with_separators([("b", "y"), ("a", "x")]);
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::move_before_node(
+            Some(&"li"),
+            TreePath::new(vec![0]),
+            vec![TreePath::new(vec![2]), TreePath::new(vec![3])]
+        )]
+    );
+}
+
+/// `depth` nested divs with the text as the innermost node
+fn nested_divs(depth: usize, content: &str) -> Node<()> {
+    (0..depth).fold(text(content), |node, _| div(vec![], vec![node]))
+}
+
+/// take apart the tree without recursion, since dropping a deep tree is recursive too
+fn dismantle(node: Node<()>) {
+    let mut stack = vec![node];
+    while let Some(mut node) = stack.pop() {
+        if let Node::Element(element) = &mut node {
+            stack.append(&mut element.children);
+        }
+    }
+}
+
+#[test]
+fn very_deep_trees_are_diffed_without_overflowing_the_stack() {
+    let old = nested_divs(5000, "old");
+    let new = nested_divs(5000, "new");
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::replace_node(
+            None,
+            TreePath::new(vec![0; 5000]),
+            vec![&text("new")]
+        )]
+    );
+    dismantle(old);
+    dismantle(new);
+}
+
+#[test]
+fn changes_above_the_deep_subtree_are_patched() {
+    let old: Node<()> = div(vec![class("old")], vec![nested_divs(500, "same")]);
+    let new: Node<()> = div(
+        vec![class("new")],
+        vec![nested_divs(500, "same"), span(vec![], vec![])],
+    );
+    assert_eq!(
+        diff(&old, &new),
+        vec![
+            Patch::add_attributes(&"div", TreePath::new(vec![]), vec![&class("new")]),
+            Patch::append_children(
+                Some(&"div"),
+                TreePath::new(vec![]),
+                vec![&span(vec![], vec![])]
+            ),
+        ]
+    );
+}
+
+fn mixed_list(keys: &[&str], paragraphs: &[&str]) -> Node<()> {
+    let items = keys
+        .iter()
+        .map(|k| li(vec![key(*k)], vec![text(k)]))
+        .chain(paragraphs.iter().map(|t| p(vec![], vec![text(t)])));
+    ul(vec![], items.collect::<Vec<_>>())
+}
+
+#[test]
+fn mixed_keyed_children_are_matched_by_key_then_by_position() {
+    let old = mixed_list(&["a", "b"], &["note"]);
+    let new = mixed_list(&["b", "a"], &["note"]);
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::move_before_node(
+            Some(&"li"),
+            TreePath::new(vec![0]),
+            vec![TreePath::new(vec![1])]
+        )]
+    );
+}
+
+#[test]
+fn unkeyed_child_is_appended_after_the_reordered_keyed_children() {
+    let old = mixed_list(&["a", "b"], &["note"]);
+    let new = mixed_list(&["b", "a"], &["note", "extra"]);
+    assert_eq!(
+        diff(&old, &new),
+        vec![
+            Patch::move_before_node(
+                Some(&"li"),
+                TreePath::new(vec![0]),
+                vec![TreePath::new(vec![1])]
+            ),
+            Patch::append_children(
+                Some(&"ul"),
+                TreePath::new(vec![]),
+                vec![&p(vec![], vec![text("extra")])]
+            ),
+        ]
+    );
+}
@@ -0,0 +1,52 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Counter {
+    count: i32,
+}
+
+enum Msg {
+    Increment,
+}
+
+impl Application<Msg> for Counter {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Increment => self.count += 1,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div([], [text(self.count)])
+    }
+}
+
+#[wasm_bindgen_test]
+async fn delayed_msg_is_dispatched() {
+    console_error_panic_hook::set_once();
+    let program = Program::mount_to_body(Counter::default());
+
+    program.dispatch_after(20, Msg::Increment);
+    assert_eq!(program.app().count, 0);
+
+    delay(100).await;
+    assert_eq!(program.app().count, 1);
+}
+
+#[wasm_bindgen_test]
+async fn shutdown_cancels_the_delayed_msg() {
+    console_error_panic_hook::set_once();
+    let mut program = Program::mount_to_body(Counter::default());
+
+    program.dispatch_after(20, Msg::Increment);
+    program.shutdown();
+
+    delay(100).await;
+    assert_eq!(program.app().count, 0);
+}
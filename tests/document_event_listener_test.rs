@@ -0,0 +1,56 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::events::*, html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Shortcuts {
+    pressed: Vec<String>,
+}
+
+enum Msg {
+    KeyPressed(String),
+}
+
+impl Application<Msg> for Shortcuts {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::KeyPressed(key) => self.pressed.push(key),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div([], [text(self.pressed.join(","))])
+    }
+}
+
+fn press_key(key: &str) {
+    let event_init = web_sys::KeyboardEventInit::new();
+    event_init.set_key(key);
+    let event =
+        web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &event_init).unwrap();
+    sauron_core::dom::document().dispatch_event(&event).unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn document_listener_is_removed_on_shutdown() {
+    console_error_panic_hook::set_once();
+    let mut program = Program::mount_to_body(Shortcuts::default());
+    program.add_document_event_listeners(vec![on_keydown(|ke: KeyboardEvent| {
+        Msg::KeyPressed(ke.key())
+    })]);
+
+    press_key("s");
+    delay(50).await;
+    assert_eq!(program.app().pressed, vec!["s".to_string()]);
+
+    program.shutdown();
+
+    // the document listener is removed, pressing a key no longer dispatches a msg
+    press_key("q");
+    delay(50).await;
+    assert_eq!(program.app().pressed, vec!["s".to_string()]);
+}
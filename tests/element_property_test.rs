@@ -0,0 +1,59 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, *},
+    *,
+};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn todo_list(items: Option<js_sys::Array>) -> Node<()> {
+    let attrs = [id("element-property"), property_or_empty(items)];
+    html_element(None, "todo-list", attrs, [], false)
+}
+
+fn property_or_empty(items: Option<js_sys::Array>) -> Attribute<()> {
+    match items {
+        Some(items) => property("items", items),
+        None => empty_attr(),
+    }
+}
+
+fn read_items() -> JsValue {
+    let element = sauron_core::dom::document()
+        .get_element_by_id("element-property")
+        .expect("must have the element");
+    js_sys::Reflect::get(&element, &JsValue::from_str("items")).expect("must get property")
+}
+
+#[wasm_bindgen_test]
+fn object_property_is_set_on_custom_element() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let items = js_sys::Array::of2(&"milk".into(), &"eggs".into());
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(todo_list(Some(items.clone())), None)
+        .expect("must not error");
+
+    let element = sauron_core::dom::document()
+        .get_element_by_id("element-property")
+        .expect("must have the element");
+    // it is a property of the element object, not an attribute
+    assert!(!element.has_attribute("items"));
+    assert_eq!(read_items(), JsValue::from(items));
+
+    let new_items = js_sys::Array::of1(&"bread".into());
+    simple_program
+        .update_dom_with_vdom(todo_list(Some(new_items.clone())), None)
+        .expect("must not error");
+    assert_eq!(read_items(), JsValue::from(new_items));
+
+    simple_program
+        .update_dom_with_vdom(todo_list(None), None)
+        .expect("must not error");
+    assert!(read_items().is_undefined());
+}
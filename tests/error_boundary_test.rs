@@ -0,0 +1,75 @@
+#![deny(warnings)]
+//! The panics are only caught when the test is built with unwinding, ie:
+//! `RUSTFLAGS="-C panic=unwind" cargo +nightly test -Z build-std=std,panic_unwind`,
+//! the default `wasm32-unknown-unknown` target aborts on panic.
+#![cfg(panic = "unwind")]
+use sauron::dom::delay;
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Fragile {
+    broken: bool,
+    errors: Vec<String>,
+}
+
+enum Msg {
+    Break,
+    Crashed(String),
+}
+
+impl Application<Msg> for Fragile {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Break => self.broken = true,
+            Msg::Crashed(message) => self.errors.push(message),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        if self.broken {
+            panic!("the view is broken");
+        }
+        button([id("break"), on_click(|_| Msg::Break)], [text("break")])
+    }
+}
+
+#[wasm_bindgen_test]
+async fn fallback_is_rendered_when_the_view_panics() {
+    let mount = sauron::dom::document().create_element("div").unwrap();
+    sauron_core::dom::util::body().append_child(&mount).unwrap();
+
+    let mut program = Program::new(
+        Fragile::default(),
+        &mount,
+        MountAction::Append,
+        MountTarget::MountNode,
+    )
+    .with_error_boundary(
+        |message| p([id("fallback")], [text(format!("oops: {message}"))]),
+        Msg::Crashed,
+    );
+    program.mount();
+
+    let button: web_sys::HtmlElement = mount
+        .query_selector("#break")
+        .unwrap()
+        .expect("must have the button")
+        .unchecked_into();
+    button.click();
+    delay(100).await;
+
+    let fallback = mount
+        .query_selector("#fallback")
+        .unwrap()
+        .expect("the fallback must be rendered");
+    assert_eq!(
+        fallback.text_content().as_deref(),
+        Some("oops: the view is broken")
+    );
+    assert_eq!(program.app().errors, ["the view is broken"]);
+}
@@ -0,0 +1,113 @@
+#![deny(warnings)]
+use sauron::dom::{delay, MountAction, MountTarget};
+use sauron::{html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug)]
+struct FetchError(u16);
+
+#[derive(Default)]
+struct Loader {
+    loaded: Vec<String>,
+    failures: Vec<u16>,
+}
+
+enum Msg {
+    Load(Result<&'static str, u16>),
+    Fail(u16),
+    Loaded(String),
+    Failed(u16),
+}
+
+impl Application<Msg> for Loader {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Load(result) => Cmd::try_single(async move {
+                delay(10).await;
+                result
+                    .map(|content| Msg::Loaded(content.to_string()))
+                    .map_err(FetchError)
+            }),
+            // the command fails right away, without awaiting anything
+            Msg::Fail(status) => Cmd::try_single(async move { Err(FetchError(status)) }),
+            Msg::Loaded(content) => {
+                self.loaded.push(content);
+                Cmd::none()
+            }
+            Msg::Failed(status) => {
+                self.failures.push(status);
+                Cmd::none()
+            }
+        }
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            [],
+            [
+                text(self.loaded.join(",")),
+                text(format!(" failures: {}", self.failures.len())),
+            ],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+async fn failing_task_is_routed_to_the_error_handler() {
+    console_error_panic_hook::set_once();
+    let document = sauron::dom::document();
+    let mount = document.create_element("div").expect("must create");
+    document
+        .body()
+        .expect("must have body")
+        .append_child(&mount)
+        .expect("must append");
+
+    let mut program = Program::new(
+        Loader::default(),
+        &mount,
+        MountAction::Append,
+        MountTarget::MountNode,
+    )
+    .with_error_handler(|FetchError(status)| Msg::Failed(status));
+    program.mount();
+
+    program.dispatch(Msg::Load(Ok("content")));
+    program.dispatch(Msg::Load(Err(404)));
+    delay(100).await;
+
+    assert_eq!(program.app().loaded, vec!["content".to_string()]);
+    assert_eq!(program.app().failures, vec![404]);
+}
+
+#[wasm_bindgen_test]
+async fn error_of_try_single_is_mapped_into_a_msg_which_reaches_update() {
+    console_error_panic_hook::set_once();
+    let document = sauron::dom::document();
+    let mount = document.create_element("div").expect("must create");
+    document
+        .body()
+        .expect("must have body")
+        .append_child(&mount)
+        .expect("must append");
+
+    let mut program = Program::new(
+        Loader::default(),
+        &mount,
+        MountAction::Append,
+        MountTarget::MountNode,
+    )
+    .with_error_handler(|FetchError(status)| Msg::Failed(status));
+    program.mount();
+
+    program.dispatch(Msg::Fail(500));
+    program.dispatch(Msg::Fail(503));
+    delay(100).await;
+
+    assert_eq!(program.app().failures, vec![500, 503]);
+    assert!(program.app().loaded.is_empty());
+    // the mapped msgs update the view the same as any other msg
+    assert_eq!(mount.text_content().as_deref(), Some(" failures: 2"));
+}
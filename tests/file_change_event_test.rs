@@ -0,0 +1,66 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn create_file(content: &str, name: &str) -> web_sys::File {
+    let parts = js_sys::Array::of1(&content.into());
+    web_sys::File::new_with_str_sequence(&parts, name).expect("must create file")
+}
+
+#[wasm_bindgen_test]
+fn file_change_dispatches_the_selected_files() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let selected: Rc<RefCell<Vec<(String, f64)>>> = Rc::new(RefCell::new(vec![]));
+    let selected_clone = Rc::clone(&selected);
+
+    let input_id = "file-change-input";
+    let view: Node<()> = input(
+        vec![
+            id(input_id),
+            r#type("file"),
+            multiple(true),
+            on_file_change(move |files| {
+                selected_clone
+                    .borrow_mut()
+                    .extend(files.iter().map(|file| (file.name(), file.size())));
+            }),
+        ],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let data_transfer = web_sys::DataTransfer::new().expect("must create data transfer");
+    let items = data_transfer.items();
+    items
+        .add_with_file(&create_file("hello", "hello.txt"))
+        .expect("must add file");
+    items
+        .add_with_file(&create_file("hi", "hi.txt"))
+        .expect("must add file");
+
+    let input_element: web_sys::HtmlInputElement = sauron_core::dom::document()
+        .get_element_by_id(input_id)
+        .unwrap()
+        .unchecked_into();
+    input_element.set_files(data_transfer.files().as_ref());
+    let event = web_sys::Event::new("change").expect("must create event");
+    input_element
+        .dispatch_event(&event)
+        .expect("must dispatch event");
+
+    assert_eq!(
+        *selected.borrow(),
+        vec![("hello.txt".to_string(), 5.0), ("hi.txt".to_string(), 2.0)]
+    );
+}
@@ -0,0 +1,53 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::attributes::*, html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Search {
+    show_input: bool,
+}
+
+enum Msg {
+    Reveal,
+}
+
+impl Application<Msg> for Search {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Reveal => {
+                self.show_input = true;
+                Cmd::focus("#focus-cmd-input")
+            }
+        }
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            [],
+            [view_if(
+                self.show_input,
+                input([id("focus-cmd-input"), r#type("search")], []),
+            )],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+async fn revealed_input_is_focused() {
+    console_error_panic_hook::set_once();
+    let mut program = Program::mount_to_body(Search::default());
+    assert!(sauron::dom::document()
+        .get_element_by_id("focus-cmd-input")
+        .is_none());
+
+    program.dispatch(Msg::Reveal);
+    delay(200).await;
+
+    let active = sauron::dom::document()
+        .active_element()
+        .expect("must have an active element");
+    assert_eq!(active.id(), "focus-cmd-input");
+}
@@ -0,0 +1,66 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::Cell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// render a form with a text input, type into the input and then reset the form,
+/// returns the value of the input after the reset
+fn type_and_reset(form_id: &'static str, prevent_default: bool, resets: Rc<Cell<u32>>) -> String {
+    let input_id = format!("{form_id}-input");
+    let view: Node<()> = form(
+        vec![
+            id(form_id),
+            on_reset(move |event| {
+                if prevent_default {
+                    event.prevent_default();
+                }
+                resets.set(resets.get() + 1);
+            }),
+        ],
+        vec![input(vec![id(&input_id), r#type("text")], vec![])],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let document = sauron::dom::document();
+    let input_element: web_sys::HtmlInputElement = document
+        .get_element_by_id(&input_id)
+        .unwrap()
+        .unchecked_into();
+    input_element.set_value("typed");
+
+    let form_element: web_sys::HtmlFormElement = document
+        .get_element_by_id(form_id)
+        .unwrap()
+        .unchecked_into();
+    form_element.reset();
+
+    input_element.value()
+}
+
+#[wasm_bindgen_test]
+fn reset_event_is_dispatched() {
+    console_error_panic_hook::set_once();
+    let resets = Rc::new(Cell::new(0));
+    let value = type_and_reset("reset-form", false, Rc::clone(&resets));
+    assert_eq!(resets.get(), 1);
+    assert_eq!(value, "");
+}
+
+#[wasm_bindgen_test]
+fn prevented_reset_keeps_the_values() {
+    console_error_panic_hook::set_once();
+    let resets = Rc::new(Cell::new(0));
+    let value = type_and_reset("prevented-reset-form", true, Rc::clone(&resets));
+    assert_eq!(resets.get(), 1);
+    assert_eq!(value, "typed");
+}
@@ -41,3 +41,58 @@ fn test_on_client() {
 
     assert_eq!(container.outer_html(), expected);
 }
+
+fn keyed_fragments(items: &[(i32, &'static str)]) -> Node<()> {
+    html::div(
+        [html::attributes::id("keyed-fragments")],
+        items.iter().map(|(id, label)| {
+            html::fragment([
+                html::dt([key!("term-{id}")], [html::text(label)]),
+                html::dd([key!("desc-{id}")], [html::text(id)]),
+            ])
+        }),
+    )
+}
+
+#[test]
+fn keys_are_on_the_roots_of_the_fragment() {
+    let view = keyed_fragments(&[(1, "one"), (2, "two")]);
+    assert!(view.find_by_key("term-2").is_some());
+    assert!(view.find_by_key("desc-1").is_some());
+}
+
+#[wasm_bindgen_test]
+fn keyed_fragments_reconcile_when_an_item_is_removed() {
+    let mut simple_program = simple_program();
+    let old = keyed_fragments(&[(1, "one"), (2, "two"), (3, "three")]);
+    simple_program
+        .update_dom_with_vdom(old, None)
+        .expect("must update dom");
+
+    let container = sauron_core::dom::document()
+        .get_element_by_id("keyed-fragments")
+        .unwrap();
+    // the dt and dd of the items which are kept
+    let surviving: Vec<web_sys::Element> = (2..6)
+        .map(|i| container.children().item(i).expect("must have a child"))
+        .collect();
+
+    let new = keyed_fragments(&[(2, "two"), (3, "three")]);
+    let expected = new.render_to_string();
+    simple_program
+        .update_dom_with_vdom(new, None)
+        .expect("must update dom");
+
+    let container = sauron_core::dom::document()
+        .get_element_by_id("keyed-fragments")
+        .unwrap();
+    assert_eq!(container.outer_html(), expected);
+
+    // the surviving nodes are kept instead of being created again
+    let children = container.children();
+    assert_eq!(children.length(), 4);
+    for (i, node) in surviving.iter().enumerate() {
+        let child = children.item(i as u32).expect("must have a child");
+        assert!(child.is_same_node(Some(node)), "child {i} is recreated");
+    }
+}
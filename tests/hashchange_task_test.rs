@@ -0,0 +1,49 @@
+#![deny(warnings)]
+use sauron::dom::{delay, Window};
+use sauron::{html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Router {
+    hashes: Vec<String>,
+}
+
+enum Msg {
+    HashChanged(String),
+}
+
+impl Application<Msg> for Router {
+    fn init(&mut self) -> Cmd<Self, Msg> {
+        Cmd::from(Window::on_hashchange(Msg::HashChanged))
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::HashChanged(hash) => self.hashes.push(hash),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div([], [text(self.hashes.join(","))])
+    }
+}
+
+#[wasm_bindgen_test]
+async fn hashchange_task_dispatches_the_new_hash() {
+    console_error_panic_hook::set_once();
+    let program = Program::mount_to_body(Router::default());
+
+    Window::set_hash("/about");
+    delay(100).await;
+    assert_eq!(program.app().hashes, vec!["#/about".to_string()]);
+
+    Window::set_hash("/contact");
+    delay(100).await;
+    assert_eq!(
+        program.app().hashes,
+        vec!["#/about".to_string(), "#/contact".to_string()]
+    );
+}
@@ -0,0 +1,103 @@
+#![deny(warnings)]
+use sauron::dom::{delay, MountAction, MountTarget};
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Panel {
+    is_hidden: bool,
+    clicks: usize,
+}
+
+enum Msg {
+    Toggle,
+    Clicked,
+}
+
+impl Application<Msg> for Panel {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Toggle => self.is_hidden = !self.is_hidden,
+            Msg::Clicked => self.clicks += 1,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            [],
+            [
+                button(
+                    [id("toggle-panel"), on_click(|_| Msg::Toggle)],
+                    [text("toggle")],
+                ),
+                div(
+                    [id("panel"), hidden_when(self.is_hidden)],
+                    [
+                        input([id("panel-input")], []),
+                        button(
+                            [id("panel-button"), on_click(|_| Msg::Clicked)],
+                            [text("click")],
+                        ),
+                    ],
+                ),
+            ],
+        )
+    }
+}
+
+fn get_element<T: JsCast>(elem_id: &str) -> T {
+    sauron::dom::document()
+        .get_element_by_id(elem_id)
+        .expect("must exist")
+        .unchecked_into()
+}
+
+#[wasm_bindgen_test]
+async fn hidden_element_keeps_its_state_and_listeners() {
+    console_error_panic_hook::set_once();
+    let document = sauron::dom::document();
+    let mount = document.create_element("div").expect("must create");
+    document
+        .body()
+        .expect("must have body")
+        .append_child(&mount)
+        .expect("must append");
+
+    let mut program = Program::new(
+        Panel::default(),
+        &mount,
+        MountAction::Append,
+        MountTarget::MountNode,
+    );
+    program.mount();
+
+    let panel: web_sys::HtmlElement = get_element("panel");
+    let panel_input: web_sys::HtmlInputElement = get_element("panel-input");
+    let toggle: web_sys::HtmlElement = get_element("toggle-panel");
+    assert!(!panel.hidden());
+    panel_input.set_value("typed by the user");
+
+    toggle.click();
+    delay(50).await;
+    assert!(panel.hidden());
+    assert!(panel.is_connected());
+
+    toggle.click();
+    delay(50).await;
+    assert!(!panel.hidden());
+    assert!(!panel.has_attribute("hidden"));
+
+    // the same elements are shown again, along with their state and event listeners
+    let shown_panel: web_sys::HtmlElement = get_element("panel");
+    assert!(shown_panel.is_same_node(Some(&panel)));
+    assert_eq!(panel_input.value(), "typed by the user");
+
+    let panel_button: web_sys::HtmlElement = get_element("panel-button");
+    panel_button.click();
+    delay(50).await;
+    assert_eq!(program.app().clicks, 1);
+}
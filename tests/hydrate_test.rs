@@ -0,0 +1,170 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Counter {
+    count: i32,
+}
+
+enum Msg {
+    Increment,
+}
+
+impl Application<Msg> for Counter {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Increment => self.count += 1,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            [class("counter")],
+            [
+                button([on_click(|_| Msg::Increment)], [text("+")]),
+                span([], [text(self.count)]),
+            ],
+        )
+    }
+}
+
+/// the server rendered html of the counter, with the marker attributes added by the server
+const SERVER_HTML: &str = r#"
+<div class="counter" data-ssr="true" data-ssr-route="/counter" data-server="1" data-stale="1">
+  <button data-ssr-key="increment">+</button>
+  <span>0</span>
+</div>
+"#;
+
+fn create_mount() -> web_sys::Element {
+    let mount = sauron::dom::document().create_element("div").unwrap();
+    mount.set_inner_html(SERVER_HTML);
+    sauron_core::dom::util::body().append_child(&mount).unwrap();
+    mount
+}
+
+fn query(mount: &web_sys::Element, selector: &str) -> web_sys::Element {
+    mount
+        .query_selector(selector)
+        .unwrap()
+        .unwrap_or_else(|| panic!("must have {selector}"))
+}
+
+#[wasm_bindgen_test]
+async fn data_ssr_attributes_survive_the_hydration_and_the_first_update() {
+    console_error_panic_hook::set_once();
+    let mount = create_mount();
+    let server_root = query(&mount, ".counter");
+    let server_button = query(&mount, "button");
+
+    let mut program = Program::new(
+        Counter { count: 0 },
+        &mount,
+        MountAction::Hydrate,
+        MountTarget::MountNode,
+    );
+    program.mount();
+
+    // the server rendered elements are adopted instead of created again
+    assert_eq!(mount.children().length(), 1);
+    assert!(query(&mount, ".counter").is_same_node(Some(&server_root)));
+    assert!(query(&mount, "button").is_same_node(Some(&server_button)));
+
+    let button: web_sys::HtmlElement = server_button.clone().unchecked_into();
+    button.click();
+    delay(100).await;
+    assert_eq!(program.app().count, 1);
+    assert_eq!(query(&mount, "span").text_content().as_deref(), Some("1"));
+
+    assert_eq!(
+        server_root.get_attribute("data-ssr").as_deref(),
+        Some("true")
+    );
+    assert_eq!(
+        server_root.get_attribute("data-ssr-route").as_deref(),
+        Some("/counter")
+    );
+    assert_eq!(
+        server_button.get_attribute("data-ssr-key").as_deref(),
+        Some("increment")
+    );
+    // the other attributes which are not in the view are removed
+    assert_eq!(server_root.get_attribute("data-stale"), None);
+    assert_eq!(server_root.get_attribute("data-server"), None);
+    assert_eq!(
+        server_root.get_attribute("class").as_deref(),
+        Some("counter")
+    );
+}
+
+#[wasm_bindgen_test]
+async fn the_preserved_attribute_prefix_is_configurable() {
+    console_error_panic_hook::set_once();
+    let mount = create_mount();
+    let server_root = query(&mount, ".counter");
+
+    let mut program = Program::new(
+        Counter { count: 0 },
+        &mount,
+        MountAction::Hydrate,
+        MountTarget::MountNode,
+    )
+    .with_preserved_attribute_prefix("data-server");
+    program.mount();
+
+    let button: web_sys::HtmlElement = query(&mount, "button").unchecked_into();
+    button.click();
+    delay(100).await;
+    assert_eq!(program.app().count, 1);
+
+    assert_eq!(
+        server_root.get_attribute("data-server").as_deref(),
+        Some("1")
+    );
+    assert_eq!(server_root.get_attribute("data-ssr"), None);
+    assert_eq!(server_root.get_attribute("data-stale"), None);
+}
+
+#[wasm_bindgen_test]
+async fn the_server_rendered_vdom_ids_are_adopted() {
+    console_error_panic_hook::set_once();
+    let server_html = Counter { count: 0 }
+        .view()
+        .render_to_string_with_options(&RenderOptions::compressed().emit_vdom_id(true));
+    let mount = sauron::dom::document().create_element("div").unwrap();
+    mount.set_inner_html(&server_html);
+    sauron_core::dom::util::body().append_child(&mount).unwrap();
+    let server_button = query(&mount, "button");
+    assert_eq!(
+        server_button.get_attribute("data-vdom-id").as_deref(),
+        Some("1")
+    );
+
+    let mut program = Program::new(
+        Counter { count: 0 },
+        &mount,
+        MountAction::Hydrate,
+        MountTarget::MountNode,
+    );
+    program.mount();
+
+    // the closures are stored with the id which is emitted by the server
+    assert_eq!(
+        server_button.get_attribute("data-vdom-id").as_deref(),
+        Some("1")
+    );
+    assert_eq!(
+        program.node_closures.borrow().keys().collect::<Vec<_>>(),
+        [&1]
+    );
+
+    let button: web_sys::HtmlElement = server_button.unchecked_into();
+    button.click();
+    delay(100).await;
+    assert_eq!(program.app().count, 1);
+}
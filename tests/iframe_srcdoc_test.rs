@@ -0,0 +1,45 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::attributes::*, html::*, *};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn iframe_displays_the_srcdoc() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let elem_id = "iframe-srcdoc";
+    let view: Node<()> = iframe_srcdoc(
+        [id(elem_id), sandbox_allow(["allow-same-origin"])],
+        r#"<p class="greeting">Tom & "Jerry"</p>"#,
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    // the srcdoc is loaded asynchronously
+    delay(300).await;
+
+    let iframe: web_sys::HtmlIFrameElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+    assert_eq!(
+        iframe.get_attribute("sandbox").as_deref(),
+        Some("allow-same-origin")
+    );
+    let content = iframe
+        .content_document()
+        .expect("must have a content document");
+    let greeting = content
+        .query_selector(".greeting")
+        .unwrap()
+        .expect("must have the greeting");
+    assert_eq!(greeting.text_content().as_deref(), Some(r#"Tom & "Jerry""#));
+}
@@ -0,0 +1,52 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn input_is_dispatched_once_after_composition() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let values: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(vec![]));
+    let values_clone = Rc::clone(&values);
+
+    let elem_id = "input-ime-composition";
+
+    let view: Node<()> = input(vec![id(elem_id), value("日本")], vec![]).with_attributes(
+        on_input_with_composition(true, move |event: InputEvent| {
+            values_clone.borrow_mut().push(event.value());
+        }),
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let input_element: web_sys::EventTarget = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .into();
+
+    let mut composing_init = web_sys::InputEventInit::new();
+    composing_init.is_composing(true);
+    for _ in 0..3 {
+        let composing_input =
+            web_sys::InputEvent::new_with_event_init_dict("input", &composing_init).unwrap();
+        input_element.dispatch_event(&composing_input).unwrap();
+    }
+    assert!(values.borrow().is_empty());
+
+    let composition_end = web_sys::Event::new("compositionend").unwrap();
+    input_element.dispatch_event(&composition_end).unwrap();
+
+    // firefox fires a non-composing input right after the compositionend
+    let final_input = web_sys::InputEvent::new("input").unwrap();
+    input_element.dispatch_event(&final_input).unwrap();
+
+    assert_eq!(*values.borrow(), vec!["日本".to_string()]);
+}
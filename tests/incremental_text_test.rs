@@ -0,0 +1,101 @@
+#![deny(warnings)]
+use sauron::dom::{delay, MountAction, MountTarget};
+use sauron::{html::attributes::*, html::*, *};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const OLD_TEXT: &str = "the quick brown fox jumps over the lazy dog";
+const NEW_TEXT: &str = "the quick red fox jumps over the lazy dog";
+
+struct Editor {
+    content: String,
+}
+
+struct SetContent(String);
+
+impl Application<SetContent> for Editor {
+    fn update(&mut self, msg: SetContent) -> Cmd<Self, SetContent> {
+        self.content = msg.0;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<SetContent> {
+        div([class("document")], [text(&self.content)])
+    }
+}
+
+/// the mutation records and whether the text node is kept, after editing the text
+async fn edit_text(incremental_text: bool) -> (Vec<web_sys::MutationRecord>, bool) {
+    let document = sauron::dom::document();
+    let mount = document.create_element("div").expect("must create");
+    document
+        .body()
+        .expect("must have body")
+        .append_child(&mount)
+        .expect("must append");
+
+    let mut program = Program::new(
+        Editor {
+            content: OLD_TEXT.to_string(),
+        },
+        &mount,
+        MountAction::Append,
+        MountTarget::MountNode,
+    )
+    .with_incremental_text(incremental_text);
+    program.mount();
+
+    let container = mount.first_child().expect("must have the document");
+    let text_node = container.first_child().expect("must have a text node");
+
+    let callback: Closure<dyn FnMut()> = Closure::new(|| {});
+    let observer = web_sys::MutationObserver::new(callback.as_ref().unchecked_ref()).unwrap();
+    let options = web_sys::MutationObserverInit::new();
+    options.set_subtree(true);
+    options.set_child_list(true);
+    options.set_character_data(true);
+    observer
+        .observe_with_options(&container, &options)
+        .expect("must observe");
+
+    program.dispatch(SetContent(NEW_TEXT.to_string()));
+    delay(50).await;
+
+    assert_eq!(container.text_content().as_deref(), Some(NEW_TEXT));
+    let records = observer.take_records();
+    observer.disconnect();
+    let records = (0..records.length())
+        .map(|i| records.get(i).unchecked_into())
+        .collect();
+    let is_same_text_node = container
+        .first_child()
+        .map_or(false, |node| node.is_same_node(Some(&text_node)));
+    (records, is_same_text_node)
+}
+
+#[wasm_bindgen_test]
+async fn incremental_text_changes_only_the_characters() {
+    console_error_panic_hook::set_once();
+    let (records, is_same_text_node) = edit_text(true).await;
+    assert!(is_same_text_node);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].type_(), "characterData");
+    assert_eq!(records[0].removed_nodes().length(), 0);
+    assert_eq!(records[0].added_nodes().length(), 0);
+}
+
+#[wasm_bindgen_test]
+async fn text_is_replaced_by_default() {
+    console_error_panic_hook::set_once();
+    let (records, is_same_text_node) = edit_text(false).await;
+    assert!(!is_same_text_node);
+    // the whole text node is removed and a new one is added
+    let changed_nodes: u32 = records
+        .iter()
+        .map(|record| record.removed_nodes().length() + record.added_nodes().length())
+        .sum();
+    assert_eq!(changed_nodes, 2);
+}
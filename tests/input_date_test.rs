@@ -0,0 +1,147 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn set_value_and_dispatch_input(elem_id: &str, value: &str) {
+    let input: web_sys::HtmlInputElement = sauron::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+    input.set_value(value);
+    let event = web_sys::InputEvent::new("input").unwrap();
+    input.dispatch_event(&event).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn date_and_time_inputs_are_parsed() {
+    console_error_panic_hook::set_once();
+    let dates: Rc<RefCell<Vec<Result<InputDate, ParseInputDateError>>>> =
+        Rc::new(RefCell::new(vec![]));
+    let times: Rc<RefCell<Vec<Result<InputTime, ParseInputDateError>>>> =
+        Rc::new(RefCell::new(vec![]));
+    let date_times: Rc<RefCell<Vec<Result<InputDateTime, ParseInputDateError>>>> =
+        Rc::new(RefCell::new(vec![]));
+    let dates_clone = Rc::clone(&dates);
+    let times_clone = Rc::clone(&times);
+    let date_times_clone = Rc::clone(&date_times);
+
+    let view: Node<()> = div(
+        vec![],
+        vec![
+            input(
+                vec![
+                    id("input-date"),
+                    r#type("date"),
+                    on_input_date(move |date| dates_clone.borrow_mut().push(date)),
+                ],
+                vec![],
+            ),
+            input(
+                vec![
+                    id("input-time"),
+                    r#type("time"),
+                    step(1),
+                    on_input_time(move |time| times_clone.borrow_mut().push(time)),
+                ],
+                vec![],
+            ),
+            input(
+                vec![
+                    id("input-datetime"),
+                    r#type("datetime-local"),
+                    on_input_datetime(move |date_time| {
+                        date_times_clone.borrow_mut().push(date_time)
+                    }),
+                ],
+                vec![],
+            ),
+        ],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    set_value_and_dispatch_input("input-date", "2024-02-29");
+    // clearing the date passes the parse error
+    set_value_and_dispatch_input("input-date", "");
+    set_value_and_dispatch_input("input-time", "13:45:30");
+    set_value_and_dispatch_input("input-datetime", "2024-12-31T23:59");
+
+    let date = InputDate {
+        year: 2024,
+        month: 2,
+        day: 29,
+    };
+    assert_eq!(
+        *dates.borrow(),
+        vec![
+            Ok(date),
+            Err(ParseInputDateError {
+                value: String::new()
+            })
+        ]
+    );
+    assert_eq!(
+        *times.borrow(),
+        vec![Ok(InputTime {
+            hour: 13,
+            minute: 45,
+            second: 30,
+            millisecond: 0,
+        })]
+    );
+    assert_eq!(
+        *date_times.borrow(),
+        vec![Ok(InputDateTime {
+            date: InputDate {
+                year: 2024,
+                month: 12,
+                day: 31,
+            },
+            time: InputTime {
+                hour: 23,
+                minute: 59,
+                second: 0,
+                millisecond: 0,
+            },
+        })]
+    );
+    // the parsed value is formatted back into the value of the input
+    assert_eq!(date.to_string(), "2024-02-29");
+}
+
+#[wasm_bindgen_test]
+fn invalid_date_and_time_values_are_errors() {
+    for value in [
+        "",
+        "2024-02-30",
+        "2023-02-29",
+        "2024-13-01",
+        "24-01-01",
+        "2024-1-01",
+    ] {
+        assert!(value.parse::<InputDate>().is_err(), "{value:?}");
+    }
+    for value in ["", "24:00", "12:60", "12:30:60", "12:30.5", "1:30"] {
+        assert!(value.parse::<InputTime>().is_err(), "{value:?}");
+    }
+    assert_eq!(
+        "08:05:09.5".parse::<InputTime>(),
+        Ok(InputTime {
+            hour: 8,
+            minute: 5,
+            second: 9,
+            millisecond: 500,
+        })
+    );
+    assert!("2024-12-31".parse::<InputDateTime>().is_err());
+}
@@ -0,0 +1,91 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn range_input_dispatches_parsed_number() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let values: Rc<RefCell<Vec<f64>>> = Rc::new(RefCell::new(vec![]));
+    let values_clone = Rc::clone(&values);
+
+    let elem_id = "input-number-range";
+    let view: Node<()> = input(
+        vec![
+            id(elem_id),
+            r#type("range"),
+            min(0),
+            max(100),
+            on_input_number(move |v| {
+                values_clone.borrow_mut().push(v);
+            }),
+        ],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let input_element: web_sys::HtmlInputElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+
+    // simulate dragging the slider
+    for v in ["10", "42"] {
+        input_element.set_value(v);
+        input_element
+            .dispatch_event(&web_sys::InputEvent::new("input").unwrap())
+            .unwrap();
+    }
+    assert_eq!(*values.borrow(), vec![10.0, 42.0]);
+}
+
+#[wasm_bindgen_test]
+fn number_input_skips_unparsable_value() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let values: Rc<RefCell<Vec<i64>>> = Rc::new(RefCell::new(vec![]));
+    let values_clone = Rc::clone(&values);
+
+    let elem_id = "input-int-number";
+    let view: Node<()> = input(
+        vec![
+            id(elem_id),
+            r#type("number"),
+            on_input_int(move |v| {
+                values_clone.borrow_mut().push(v);
+            }),
+        ],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let input_element: web_sys::HtmlInputElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+
+    input_element.set_value("");
+    input_element
+        .dispatch_event(&web_sys::InputEvent::new("input").unwrap())
+        .unwrap();
+    assert!(values.borrow().is_empty());
+
+    input_element.set_value("7");
+    input_element
+        .dispatch_event(&web_sys::InputEvent::new("input").unwrap())
+        .unwrap();
+    assert_eq!(*values.borrow(), vec![7]);
+}
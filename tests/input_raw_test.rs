@@ -0,0 +1,72 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn input_type_is_reported_for_insertion_and_deletion() {
+    console_error_panic_hook::set_once();
+    type Reported = (Option<String>, Option<String>, String);
+    let reported: Rc<RefCell<Vec<Reported>>> = Rc::new(RefCell::new(vec![]));
+    let reported_clone = Rc::clone(&reported);
+
+    let elem_id = "input-raw-event";
+    let view: Node<()> = input(
+        vec![
+            id(elem_id),
+            value("ab"),
+            on_input_raw(move |event: InputEvent| {
+                reported_clone
+                    .borrow_mut()
+                    .push((event.input_type(), event.data(), event.value()));
+            }),
+        ],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let input_element: web_sys::EventTarget = sauron::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .into();
+
+    let insert_init = web_sys::InputEventInit::new();
+    insert_init.set_input_type("insertText");
+    insert_init.set_data(Some("c"));
+    let insert = web_sys::InputEvent::new_with_event_init_dict("input", &insert_init).unwrap();
+    input_element.dispatch_event(&insert).unwrap();
+
+    let delete_init = web_sys::InputEventInit::new();
+    delete_init.set_input_type("deleteContentBackward");
+    let delete = web_sys::InputEvent::new_with_event_init_dict("input", &delete_init).unwrap();
+    input_element.dispatch_event(&delete).unwrap();
+
+    // a plain event named `input` is not dispatched to the listener
+    let plain = web_sys::Event::new("input").unwrap();
+    input_element.dispatch_event(&plain).unwrap();
+
+    assert_eq!(
+        *reported.borrow(),
+        vec![
+            (
+                Some("insertText".to_string()),
+                Some("c".to_string()),
+                "ab".to_string()
+            ),
+            (
+                Some("deleteContentBackward".to_string()),
+                None,
+                "ab".to_string()
+            ),
+        ]
+    );
+}
@@ -0,0 +1,65 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn out_of_range_number_dispatches_invalid_on_submit() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let overflows: Rc<RefCell<Vec<bool>>> = Rc::new(RefCell::new(vec![]));
+    let overflows_clone = Rc::clone(&overflows);
+    let submitted = Rc::new(RefCell::new(false));
+    let submitted_clone = Rc::clone(&submitted);
+
+    let form_id = "invalid-event-form";
+    let input_id = "invalid-event-input";
+    let view: Node<()> = form(
+        vec![
+            id(form_id),
+            on_submit(move |event| {
+                event.prevent_default();
+                *submitted_clone.borrow_mut() = true;
+            }),
+        ],
+        vec![input(
+            vec![
+                id(input_id),
+                r#type("number"),
+                min(1),
+                max(10),
+                step(1),
+                on_invalid(move |validity| {
+                    let validity = validity.expect("must have the validity of the input");
+                    overflows_clone.borrow_mut().push(validity.range_overflow());
+                }),
+            ],
+            vec![],
+        )],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let input_element: web_sys::HtmlInputElement = sauron_core::dom::document()
+        .get_element_by_id(input_id)
+        .unwrap()
+        .unchecked_into();
+    input_element.set_value("20");
+
+    let form_element: web_sys::HtmlFormElement = sauron_core::dom::document()
+        .get_element_by_id(form_id)
+        .unwrap()
+        .unchecked_into();
+    form_element.request_submit().expect("must request submit");
+
+    assert_eq!(*overflows.borrow(), vec![true]);
+    assert!(!*submitted.borrow());
+}
@@ -0,0 +1,88 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::*, *};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn items(order: [i32; 4]) -> Node<()> {
+    ul(
+        vec![id("keyed-text-items")],
+        order.map(|i| li(vec![key(i)], vec![text(format!("item{i}"))])),
+    )
+}
+
+fn item_nodes() -> Vec<web_sys::Node> {
+    let list = sauron::dom::document()
+        .get_element_by_id("keyed-text-items")
+        .expect("must have the list");
+    let children = list.child_nodes();
+    (0..children.length())
+        .map(|i| children.item(i).expect("must have a child"))
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn reordered_keyed_text_items_are_moved_not_replaced() {
+    console_error_panic_hook::set_once();
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(items([1, 2, 3, 4]), None)
+        .expect("must not error");
+    let before = item_nodes();
+
+    simple_program
+        .update_dom_with_vdom(items([4, 1, 3, 2]), None)
+        .expect("must not error");
+    let after = item_nodes();
+
+    let texts: Vec<String> = after
+        .iter()
+        .map(|node| node.text_content().unwrap_or_default())
+        .collect();
+    assert_eq!(texts, ["item4", "item1", "item3", "item2"]);
+
+    // the same DOM nodes are reused, in their new position
+    for (new_idx, old_idx) in [(0, 3), (1, 0), (2, 2), (3, 1)] {
+        assert!(after[new_idx].is_same_node(Some(&before[old_idx])));
+    }
+}
+
+#[wasm_bindgen_test]
+fn text_nodes_reordered_with_the_keyed_items_are_moved_not_replaced() {
+    console_error_panic_hook::set_once();
+    let items = |order: [i32; 4]| -> Node<()> {
+        ul(
+            vec![id("keyed-text-items")],
+            order
+                .into_iter()
+                .flat_map(|i| [li(vec![key(i)], vec![]), text(format!("item{i}"))])
+                .collect::<Vec<_>>(),
+        )
+    };
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(items([1, 2, 3, 4]), None)
+        .expect("must not error");
+    let before = item_nodes();
+
+    simple_program
+        .update_dom_with_vdom(items([4, 1, 3, 2]), None)
+        .expect("must not error");
+    let after = item_nodes();
+
+    let texts: Vec<String> = after
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .map(|node| node.text_content().unwrap_or_default())
+        .collect();
+    assert_eq!(texts, ["item4", "item1", "item3", "item2"]);
+
+    // the text nodes are moved along with the keyed items, instead of being replaced
+    for (new_idx, old_idx) in [(0, 3), (1, 0), (2, 2), (3, 1)] {
+        assert!(after[2 * new_idx + 1].is_same_node(Some(&before[2 * old_idx + 1])));
+    }
+}
@@ -0,0 +1,81 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn passive_scroll_listener_fires() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let scrolled: Rc<RefCell<Vec<i32>>> = Rc::new(RefCell::new(vec![]));
+    let scrolled_clone = Rc::clone(&scrolled);
+
+    let elem_id = "passive-scroll";
+    let view: Node<()> = div(
+        vec![
+            id(elem_id),
+            style! {"height": px(50), "overflow": "scroll"},
+            on_scroll(move |(top, _left)| scrolled_clone.borrow_mut().push(top)).passive(),
+        ],
+        vec![div(vec![style! {"height": px(500)}], vec![])],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let element: web_sys::Element = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+    element.set_scroll_top(20);
+    element
+        .dispatch_event(&web_sys::Event::new("scroll").unwrap())
+        .unwrap();
+
+    assert_eq!(*scrolled.borrow(), vec![20]);
+}
+
+#[wasm_bindgen_test]
+fn once_listener_fires_only_once() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let clicks = Rc::new(RefCell::new(0));
+    let clicks_clone = Rc::clone(&clicks);
+
+    let elem_id = "once-click";
+    let view: Node<()> = button(
+        vec![
+            id(elem_id),
+            on_click(move |_| *clicks_clone.borrow_mut() += 1).once(),
+        ],
+        vec![text("click")],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let button: web_sys::HtmlElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+    button.click();
+    button.click();
+
+    assert_eq!(*clicks.borrow(), 1);
+}
+
+#[test]
+fn listeners_with_different_options_are_not_equal() {
+    fn scrolled(_: (i32, i32)) {}
+    let listener: Attribute<()> = on_scroll(scrolled);
+    assert_eq!(listener, on_scroll(scrolled));
+    assert_ne!(listener, on_scroll(scrolled).passive());
+}
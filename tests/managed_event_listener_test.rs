@@ -0,0 +1,40 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::*, *};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn managed_listener_is_dropped_when_the_node_is_removed() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let elem_id = "managed-listener-target";
+    let old: Node<()> = div(vec![], vec![span(vec![id(elem_id)], vec![])]);
+    let new: Node<()> = div(vec![], vec![]);
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(old, None)
+        .expect("must not error");
+    assert!(simple_program.node_closures.borrow().is_empty());
+
+    let element: web_sys::Element = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+    simple_program
+        .add_managed_event_listener(&element, "click", |_| ())
+        .expect("must add listener");
+    assert_eq!(simple_program.node_closures.borrow().len(), 1);
+
+    simple_program
+        .update_dom_with_vdom(new, None)
+        .expect("must not error");
+    assert!(simple_program.node_closures.borrow().is_empty());
+    assert!(sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .is_none());
+}
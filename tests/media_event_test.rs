@@ -0,0 +1,44 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn timeupdate_event_has_the_current_time() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let times = Rc::new(RefCell::new(vec![]));
+    let times_clone = Rc::clone(&times);
+
+    let elem_id = "timeupdate-audio";
+    let view: Node<()> = audio(
+        vec![
+            id(elem_id),
+            on_timeupdate(move |current_time: Option<f64>| {
+                times_clone.borrow_mut().push(current_time);
+            }),
+        ],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let audio_element: web_sys::HtmlMediaElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+    // without a media source, the current time is the default playback start position
+    audio_element.set_current_time(2.5);
+    let timeupdate_event = web_sys::Event::new("timeupdate").unwrap();
+    audio_element.dispatch_event(&timeupdate_event).unwrap();
+
+    assert_eq!(times.borrow().as_slice(), [Some(2.5)]);
+}
@@ -0,0 +1,72 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::*, *};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn externally_added_class_survives_the_update() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let elem_id = "merge-class-tab";
+    let old: Node<()> = div(
+        vec![id(elem_id), merge_class(true), class("tab"), class("first")],
+        vec![],
+    );
+    let new: Node<()> = div(
+        vec![
+            id(elem_id),
+            merge_class(true),
+            class("tab"),
+            class("second"),
+        ],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(old, None)
+        .expect("must not error");
+
+    let element = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap();
+    // added by an external javascript library
+    element.class_list().add_1("is-active").unwrap();
+
+    simple_program
+        .update_dom_with_vdom(new, None)
+        .expect("must not error");
+
+    let class_list = element.class_list();
+    assert!(class_list.contains("is-active"));
+    assert!(class_list.contains("tab"));
+    assert!(class_list.contains("second"));
+    assert!(!class_list.contains("first"));
+}
+
+#[wasm_bindgen_test]
+fn without_merge_class_the_class_is_overwritten() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let elem_id = "overwrite-class-tab";
+    let old: Node<()> = div(vec![id(elem_id), class("first")], vec![]);
+    let new: Node<()> = div(vec![id(elem_id), class("second")], vec![]);
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(old, None)
+        .expect("must not error");
+
+    let element = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap();
+    element.class_list().add_1("is-active").unwrap();
+
+    simple_program
+        .update_dom_with_vdom(new, None)
+        .expect("must not error");
+
+    assert_eq!(element.class_name(), "second");
+}
@@ -0,0 +1,44 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn listeners_of_the_same_event_fire_in_order() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let fired: Rc<RefCell<Vec<&'static str>>> = Rc::new(RefCell::new(vec![]));
+    let fired1 = Rc::clone(&fired);
+    let fired2 = Rc::clone(&fired);
+    let fired3 = Rc::clone(&fired);
+
+    let elem_id = "merged-click-listeners";
+    let view: Node<()> = button(
+        vec![
+            id(elem_id),
+            on_click(move |_| fired1.borrow_mut().push("first")),
+            on_input(move |_| fired3.borrow_mut().push("input")),
+        ],
+        vec![],
+    )
+    .with_attributes([on_click(move |_| fired2.borrow_mut().push("second"))]);
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let element: web_sys::HtmlElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+    element.click();
+    element.click();
+
+    assert_eq!(*fired.borrow(), vec!["first", "second", "first", "second"]);
+}
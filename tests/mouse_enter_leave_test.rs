@@ -0,0 +1,75 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn mouse_event(event_name: &str, bubbles: bool, x: i32, y: i32) -> web_sys::MouseEvent {
+    let mouse_init = web_sys::MouseEventInit::new();
+    mouse_init.set_bubbles(bubbles);
+    mouse_init.set_client_x(x);
+    mouse_init.set_client_y(y);
+    web_sys::MouseEvent::new_with_mouse_event_init_dict(event_name, &mouse_init).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn mouseenter_and_mouseleave_fire_once_per_boundary_crossing() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let crossings: Rc<RefCell<Vec<(&str, i32, i32)>>> = Rc::new(RefCell::new(vec![]));
+    let enter_crossings = Rc::clone(&crossings);
+    let leave_crossings = Rc::clone(&crossings);
+
+    let outer_id = "mouse-enter-leave-outer";
+    let inner_id = "mouse-enter-leave-inner";
+    let view: Node<()> = div(
+        vec![
+            id(outer_id),
+            on_mouseenter(move |event| {
+                enter_crossings
+                    .borrow_mut()
+                    .push(("enter", event.client_x(), event.client_y()));
+            }),
+            on_mouseleave(move |event| {
+                leave_crossings
+                    .borrow_mut()
+                    .push(("leave", event.client_x(), event.client_y()));
+            }),
+        ],
+        vec![span(vec![id(inner_id)], vec![text("tooltip target")])],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let document = sauron_core::dom::document();
+    let outer = document.get_element_by_id(outer_id).unwrap();
+    let inner = document.get_element_by_id(inner_id).unwrap();
+
+    outer
+        .dispatch_event(&mouse_event("mouseenter", false, 10, 20))
+        .unwrap();
+    // moving in between the descendants doesn't enter the outer element again
+    inner
+        .dispatch_event(&mouse_event("mouseover", true, 12, 22))
+        .unwrap();
+    inner
+        .dispatch_event(&mouse_event("mouseenter", false, 12, 22))
+        .unwrap();
+    inner
+        .dispatch_event(&mouse_event("mouseleave", false, 14, 24))
+        .unwrap();
+    outer
+        .dispatch_event(&mouse_event("mouseleave", false, 30, 40))
+        .unwrap();
+
+    assert_eq!(
+        *crossings.borrow(),
+        vec![("enter", 10, 20), ("leave", 30, 40)]
+    );
+}
@@ -0,0 +1,124 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::collections::BTreeSet;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Counter {
+    count: i32,
+}
+
+enum Msg {
+    Increment,
+}
+
+impl Application<Msg> for Counter {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Increment => self.count += 1,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            [class("counter")],
+            [
+                button([on_click(|_| Msg::Increment)], [text("+")]),
+                span([on_mouseover(|_| Msg::Increment)], [text(self.count)]),
+            ],
+        )
+    }
+}
+
+fn create_mount() -> web_sys::Element {
+    let mount = sauron::dom::document().create_element("div").unwrap();
+    sauron_core::dom::util::body().append_child(&mount).unwrap();
+    mount
+}
+
+fn click(mount: &web_sys::Element) {
+    let button: web_sys::HtmlElement = mount
+        .query_selector("button")
+        .unwrap()
+        .expect("must have a button")
+        .unchecked_into();
+    button.click();
+}
+
+fn vdom_ids(mount: &web_sys::Element) -> BTreeSet<String> {
+    let elements = mount.query_selector_all("[data-vdom-id]").unwrap();
+    (0..elements.length())
+        .filter_map(|i| elements.item(i))
+        .filter_map(|node| {
+            node.unchecked_into::<web_sys::Element>()
+                .get_attribute("data-vdom-id")
+        })
+        .collect()
+}
+
+#[wasm_bindgen_test]
+async fn two_apps_on_one_page_do_not_interfere() {
+    console_error_panic_hook::set_once();
+    let mount_a = create_mount();
+    let mount_b = create_mount();
+    let mut program_a = Program::append_to_mount(Counter { count: 0 }, &mount_a);
+    let program_b = Program::append_to_mount(Counter { count: 0 }, &mount_b);
+
+    // the vdom ids are assigned per program
+    let ids_a = vdom_ids(&mount_a);
+    let ids_b = vdom_ids(&mount_b);
+    assert_eq!(ids_a.len(), 2);
+    assert_eq!(ids_a, ids_b);
+
+    click(&mount_a);
+    click(&mount_b);
+    click(&mount_b);
+    delay(100).await;
+    assert_eq!(program_a.app().count, 1);
+    assert_eq!(program_b.app().count, 2);
+
+    // unmounting one app leaves the listeners of the other app intact
+    program_a.shutdown();
+    assert_eq!(program_a.node_closures.borrow().len(), 0);
+    assert!(!program_b.node_closures.borrow().is_empty());
+
+    click(&mount_b);
+    delay(100).await;
+    assert_eq!(program_b.app().count, 3);
+    assert_eq!(program_a.app().count, 1);
+}
+
+#[wasm_bindgen_test]
+async fn shutting_down_an_outer_app_leaves_the_nested_app_intact() {
+    console_error_panic_hook::set_once();
+    let mount_outer = create_mount();
+    let mut outer = Program::append_to_mount(Counter { count: 0 }, &mount_outer);
+
+    // the nested app is mounted inside the DOM of the outer app,
+    // and its elements have the same vdom ids as the elements of the outer app
+    let mount_inner = sauron::dom::document().create_element("div").unwrap();
+    mount_outer
+        .query_selector(".counter")
+        .unwrap()
+        .expect("must have the root of the outer app")
+        .append_child(&mount_inner)
+        .unwrap();
+    let inner = Program::append_to_mount(Counter { count: 0 }, &mount_inner);
+    assert_eq!(vdom_ids(&mount_inner), vdom_ids(&mount_outer));
+    let elements_with_ids = mount_outer.query_selector_all("[data-vdom-id]").unwrap();
+    assert_eq!(elements_with_ids.length(), 4);
+
+    outer.shutdown();
+    assert_eq!(outer.node_closures.borrow().len(), 0);
+    assert_eq!(inner.node_closures.borrow().len(), 2);
+
+    // the nested app is detached along with the outer app, but its listeners still work
+    click(&mount_inner);
+    delay(100).await;
+    assert_eq!(inner.app().count, 1);
+    assert_eq!(outer.app().count, 0);
+}
@@ -0,0 +1,48 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, *},
+    *,
+};
+
+fn list() -> Node<()> {
+    div(
+        vec![class("container")],
+        vec![
+            ul(
+                vec![],
+                vec![
+                    li(vec![key("item-1")], vec![text("one")]),
+                    li(vec![key("item-2")], vec![text("two")]),
+                ],
+            ),
+            ol(vec![], vec![li(vec![key(3)], vec![text("three")])]),
+        ],
+    )
+}
+
+#[test]
+fn find_keyed_li() {
+    let view = list();
+    let found = view.find_by_key("item-2").expect("must find the keyed li");
+    assert_eq!(*found, li(vec![key("item-2")], vec![text("two")]));
+
+    let found = view.find_by_key(3).expect("must find the keyed li");
+    assert_eq!(*found, li(vec![key(3)], vec![text("three")]));
+
+    assert!(view.find_by_key("not-there").is_none());
+}
+
+#[test]
+fn find_all_li_tags() {
+    let view = list();
+    let items = view.find_all_by_tag("li");
+    assert_eq!(items.len(), 3);
+    let texts: Vec<String> = items
+        .iter()
+        .map(|item| item.children()[0].as_text().unwrap().to_string())
+        .collect();
+    assert_eq!(texts, vec!["one", "two", "three"]);
+
+    assert_eq!(view.find_all_by_tag("div").len(), 1);
+    assert!(view.find_all_by_tag("span").is_empty());
+}
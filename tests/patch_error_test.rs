@@ -0,0 +1,73 @@
+#![deny(warnings)]
+use sauron::dom::{delay, PatchError};
+use sauron::{html::attributes::*, html::*, *};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn node_removed_outside_of_the_program_is_a_missing_node() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let elem_id = "patch-error-missing";
+    let old: Node<()> = div(vec![], vec![span(vec![id(elem_id)], vec![text("old")])]);
+    let new: Node<()> = div(vec![], vec![span(vec![id(elem_id)], vec![text("new")])]);
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(old, None)
+        .expect("must not error");
+
+    // remove the span behind the back of the program
+    sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .expect("must have the span")
+        .remove();
+
+    let result = simple_program.update_dom_with_vdom(new, None);
+    assert!(
+        matches!(result, Err(PatchError::MissingNode { ref path }) if path.path == vec![0, 0]),
+        "unexpected result: {result:?}"
+    );
+}
+
+struct Counter {
+    count: i32,
+}
+
+impl Application<()> for Counter {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        self.count += 1;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        div(
+            vec![],
+            vec![span(
+                vec![id("patch-error-counter")],
+                vec![text(self.count)],
+            )],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+async fn dispatching_on_a_drifted_dom_does_not_panic() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let mut program = Program::mount_to_body(Counter { count: 0 });
+
+    // the span is removed outside of the program, so the text patch has no target node
+    sauron_core::dom::document()
+        .get_element_by_id("patch-error-counter")
+        .expect("must have the counter")
+        .remove();
+
+    program.dispatch(());
+    delay(50).await;
+    program.dispatch(());
+    delay(50).await;
+    assert_eq!(program.app().count, 2);
+}
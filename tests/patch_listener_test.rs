@@ -0,0 +1,57 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::vdom::PatchType;
+use sauron::{html::attributes::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Counter {
+    count: i32,
+}
+
+enum Msg {
+    Increment,
+}
+
+impl Application<Msg> for Counter {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Increment => self.count += 1,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            [class(format!("count-{}", self.count))],
+            [span([], [text("counter")])],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+async fn patch_listener_receives_the_patches_of_each_update() {
+    console_error_panic_hook::set_once();
+    let received: Rc<RefCell<Vec<(Vec<usize>, bool)>>> = Rc::new(RefCell::new(vec![]));
+    let received_clone = Rc::clone(&received);
+
+    let mut program = Program::mount_to_body(Counter::default());
+    program.on_patch(move |patches| {
+        received_clone
+            .borrow_mut()
+            .extend(patches.iter().map(|patch| {
+                (
+                    patch.patch_path.path.clone(),
+                    matches!(patch.patch_type, PatchType::AddAttributes { .. }),
+                )
+            }));
+    });
+
+    program.dispatch(Msg::Increment);
+    delay(100).await;
+
+    assert_eq!(*received.borrow(), vec![(vec![], true)]);
+}
@@ -0,0 +1,81 @@
+#![deny(warnings)]
+use sauron::dom::PatchError;
+use sauron::{html::attributes::*, html::*, *};
+use test_fixtures::simple_program;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn observe(element_id: &str, callback: &Closure<dyn FnMut()>) -> web_sys::MutationObserver {
+    let observer = web_sys::MutationObserver::new(callback.as_ref().unchecked_ref()).unwrap();
+    let options = web_sys::MutationObserverInit::new();
+    options.set_subtree(true);
+    options.set_child_list(true);
+    options.set_character_data(true);
+    options.set_attributes(true);
+    let target = sauron_core::dom::document()
+        .get_element_by_id(element_id)
+        .unwrap();
+    observer
+        .observe_with_options(&target, &options)
+        .expect("must observe");
+    observer
+}
+
+fn counter_panel(count: i32) -> Node<()> {
+    div(
+        vec![id("subtree-counter")],
+        vec![span(vec![class("count")], vec![text(count)])],
+    )
+}
+
+#[wasm_bindgen_test]
+fn patch_subtree_leaves_the_siblings_untouched() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let view: Node<()> = div(
+        vec![],
+        vec![
+            div(vec![id("subtree-sidebar")], vec![text("menu")]),
+            counter_panel(0),
+        ],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let callback: Closure<dyn FnMut()> = Closure::new(|| {});
+    let sidebar_observer = observe("subtree-sidebar", &callback);
+    let counter_observer = observe("subtree-counter", &callback);
+
+    let total_patches = simple_program
+        .patch_subtree("subtree-counter", counter_panel(1))
+        .expect("must patch the subtree");
+    assert_eq!(total_patches, 1);
+
+    assert_eq!(sidebar_observer.take_records().length(), 0);
+    assert!(counter_observer.take_records().length() > 0);
+
+    let counter = sauron_core::dom::document()
+        .get_element_by_id("subtree-counter")
+        .unwrap();
+    assert_eq!(counter.text_content().as_deref(), Some("1"));
+
+    // the current vdom is updated, so the subsequent patch is relative to the new subtree
+    let total_patches = simple_program
+        .patch_subtree("subtree-counter", counter_panel(1))
+        .expect("must patch the subtree");
+    assert_eq!(total_patches, 0);
+
+    assert_eq!(
+        simple_program.patch_subtree("no-such-subtree", counter_panel(2)),
+        Err(PatchError::MissingSubtree {
+            id: "no-such-subtree".to_string()
+        })
+    );
+}
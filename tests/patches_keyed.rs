@@ -358,3 +358,52 @@ fn mixed_keyed_and_non_keyed_elements() {
 
     assert_eq!(expected1, container.outer_html());
 }
+
+#[wasm_bindgen_test]
+fn keyed_media_element_is_preserved_among_changing_siblings() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    console_error_panic_hook::set_once();
+
+    let document = web_sys::window().unwrap().document().unwrap();
+
+    let view = |step: usize| -> Node<()> {
+        // the siblings before the video changes in number and in type
+        let banner: Vec<Node<()>> = match step {
+            0 => vec![div(vec![], vec![text("loading")])],
+            1 => vec![span(vec![], vec![text("ready")]), hr(vec![], vec![])],
+            _ => vec![],
+        };
+        main(
+            vec![class("test-keyed-media")],
+            vec![
+                node_list(banner),
+                video(vec![key("player"), controls(true)], vec![]),
+                p(vec![], vec![text(format!("step {step}"))]),
+            ],
+        )
+    };
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view(0), None)
+        .expect("must update dom");
+
+    let find_video = || {
+        document
+            .query_selector(".test-keyed-media video")
+            .expect("must not error")
+            .expect("must have the video")
+    };
+    let video_element = find_video();
+
+    for step in 1..=3 {
+        simple_program
+            .update_dom_with_vdom(view(step), None)
+            .expect("must update dom");
+
+        assert!(
+            video_element.is_same_node(Some(&find_video())),
+            "the keyed video must not be recreated in step {step}"
+        );
+    }
+}
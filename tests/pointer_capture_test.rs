@@ -0,0 +1,63 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn dispatch_pointer_event(target: &web_sys::EventTarget, event_type: &str, pointer_id: i32) {
+    let init = web_sys::PointerEventInit::new();
+    init.set_pointer_id(pointer_id);
+    let event = web_sys::PointerEvent::new_with_event_init_dict(event_type, &init).unwrap();
+    target.dispatch_event(&event).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn got_and_lost_pointer_capture_are_dispatched() {
+    console_error_panic_hook::set_once();
+    let captures: Rc<RefCell<Vec<(&'static str, i32)>>> = Rc::new(RefCell::new(vec![]));
+    let got = Rc::clone(&captures);
+    let lost = Rc::clone(&captures);
+
+    let elem_id = "pointer-capture";
+    let view: Node<()> = div(
+        vec![
+            id(elem_id),
+            on_gotpointercapture(move |event: PointerEvent| {
+                got.borrow_mut().push(("got", event.pointer_id()));
+            }),
+            on_lostpointercapture(move |event: PointerEvent| {
+                lost.borrow_mut().push(("lost", event.pointer_id()));
+            }),
+        ],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let element: web_sys::EventTarget = sauron::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .into();
+
+    dispatch_pointer_event(&element, "gotpointercapture", 7);
+    dispatch_pointer_event(&element, "lostpointercapture", 7);
+
+    assert_eq!(*captures.borrow(), vec![("got", 7), ("lost", 7)]);
+
+    // the listeners are removed when the element no longer has the handlers
+    simple_program
+        .update_dom_with_vdom(div(vec![id(elem_id)], vec![]), None)
+        .expect("must not error");
+
+    dispatch_pointer_event(&element, "gotpointercapture", 8);
+    dispatch_pointer_event(&element, "lostpointercapture", 8);
+
+    assert_eq!(*captures.borrow(), vec![("got", 7), ("lost", 7)]);
+}
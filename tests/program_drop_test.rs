@@ -0,0 +1,57 @@
+#![deny(warnings)]
+use sauron::dom::{delay, MountAction, MountTarget, Window};
+use sauron::{html::*, *};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Router {
+    hash_changes: Rc<Cell<usize>>,
+}
+
+enum Msg {
+    HashChanged,
+}
+
+impl Application<Msg> for Router {
+    fn init(&mut self) -> Cmd<Self, Msg> {
+        Cmd::from(Window::on_hashchange(|_| Msg::HashChanged))
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::HashChanged => self.hash_changes.set(self.hash_changes.get() + 1),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div([], [text(self.hash_changes.get())])
+    }
+}
+
+#[wasm_bindgen_test]
+async fn dropped_program_stops_the_recurring_tasks() {
+    console_error_panic_hook::set_once();
+    let hash_changes = Rc::new(Cell::new(0));
+    let mut program = Program::new(
+        Router {
+            hash_changes: Rc::clone(&hash_changes),
+        },
+        &sauron::dom::util::body(),
+        MountAction::Append,
+        MountTarget::MountNode,
+    );
+    program.mount();
+
+    Window::set_hash("/before-drop");
+    delay(100).await;
+    assert_eq!(hash_changes.get(), 1);
+
+    drop(program);
+
+    Window::set_hash("/after-drop");
+    delay(100).await;
+    assert_eq!(hash_changes.get(), 1);
+}
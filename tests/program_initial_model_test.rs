@@ -0,0 +1,34 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Counter {
+    count: i32,
+}
+
+impl Application<()> for Counter {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        self.count += 1;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        div([class("counter")], [text(self.count)])
+    }
+}
+
+#[wasm_bindgen_test]
+fn view_of_initial_model() {
+    console_error_panic_hook::set_once();
+    let program = Program::with_initial_model(Counter { count: 42 });
+
+    assert_eq!(
+        r#"<div class="counter">42</div>"#,
+        program.view().render_to_string()
+    );
+    assert_eq!(42, program.app().count);
+    // the program is not mounted
+    assert!(program.mount_node().parent_node().is_none());
+}
@@ -0,0 +1,71 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::*, *};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn progress_and_meter_display_their_value() {
+    console_error_panic_hook::set_once();
+    let view: Node<()> = div(
+        vec![],
+        vec![
+            progress(vec![id("progress-fraction"), value(0.5)], vec![]),
+            progress(vec![id("progress-max"), value(5), max(10)], vec![]),
+            progress(vec![id("progress-str"), value("0.25")], vec![]),
+            meter(
+                vec![id("meter-value"), min(0), max(100), value("75")],
+                vec![],
+            ),
+        ],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let document = sauron::dom::document();
+    let progress_element = |elem_id: &str| -> web_sys::HtmlProgressElement {
+        document
+            .get_element_by_id(elem_id)
+            .unwrap()
+            .unchecked_into()
+    };
+
+    assert_eq!(progress_element("progress-fraction").value(), 0.5);
+    assert_eq!(progress_element("progress-fraction").position(), 0.5);
+
+    assert_eq!(progress_element("progress-max").value(), 5.0);
+    assert_eq!(progress_element("progress-max").position(), 0.5);
+
+    assert_eq!(progress_element("progress-str").value(), 0.25);
+
+    let meter: web_sys::HtmlMeterElement = document
+        .get_element_by_id("meter-value")
+        .unwrap()
+        .unchecked_into();
+    assert_eq!(meter.value(), 75.0);
+
+    // updating the value is reflected in the displayed value
+    let update: Node<()> = div(
+        vec![],
+        vec![
+            progress(vec![id("progress-fraction"), value(0.75)], vec![]),
+            progress(vec![id("progress-max"), value(5), max(10)], vec![]),
+            progress(vec![id("progress-str"), value("0.25")], vec![]),
+            meter(
+                vec![id("meter-value"), min(0), max(100), value("75")],
+                vec![],
+            ),
+        ],
+    );
+    simple_program
+        .update_dom_with_vdom(update, None)
+        .expect("must not error");
+    assert_eq!(progress_element("progress-fraction").value(), 0.75);
+}
@@ -0,0 +1,73 @@
+#![deny(warnings)]
+use sauron::dom::{delay, request_animation_frame, MountAction, MountTarget};
+use sauron::{html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Counter {
+    count: usize,
+}
+
+struct Increment;
+
+impl Application<Increment> for Counter {
+    fn update(&mut self, _msg: Increment) -> Cmd<Self, Increment> {
+        self.count += 1;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Increment> {
+        div([], [text(self.count)])
+    }
+}
+
+#[wasm_bindgen_test]
+async fn microtask_runs_after_the_update_and_before_the_animation_frame() {
+    console_error_panic_hook::set_once();
+    let document = sauron::dom::document();
+    let mount = document.create_element("div").expect("must create");
+    document
+        .body()
+        .expect("must have body")
+        .append_child(&mount)
+        .expect("must append");
+
+    let mut program = Program::new(
+        Counter::default(),
+        &mount,
+        MountAction::Append,
+        MountTarget::MountNode,
+    );
+    program.mount();
+
+    let log: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(vec![]));
+
+    let log_clone = Rc::clone(&log);
+    let _frame = request_animation_frame(move || {
+        log_clone.borrow_mut().push("animation frame".to_string());
+    })
+    .expect("must request");
+
+    program.dispatch(Increment);
+    let log_clone = Rc::clone(&log);
+    program.queue_microtask(move |program| {
+        // the patches of the update are applied before the microtask is executed
+        let content = program.mount_node().text_content().unwrap_or_default();
+        log_clone.borrow_mut().push(format!("microtask: {content}"));
+    });
+    log.borrow_mut().push("sync".to_string());
+
+    delay(100).await;
+
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            "sync".to_string(),
+            "microtask: 1".to_string(),
+            "animation frame".to_string()
+        ]
+    );
+}
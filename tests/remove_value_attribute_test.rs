@@ -0,0 +1,65 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::*, *};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn removing_value_attribute_clears_the_input() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let elem_id = "input-remove-value";
+
+    let old: Node<()> = input(vec![id(elem_id), value("hello")], vec![]);
+    let new: Node<()> = input(vec![id(elem_id)], vec![]);
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(old, None)
+        .expect("must not error");
+
+    let input_element: web_sys::HtmlInputElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+    assert_eq!(input_element.value(), "hello");
+
+    simple_program
+        .update_dom_with_vdom(new, None)
+        .expect("must not error");
+
+    assert_eq!(input_element.value(), "");
+    assert!(!input_element.has_attribute("value"));
+}
+
+#[wasm_bindgen_test]
+fn removing_checked_attribute_unchecks_the_input() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let elem_id = "input-remove-checked";
+
+    let old: Node<()> = input(vec![id(elem_id), r#type("checkbox"), checked(true)], vec![]);
+    let new: Node<()> = input(
+        vec![id(elem_id), r#type("checkbox"), checked(false)],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(old, None)
+        .expect("must not error");
+
+    let input_element: web_sys::HtmlInputElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+    assert!(input_element.checked());
+
+    simple_program
+        .update_dom_with_vdom(new, None)
+        .expect("must not error");
+
+    assert!(!input_element.checked());
+}
@@ -0,0 +1,62 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const ELEM_ID: &str = "replace-app-button";
+
+struct Labeled {
+    label: &'static str,
+    clicked: Vec<&'static str>,
+}
+
+enum Msg {
+    Clicked(&'static str),
+}
+
+impl Application<Msg> for Labeled {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Clicked(label) => self.clicked.push(label),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        let label = self.label;
+        button(
+            [id(ELEM_ID), on_click(move |_| Msg::Clicked(label))],
+            [text(label)],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+async fn replaced_app_is_rendered_and_receives_events() {
+    console_error_panic_hook::set_once();
+    let mut program = Program::mount_to_body(Labeled {
+        label: "old",
+        clicked: vec![],
+    });
+
+    program
+        .replace_app(Labeled {
+            label: "new",
+            clicked: vec![],
+        })
+        .expect("must replace the app");
+
+    let button: web_sys::HtmlElement = sauron_core::dom::document()
+        .get_element_by_id(ELEM_ID)
+        .unwrap()
+        .unchecked_into();
+    assert_eq!(button.inner_text(), "new");
+
+    button.click();
+    delay(100).await;
+
+    assert_eq!(program.app().clicked, vec!["new"]);
+}
@@ -0,0 +1,94 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Card {
+    is_article: bool,
+    clicks: u32,
+}
+
+enum Msg {
+    ToggleWrapper,
+    Click,
+}
+
+impl Application<Msg> for Card {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::ToggleWrapper => self.is_article = !self.is_article,
+            Msg::Click => self.clicks += 1,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        let children = [
+            input([id("replace-tag-input"), r#type("text")], []),
+            button(
+                [id("replace-tag-button"), on_click(|_| Msg::Click)],
+                [text("click")],
+            ),
+        ];
+        let attrs = [id("replace-tag-wrapper"), class("card")];
+        let wrapper = if self.is_article {
+            article(attrs, children)
+        } else {
+            section(attrs, children)
+        };
+        div([], [wrapper])
+    }
+}
+
+#[wasm_bindgen_test]
+async fn changing_the_wrapper_tag_keeps_the_children() {
+    console_error_panic_hook::set_once();
+    let mut program = Program::mount_to_body(Card::default());
+    let document = sauron::dom::document();
+
+    let input: web_sys::HtmlInputElement = document
+        .get_element_by_id("replace-tag-input")
+        .unwrap()
+        .unchecked_into();
+    input.set_value("typed");
+    let button: web_sys::HtmlElement = document
+        .get_element_by_id("replace-tag-button")
+        .unwrap()
+        .unchecked_into();
+
+    program.dispatch(Msg::ToggleWrapper);
+    delay(50).await;
+
+    let wrapper = document.get_element_by_id("replace-tag-wrapper").unwrap();
+    assert_eq!(wrapper.tag_name().to_lowercase(), "article");
+    assert_eq!(wrapper.class_name(), "card");
+
+    // the children are the same DOM nodes, not recreated ones
+    let input_after = document.get_element_by_id("replace-tag-input").unwrap();
+    let button_after = document.get_element_by_id("replace-tag-button").unwrap();
+    assert!(input_after.is_same_node(Some(&input)));
+    assert!(button_after.is_same_node(Some(&button)));
+    assert_eq!(input.value(), "typed");
+
+    // the event listeners of the moved children still works
+    button.click();
+    delay(50).await;
+    assert_eq!(program.app().clicks, 1);
+
+    // and changing back is the same
+    program.dispatch(Msg::ToggleWrapper);
+    delay(50).await;
+    let wrapper = document.get_element_by_id("replace-tag-wrapper").unwrap();
+    assert_eq!(wrapper.tag_name().to_lowercase(), "section");
+    assert!(document
+        .get_element_by_id("replace-tag-input")
+        .unwrap()
+        .is_same_node(Some(&input)));
+    button.click();
+    delay(50).await;
+    assert_eq!(program.app().clicks, 2);
+}
@@ -0,0 +1,73 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const MOUNT_ID: &str = "replace-view-mount";
+
+#[derive(Default)]
+struct Screens {
+    clicked: Vec<&'static str>,
+}
+
+enum Msg {
+    Clicked(&'static str),
+}
+
+impl Application<Msg> for Screens {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Clicked(label) => self.clicked.push(label),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            [class("home")],
+            [button([on_click(|_| Msg::Clicked("home"))], [text("home")])],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+async fn replaced_view_is_rendered_and_receives_events() {
+    console_error_panic_hook::set_once();
+    let document = sauron_core::dom::document();
+    let mount = document.create_element("div").unwrap();
+    mount.set_id(MOUNT_ID);
+    sauron_core::dom::util::body().append_child(&mount).unwrap();
+
+    let mut program = Program::append_to_mount(Screens::default(), &mount);
+
+    let new_view = section(
+        [class("settings")],
+        [
+            h1([], [text("Settings")]),
+            button([on_click(|_| Msg::Clicked("settings"))], [text("save")]),
+        ],
+    );
+    program
+        .replace_view(new_view)
+        .expect("must replace the view");
+
+    assert!(mount.query_selector(".home").unwrap().is_none());
+    let section = mount
+        .query_selector("section.settings")
+        .unwrap()
+        .expect("must have the new view");
+    assert_eq!(section.text_content().as_deref(), Some("Settingssave"));
+
+    let button: web_sys::HtmlElement = mount
+        .query_selector("button")
+        .unwrap()
+        .unwrap()
+        .unchecked_into();
+    button.click();
+    delay(100).await;
+
+    assert_eq!(program.app().clicked, vec!["settings"]);
+}
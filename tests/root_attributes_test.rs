@@ -0,0 +1,62 @@
+#![deny(warnings)]
+use sauron::dom::{delay, MountAction, MountTarget};
+use sauron::{html::attributes::*, html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Counter {
+    count: i32,
+}
+
+enum Msg {
+    Increment,
+}
+
+impl Application<Msg> for Counter {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Increment => self.count += 1,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div([class(format!("count-{}", self.count))], [text(self.count)])
+    }
+}
+
+#[wasm_bindgen_test]
+async fn root_attributes_persist_across_updates() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    console_error_panic_hook::set_once();
+    let document = sauron::dom::document();
+    let mount = document.create_element("div").expect("must create");
+    document
+        .body()
+        .expect("must have body")
+        .append_child(&mount)
+        .expect("must append");
+
+    let mut program = Program::new(
+        Counter::default(),
+        &mount,
+        MountAction::Append,
+        MountTarget::MountNode,
+    )
+    .with_root_attributes([class("app-root"), attr("data-theme", "dark")]);
+    program.mount();
+
+    assert_eq!(mount.class_name(), "app-root");
+    assert_eq!(mount.get_attribute("data-theme").as_deref(), Some("dark"));
+
+    program.dispatch(Msg::Increment);
+    program.dispatch(Msg::Increment);
+    delay(100).await;
+
+    assert_eq!(program.app().count, 2);
+    assert_eq!(mount.class_name(), "app-root");
+    assert_eq!(mount.get_attribute("data-theme").as_deref(), Some("dark"));
+    assert_eq!(mount.inner_html(), r#"<div class="count-2">2</div>"#);
+}
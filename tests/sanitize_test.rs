@@ -0,0 +1,174 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, *},
+    svg::svg_element,
+    vdom::SanitizePolicy,
+    *,
+};
+
+#[test]
+fn script_and_inline_handlers_are_stripped() {
+    let view: Node<()> = div(
+        vec![class("comment"), attr("onclick", "steal()")],
+        vec![
+            p(
+                vec![attr("onmouseover", "steal()")],
+                vec![text("hello "), b(vec![], vec![text("world")])],
+            ),
+            script(vec![], vec![text("steal()")]),
+            img(
+                vec![src("cat.png"), alt("cat"), attr("onerror", "steal()")],
+                vec![],
+            ),
+        ],
+    );
+    let safe = view.sanitize(&SanitizePolicy::default());
+    assert_eq!(
+        safe.render_to_string(),
+        r#"<div class="comment"><p>hello <b>world</b></p><img src="cat.png" alt="cat"/></div>"#
+    );
+}
+
+#[test]
+fn javascript_urls_are_stripped() {
+    let view: Node<()> = div(
+        vec![],
+        vec![
+            a(vec![href("javascript:steal()")], vec![text("one")]),
+            a(vec![href(" JAVA\tscript:steal()")], vec![text("two")]),
+            a(vec![href("https://example.com")], vec![text("three")]),
+            a(vec![href("/relative/path?q=a:b")], vec![text("four")]),
+            a(vec![href("mailto:me@example.com")], vec![text("five")]),
+        ],
+    );
+    let safe = view.sanitize(&SanitizePolicy::default());
+    assert_eq!(
+        safe.render_to_string(),
+        concat!(
+            "<div>",
+            "<a>one</a>",
+            "<a>two</a>",
+            r#"<a href="https://example.com">three</a>"#,
+            r#"<a href="/relative/path?q=a:b">four</a>"#,
+            r#"<a href="mailto:me@example.com">five</a>"#,
+            "</div>"
+        )
+    );
+}
+
+#[test]
+fn raw_html_is_stripped() {
+    let view: Node<()> = div(
+        vec![],
+        vec![safe_html("<img src=x onerror=steal()>"), text("safe")],
+    );
+    let safe = view.sanitize(&SanitizePolicy::default());
+    assert_eq!(safe.render_to_string(), "<div>safe</div>");
+}
+
+#[test]
+fn allowlists_of_tags_and_attributes() {
+    let view: Node<()> = article(
+        vec![class("post"), id("post-1")],
+        vec![
+            h1(vec![class("title")], vec![text("Title")]),
+            p(vec![], vec![text("some "), em(vec![], vec![text("text")])]),
+        ],
+    );
+    let policy = SanitizePolicy::default()
+        .allow_tags(["article", "p", "em"])
+        .allow_attributes(["class"]);
+    let safe = view.sanitize(&policy);
+    // the elements which are not allowed are replaced by their children
+    assert_eq!(
+        safe.render_to_string(),
+        r#"<article class="post">Title<p>some <em>text</em></p></article>"#
+    );
+}
+
+#[test]
+fn disallowed_root_is_removed() {
+    let view: Node<()> = script(vec![], vec![text("steal()")]);
+    let safe = view.sanitize(&SanitizePolicy::default());
+    assert_eq!(safe.render_to_string(), "");
+}
+
+#[test]
+fn svg_links_and_animations_are_stripped() {
+    let view: Node<()> = svg_element(
+        "a",
+        vec![attr("xlink:href", "javascript:steal()")],
+        vec![
+            svg_element(
+                "animate",
+                vec![
+                    attr("attributeName", "href"),
+                    attr("to", "javascript:steal()"),
+                ],
+                vec![],
+            ),
+            svg_element(
+                "set",
+                vec![
+                    attr("attributeName", "href"),
+                    attr("to", "javascript:steal()"),
+                ],
+                vec![],
+            ),
+            text("click"),
+        ],
+    );
+    let safe = view.sanitize(&SanitizePolicy::default());
+    assert_eq!(safe.render_to_string(), "<a>click</a>");
+}
+
+#[test]
+fn every_url_of_the_srcset_is_checked() {
+    let view: Node<()> = div(
+        vec![],
+        vec![
+            img(
+                vec![srcset(["cat.png 1x", "javascript:steal() 2x"])],
+                vec![],
+            ),
+            img(
+                vec![srcset(["cat.png 1x", "https://example.com/cat.png 2x"])],
+                vec![],
+            ),
+        ],
+    );
+    let safe = view.sanitize(&SanitizePolicy::default());
+    assert_eq!(
+        safe.render_to_string(),
+        r#"<div><img/><img srcset="cat.png 1x, https://example.com/cat.png 2x"/></div>"#
+    );
+}
+
+#[test]
+fn tags_and_attribute_names_are_compared_case_insensitively() {
+    let view: Node<()> = div(
+        vec![],
+        vec![
+            html_element(None, "SCRIPT", vec![], vec![text("steal()")], false),
+            a(vec![attr("HREF", "javascript:steal()")], vec![text("link")]),
+        ],
+    );
+    let safe = view.sanitize(&SanitizePolicy::default());
+    assert_eq!(safe.render_to_string(), "<div><a>link</a></div>");
+
+    let view: Node<()> = p(vec![class("note")], vec![text("note")]);
+    let policy = SanitizePolicy::default()
+        .allow_tags(["P"])
+        .allow_attributes(["CLASS"]);
+    assert_eq!(
+        view.sanitize(&policy).render_to_string(),
+        r#"<p class="note">note</p>"#
+    );
+}
+
+#[test]
+fn attribute_names_with_multibyte_characters_are_kept() {
+    let view: Node<()> = div(vec![attr("中x", "1"), attr("éon", "2")], vec![]);
+    let safe = view.sanitize(&SanitizePolicy::default());
+    assert_eq!(safe.render_to_string(), r#"<div 中x="1" éon="2"></div>"#);
+}
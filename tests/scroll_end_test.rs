@@ -0,0 +1,85 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn scroll_end_is_emitted_once_when_scrolling_pauses() {
+    console_error_panic_hook::set_once();
+    let scroll_ends: Rc<RefCell<Vec<(i32, i32)>>> = Rc::new(RefCell::new(vec![]));
+    let scroll_ends_clone = Rc::clone(&scroll_ends);
+
+    let view: Node<()> = div(
+        vec![
+            id("scroll-end-container"),
+            style! {height: "50px", overflow: "auto"},
+            on_scroll_end(100, move |position| {
+                scroll_ends_clone.borrow_mut().push(position)
+            }),
+        ],
+        vec![div(vec![style! {height: "1000px"}], vec![])],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let container = sauron::dom::document()
+        .get_element_by_id("scroll-end-container")
+        .unwrap();
+    for top in [10, 20, 30, 40, 50] {
+        container.set_scroll_top(top);
+        let scroll = web_sys::Event::new("scroll").unwrap();
+        container.dispatch_event(&scroll).unwrap();
+        delay(20).await;
+    }
+    // still scrolling, the wait is restarted by each scroll event
+    assert!(scroll_ends.borrow().is_empty());
+
+    delay(300).await;
+    assert_eq!(*scroll_ends.borrow(), vec![(50, 0)]);
+}
+
+#[wasm_bindgen_test]
+async fn scroll_end_is_not_seen_by_the_other_scroll_listeners() {
+    console_error_panic_hook::set_once();
+    let scroll_ends: Rc<RefCell<Vec<&str>>> = Rc::new(RefCell::new(vec![]));
+    let scrolls: Rc<RefCell<usize>> = Rc::new(RefCell::new(0));
+    let (first_ends, second_ends) = (Rc::clone(&scroll_ends), Rc::clone(&scroll_ends));
+    let scrolls_clone = Rc::clone(&scrolls);
+
+    let view: Node<()> = div(
+        vec![
+            id("scroll-end-listeners"),
+            style! {height: "50px", overflow: "auto"},
+            on_scroll(move |_| *scrolls_clone.borrow_mut() += 1),
+            on_scroll_end(50, move |_| first_ends.borrow_mut().push("first")),
+            on_scroll_end(100, move |_| second_ends.borrow_mut().push("second")),
+        ],
+        vec![div(vec![style! {height: "1000px"}], vec![])],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let container = sauron::dom::document()
+        .get_element_by_id("scroll-end-listeners")
+        .unwrap();
+    container.set_scroll_top(10);
+    let scroll = web_sys::Event::new("scroll").unwrap();
+    container.dispatch_event(&scroll).unwrap();
+
+    // the scroll end of one listener doesn't restart the wait of the other
+    delay(500).await;
+    assert_eq!(*scroll_ends.borrow(), vec!["first", "second"]);
+    assert_eq!(*scrolls.borrow(), 1);
+}
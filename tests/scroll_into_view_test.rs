@@ -0,0 +1,67 @@
+#![deny(warnings)]
+use sauron::dom::{delay, ScrollLogicalPosition, ScrollOptions};
+use sauron::{html::attributes::*, html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Chat {
+    messages: Vec<String>,
+}
+
+enum Msg {
+    Send(String),
+}
+
+impl Application<Msg> for Chat {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Send(message) => {
+                self.messages.push(message);
+                Cmd::scroll_into_view(
+                    "#chat-messages > li:last-child",
+                    ScrollOptions::default().with_block(ScrollLogicalPosition::End),
+                )
+            }
+        }
+    }
+
+    fn view(&self) -> Node<Msg> {
+        ul(
+            [
+                id("chat-messages"),
+                style! {"height": px(100), "overflow": "auto"},
+            ],
+            self.messages
+                .iter()
+                .map(|message| li([style! {"height": px(50)}], [text(message)])),
+        )
+    }
+}
+
+fn scroll_top() -> i32 {
+    sauron::dom::document()
+        .get_element_by_id("chat-messages")
+        .expect("must have the messages")
+        .scroll_top()
+}
+
+#[wasm_bindgen_test]
+async fn appended_message_is_scrolled_into_view() {
+    console_error_panic_hook::set_once();
+    let mut program = Program::mount_to_body(Chat::default());
+
+    for i in 0..5 {
+        program.dispatch(Msg::Send(format!("message {i}")));
+    }
+    delay(200).await;
+    // 5 messages of 50px in a 100px container, the last one is at the bottom
+    assert_eq!(scroll_top(), 150);
+
+    // scroll back to the first message through the program handle
+    program
+        .scroll_into_view("#chat-messages > li:first-child", ScrollOptions::default())
+        .expect("must scroll");
+    assert_eq!(scroll_top(), 0);
+}
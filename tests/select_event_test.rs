@@ -0,0 +1,48 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn select_event_has_the_selection_range() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let selections = Rc::new(RefCell::new(vec![]));
+    let selections_clone = Rc::clone(&selections);
+
+    let elem_id = "select-event-textarea";
+    let view: Node<()> = textarea(
+        vec![
+            id(elem_id),
+            on_select(move |event: SelectionEvent| {
+                selections_clone
+                    .borrow_mut()
+                    .push((event.selection_start(), event.selection_end()));
+            }),
+        ],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let textarea_element: web_sys::HtmlTextAreaElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+    textarea_element.set_value("hello world");
+    textarea_element
+        .set_selection_range(2, 7)
+        .expect("must set selection range");
+    let select_event = web_sys::Event::new("select").unwrap();
+    textarea_element.dispatch_event(&select_event).unwrap();
+
+    assert_eq!(selections.borrow().first(), Some(&(Some(2), Some(7))));
+}
@@ -0,0 +1,41 @@
+#![deny(warnings)]
+use sauron::dom::{delay, set_timeout};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn timeout_callback_is_called() {
+    console_error_panic_hook::set_once();
+    let called = Rc::new(Cell::new(false));
+    let called_clone = Rc::clone(&called);
+    let _handle = set_timeout(move || called_clone.set(true), 10);
+
+    assert!(!called.get());
+    delay(50).await;
+    assert!(called.get());
+}
+
+#[wasm_bindgen_test]
+async fn cancelled_timeout_is_never_called() {
+    console_error_panic_hook::set_once();
+    let called = Rc::new(Cell::new(false));
+    let called_clone = Rc::clone(&called);
+    let handle = set_timeout(move || called_clone.set(true), 10);
+    handle.cancel();
+
+    delay(50).await;
+    assert!(!called.get());
+}
+
+#[wasm_bindgen_test]
+async fn dropped_timeout_is_never_called() {
+    console_error_panic_hook::set_once();
+    let called = Rc::new(Cell::new(false));
+    let called_clone = Rc::clone(&called);
+    drop(set_timeout(move || called_clone.set(true), 10));
+
+    delay(50).await;
+    assert!(!called.get());
+}
@@ -0,0 +1,60 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const MOUNT_ID: &str = "shutdown-mount";
+
+struct Counter {
+    count: i32,
+}
+
+enum Msg {
+    Increment,
+    KeyPressed,
+}
+
+impl Application<Msg> for Counter {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Increment => self.count += 1,
+            Msg::KeyPressed => (),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            [class("counter")],
+            [
+                button([on_click(|_| Msg::Increment)], [text("+")]),
+                span([on_mouseover(|_| Msg::Increment)], [text(self.count)]),
+            ],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+fn shutdown_unmounts_the_app_and_drops_closures() {
+    console_error_panic_hook::set_once();
+    let document = sauron_core::dom::document();
+    let mount = document.create_element("div").unwrap();
+    mount.set_id(MOUNT_ID);
+    sauron_core::dom::util::body().append_child(&mount).unwrap();
+
+    let mut program = Program::append_to_mount(Counter { count: 0 }, &mount);
+    program.add_window_event_listeners(vec![on_keydown(|_| Msg::KeyPressed)]);
+    assert_eq!(mount.child_element_count(), 1);
+    assert!(!program.node_closures.borrow().is_empty());
+
+    program.shutdown();
+
+    assert_eq!(mount.inner_html(), "");
+    assert_eq!(program.node_closures.borrow().len(), 0);
+
+    // the window listener is detached, dispatching the event must not invoke a dropped closure
+    sauron_core::dom::window()
+        .dispatch_event(&web_sys::Event::new("keydown").unwrap())
+        .unwrap();
+}
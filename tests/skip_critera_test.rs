@@ -11,3 +11,27 @@ fn must_skip_diff() {
     dbg!(&patch);
     assert_eq!(patch, vec![]);
 }
+
+#[test]
+fn custom_comparison_skips_version_equal_nodes() {
+    let same_version = |old: &Node<()>, new: &Node<()>| {
+        old.attribute_value(&"data-version") == new.attribute_value(&"data-version")
+    };
+    let old: Node<()> = div(
+        [attr("data-version", 7)],
+        [span([class("old")], [text("old here")])],
+    );
+    let new: Node<()> = div(
+        [attr("data-version", 7)],
+        [span([class("new")], [text("new here")])],
+    );
+    assert_eq!(diff_with(&old, &new, same_version), vec![]);
+    // the plain diff still sees the changes
+    assert!(!diff(&old, &new).is_empty());
+
+    let newer: Node<()> = div(
+        [attr("data-version", 8)],
+        [span([class("new")], [text("new here")])],
+    );
+    assert!(!diff_with(&old, &newer, same_version).is_empty());
+}
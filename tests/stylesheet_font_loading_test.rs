@@ -0,0 +1,44 @@
+#![deny(warnings)]
+use sauron::dom::{document, fonts_ready};
+use sauron::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn fonts_ready_task_completes() {
+    console_error_panic_hook::set_once();
+
+    fonts_ready().await.expect("must resolve");
+
+    let mut task = Task::fonts_ready("fonts loaded");
+    assert_eq!(task.next().await, Some("fonts loaded"));
+    assert_eq!(task.next().await, None);
+}
+
+#[wasm_bindgen_test]
+async fn stylesheet_loaded_task_completes() {
+    console_error_panic_hook::set_once();
+
+    let link: web_sys::HtmlLinkElement = document()
+        .create_element("link")
+        .expect("must create link")
+        .unchecked_into();
+    link.set_rel("stylesheet");
+    link.set_href("data:text/css,.styled{color:red}");
+    let mut task = Task::stylesheet_loaded(link.clone(), "stylesheet loaded");
+    document()
+        .head()
+        .expect("must have head")
+        .append_child(&link)
+        .expect("must append");
+
+    assert_eq!(task.next().await, Some("stylesheet loaded"));
+    assert!(link.sheet().is_some());
+
+    // the stylesheet is already loaded, so this resolves right away
+    let mut task = Task::stylesheet_loaded(link.clone(), "already loaded");
+    assert_eq!(task.next().await, Some("already loaded"));
+    link.remove();
+}
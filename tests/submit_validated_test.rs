@@ -0,0 +1,77 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Signup {
+    submitted: bool,
+    invalid: Vec<(String, bool)>,
+}
+
+enum Msg {
+    Submit,
+    Invalid(Vec<InvalidField>),
+}
+
+impl Application<Msg> for Signup {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Submit => self.submitted = true,
+            Msg::Invalid(fields) => {
+                self.invalid = fields
+                    .into_iter()
+                    .map(|field| (field.name, field.validity.value_missing()))
+                    .collect()
+            }
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        form(
+            [
+                id("signup-form"),
+                novalidate(true),
+                on_submit_validated(
+                    |e| {
+                        e.prevent_default();
+                        Msg::Submit
+                    },
+                    Msg::Invalid,
+                ),
+            ],
+            [
+                input([name("username"), value("sauron")], []),
+                input([r#type("email"), name("email"), required(true)], []),
+            ],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+async fn invalid_form_dispatches_the_validation_msg_instead_of_submitting() {
+    console_error_panic_hook::set_once();
+    let program = Program::mount_to_body(Signup::default());
+
+    let invalid = program
+        .form_validity("signup-form")
+        .expect("must find the form");
+    assert_eq!(invalid.len(), 1);
+    assert_eq!(invalid[0].name, "email");
+    assert!(invalid[0].validity.value_missing());
+    assert!(!invalid[0].message.is_empty());
+
+    let form_element: web_sys::HtmlFormElement = sauron::dom::document()
+        .get_element_by_id("signup-form")
+        .unwrap()
+        .unchecked_into();
+    form_element.request_submit().expect("must request submit");
+    delay(100).await;
+
+    assert!(!program.app().submitted);
+    assert_eq!(program.app().invalid, vec![("email".to_string(), true)]);
+}
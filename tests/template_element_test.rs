@@ -0,0 +1,115 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn template_renders_children() {
+    let view: Node<()> = template(vec![], vec![li(vec![class("row")], vec![text("item")])]);
+    assert_eq!(
+        r#"<template><li class="row">item</li></template>"#,
+        view.render_to_string()
+    );
+}
+
+#[wasm_bindgen_test]
+fn template_children_are_in_the_content() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let elem_id = "template-content";
+    let old: Node<()> = template(
+        vec![id(elem_id)],
+        vec![li(vec![class("row")], vec![text("item")])],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(old, None)
+        .expect("must not error");
+
+    let template_element: web_sys::HtmlTemplateElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+
+    assert_eq!(template_element.child_element_count(), 0);
+    let content = template_element.content();
+    assert_eq!(content.child_element_count(), 1);
+    assert_eq!(
+        content.first_element_child().unwrap().outer_html(),
+        r#"<li class="row">item</li>"#
+    );
+
+    let new: Node<()> = template(
+        vec![id(elem_id)],
+        vec![
+            li(vec![class("row")], vec![text("changed")]),
+            li(vec![class("row")], vec![text("appended")]),
+        ],
+    );
+    simple_program
+        .update_dom_with_vdom(new, None)
+        .expect("must not error");
+
+    assert_eq!(content.child_element_count(), 2);
+    assert_eq!(
+        content.first_element_child().unwrap().outer_html(),
+        r#"<li class="row">changed</li>"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn safe_html_of_template_is_in_the_content() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let elem_id = "template-safe-html";
+    let view: Node<()> = template(
+        vec![id(elem_id)],
+        vec![safe_html(r#"<li class="row">raw</li>"#)],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let template_element: web_sys::HtmlTemplateElement = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .unchecked_into();
+
+    assert_eq!(template_element.child_element_count(), 0);
+    let content = template_element.content();
+    assert_eq!(content.child_element_count(), 1);
+    assert_eq!(
+        content.first_element_child().unwrap().outer_html(),
+        r#"<li class="row">raw</li>"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn listeners_in_the_template_content_are_removed_with_the_template() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let old: Node<()> = div(
+        vec![id("template-host")],
+        vec![template(
+            vec![],
+            vec![button(vec![on_click(|_| ())], vec![text("click")])],
+        )],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(old, None)
+        .expect("must not error");
+    assert_eq!(simple_program.node_closures.borrow().len(), 1);
+
+    let new: Node<()> = div(vec![id("template-host")], vec![]);
+    simple_program
+        .update_dom_with_vdom(new, None)
+        .expect("must not error");
+    assert!(simple_program.node_closures.borrow().is_empty());
+}
@@ -119,3 +119,482 @@ fn text_node_in_script_work_as_is() {
     println!("result: {}", result);
     assert_eq!(expected, result)
 }
+
+#[test]
+fn render_sorted_attributes() {
+    let view: Node<()> = div(
+        vec![
+            lang("en"),
+            class("frame"),
+            id("main"),
+            class("layer"),
+            attr("data-id", 1),
+        ],
+        vec![],
+    );
+
+    assert_eq!(
+        r#"<div lang="en" class="frame layer" id="main" data-id="1"></div>"#,
+        view.render_to_string()
+    );
+
+    let options = RenderOptions::compressed().sort_attributes(true);
+    assert_eq!(
+        r#"<div class="frame layer" data-id="1" id="main" lang="en"></div>"#,
+        view.render_to_string_with_options(&options)
+    );
+}
+
+#[test]
+fn text_macro_creates_text_node() {
+    let count = 42;
+    let node: Node<()> = text!("count: {}", count);
+    assert_eq!(node, text("count: 42"));
+}
+
+#[test]
+fn text_macro_value_is_escaped_when_rendered() {
+    let user_input = "<b>bold</b> & co";
+    let view: Node<()> = p(vec![], vec![text!("says: {}", user_input)]);
+    assert_eq!(
+        "<p>says: &lt;b&gt;bold&lt;/b&gt; &amp; co</p>",
+        view.render_to_string()
+    );
+}
+
+#[test]
+fn text_in_style_is_not_escaped() {
+    let view: Node<()> = html::tags::style(vec![], vec![text("div > p { color: red; }")]);
+    assert_eq!(
+        "<style>div > p { color: red; }</style>",
+        view.render_to_string()
+    );
+}
+
+#[test]
+fn contenteditable_renders_both_states() {
+    let editable: Node<()> = div(vec![contenteditable(true)], vec![]);
+    assert_eq!(
+        r#"<div contenteditable="true"></div>"#,
+        editable.render_to_string()
+    );
+    let not_editable: Node<()> = div(vec![contenteditable(false)], vec![]);
+    assert_eq!(
+        r#"<div contenteditable="false"></div>"#,
+        not_editable.render_to_string()
+    );
+}
+
+#[test]
+fn tabindex_renders_number() {
+    let view: Node<()> = div(vec![tabindex(0)], vec![]);
+    assert_eq!(r#"<div tabindex="0"></div>"#, view.render_to_string());
+    let view: Node<()> = div(vec![tabindex(-1)], vec![]);
+    assert_eq!(r#"<div tabindex="-1"></div>"#, view.render_to_string());
+}
+
+#[test]
+fn render_with_node_idx() {
+    let view: Node<()> = div(
+        vec![class("container")],
+        vec![
+            span(vec![], vec![text("hello")]),
+            ul(vec![], vec![li(vec![], vec![]), li(vec![], vec![])]),
+        ],
+    );
+    let options = RenderOptions::compressed().emit_node_idx(true);
+    assert_eq!(
+        view.render_to_string_with_options(&options),
+        r#"<div class="container" data-node-idx="0"><span data-node-idx="1">hello</span><ul data-node-idx="3"><li data-node-idx="4"></li><li data-node-idx="5"></li></ul></div>"#
+    );
+
+    let options = RenderOptions::compressed().emit_node_idx(false);
+    assert_eq!(
+        view.render_to_string_with_options(&options),
+        r#"<div class="container"><span>hello</span><ul><li></li><li></li></ul></div>"#
+    );
+
+    // the node index is opt-in, regardless of the enabled features
+    assert!(!RenderOptions::default().emit_node_idx);
+    assert_eq!(
+        view.render_to_string(),
+        r#"<div class="container"><span>hello</span><ul><li></li><li></li></ul></div>"#
+    );
+}
+
+#[test]
+fn srcset_is_comma_separated() {
+    let view: Node<()> = img(
+        vec![srcset(["small.png 480w", "large.png 1080w"]), sizes("50vw")],
+        vec![],
+    );
+    assert_eq!(
+        r#"<img srcset="small.png 480w, large.png 1080w" sizes="50vw"/>"#,
+        view.render_to_string()
+    );
+
+    let merged: Node<()> = img(
+        vec![srcset(["small.png 1x"]), srcset(["large.png 2x"])],
+        vec![],
+    );
+    assert_eq!(
+        r#"<img srcset="small.png 1x, large.png 2x"/>"#,
+        merged.render_to_string()
+    );
+}
+
+#[test]
+fn none_child_produces_no_node() {
+    let badge: Option<Node<()>> = None;
+    let view: Node<()> = div(vec![], vec![text("inbox"), badge.into_node()]);
+    assert_eq!("<div>inbox</div>", view.render_to_string());
+
+    let badge: Option<Node<()>> = None;
+    let view: Node<()> = node! {<div>"inbox"{badge}</div>};
+    assert_eq!("<div>inbox</div>", view.render_to_string());
+}
+
+#[test]
+fn some_and_vec_children_are_flattened() {
+    let badge: Option<Node<()>> = Some(span(vec![], vec![text("3")]));
+    let items: Vec<Node<()>> = vec![li(vec![], vec![text("one")]), li(vec![], vec![text("two")])];
+    let view: Node<()> = div(
+        vec![],
+        vec![
+            badge.clone().into_node(),
+            ul(vec![], vec![items.clone().into_node()]),
+        ],
+    );
+    let expected = "<div><span>3</span><ul><li>one</li><li>two</li></ul></div>";
+    assert_eq!(expected, view.render_to_string());
+
+    let view: Node<()> = node! {<div>{badge}<ul>{items}</ul></div>};
+    assert_eq!(expected, view.render_to_string());
+}
+
+#[test]
+fn render_dialog_open() {
+    let view: Node<()> = dialog(vec![open(true)], vec![text("Saved")]);
+    assert_eq!(
+        r#"<dialog open="true">Saved</dialog>"#,
+        view.render_to_string()
+    );
+
+    let closed: Node<()> = dialog(vec![open(false)], vec![text("Saved")]);
+    assert_eq!("<dialog>Saved</dialog>", closed.render_to_string());
+}
+
+#[test]
+fn render_named_slot() {
+    let view: Node<()> = html::tags::slot(vec![name("x")], vec![]);
+    assert_eq!(r#"<slot name="x"></slot>"#, view.render_to_string());
+}
+
+#[test]
+fn render_search_and_figure() {
+    let view: Node<()> = search(
+        vec![],
+        vec![figure(
+            vec![],
+            vec![
+                picture(vec![], vec![]),
+                figcaption(vec![], vec![text("map")]),
+            ],
+        )],
+    );
+    assert_eq!(
+        "<search><figure><picture></picture><figcaption>map</figcaption></figure></search>",
+        view.render_to_string()
+    );
+}
+
+#[test]
+fn render_input_constraints() {
+    let view: Node<()> = input(
+        vec![
+            r#type("number"),
+            min(1),
+            max(10),
+            step(0.5),
+            html::attributes::pattern("[0-9]+"),
+        ],
+        vec![],
+    );
+    assert_eq!(
+        r#"<input type="number" min="1" max="10" step="0.5" pattern="[0-9]+"/>"#,
+        view.render_to_string()
+    );
+}
+
+#[test]
+fn render_large_page_with_capacity() {
+    let view: Node<()> = ul(
+        vec![class("rows")],
+        (0..1000).map(|i| {
+            li(
+                vec![class("row"), id(format!("row-{i}"))],
+                vec![text(format!("row {i}"))],
+            )
+        }),
+    );
+    let expected = view.render_to_string();
+
+    let rendered = view.render_to_string_with_capacity(expected.len());
+    assert_eq!(rendered, expected);
+    // the allocator may give more than the requested capacity, but never less
+    assert!(rendered.capacity() >= expected.len());
+}
+
+#[test]
+fn attribute_values_are_escaped() {
+    let view: Node<()> = a(
+        vec![href("/search?q=rust&page=2"), attr("title", r#"say "hi""#)],
+        vec![text("search")],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        r#"<a href="/search?q=rust&amp;page=2" title="say &quot;hi&quot;">search</a>"#
+    );
+}
+
+#[test]
+fn render_iframe_srcdoc() {
+    let view: Node<()> = iframe_srcdoc(
+        [sandbox_allow(["allow-scripts", "allow-forms"])],
+        r#"<p class="greeting">Tom & Jerry</p>"#,
+    );
+    assert_eq!(
+        view.render_to_string(),
+        r#"<iframe sandbox="allow-scripts allow-forms" srcdoc="<p class=&quot;greeting&quot;>Tom &amp; Jerry</p>"></iframe>"#
+    );
+}
+
+#[test]
+fn vdom_attributes_are_not_rendered() {
+    let view: Node<()> = ul(
+        vec![],
+        vec![
+            li(vec![key(1)], vec![text("one")]),
+            li(
+                vec![key(2), skip(true), replace(false), merge_class(true)],
+                vec![text("two")],
+            ),
+        ],
+    );
+    assert_eq!(view.render_to_string(), "<ul><li>one</li><li>two</li></ul>");
+}
+
+#[test]
+fn render_shadow_parts() {
+    let view: Node<()> = div(
+        vec![exportparts(["header", "title:card-title"])],
+        vec![header(vec![part("header")], vec![text("Card")])],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        r#"<div exportparts="header, title:card-title"><header part="header">Card</header></div>"#
+    );
+}
+
+#[test]
+fn render_node_offsets() {
+    let view: Node<()> = div(
+        [class("app")],
+        [
+            h1([], [text("Title")]),
+            ul([], [li([], [text("one")]), li([], [text("two")])]),
+        ],
+    );
+    let (html, offsets) = render_to_string_with_node_offsets(&view);
+    assert_eq!(
+        html,
+        r#"<div class="app"><h1>Title</h1><ul><li>one</li><li>two</li></ul></div>"#
+    );
+    assert_eq!(
+        offsets,
+        [
+            (0, 0),
+            (1, 17),
+            (2, 21),
+            (3, 31),
+            (4, 35),
+            (5, 39),
+            (6, 47),
+            (7, 51)
+        ]
+    );
+    // the elements start at the `<`, the text nodes start at their first character
+    for idx in [0, 1, 3, 4, 6] {
+        assert_eq!(html.as_bytes()[offsets[idx].1], b'<');
+    }
+    assert!(html[offsets[2].1..].starts_with("Title"));
+    assert!(html[offsets[7].1..].starts_with("two"));
+}
+
+#[test]
+fn stripped_event_handlers_render_identically() {
+    use sauron::html::events::{on_click, on_input};
+
+    let view: Node<String> = div(
+        vec![class("form"), on_click(|_| "clicked".to_string())],
+        vec![
+            input(vec![r#type("text"), on_input(|e| e.value())], vec![]),
+            button(
+                vec![on_click(|_| "submit".to_string())],
+                vec![text("Submit")],
+            ),
+        ],
+    );
+    let html = view.render_to_string();
+
+    let stripped: Node<()> = view.strip_event_handlers();
+    assert!(stripped.get_callbacks().is_empty());
+    assert_eq!(stripped.render_to_string(), html);
+
+    let cached = stripped.clone();
+    assert_eq!(cached, stripped);
+    assert_eq!(cached.render_to_string(), html);
+}
+
+#[test]
+fn render_responsive_picture() {
+    let view: Node<()> = picture(
+        vec![],
+        vec![
+            source(
+                vec![
+                    media("(min-width: 800px)"),
+                    r#type("image/avif"),
+                    srcset(["large.avif 1x", "large@2x.avif 2x"]),
+                ],
+                vec![],
+            ),
+            source(
+                vec![
+                    r#type("image/webp"),
+                    srcset(["small.webp 480w", "medium.webp 800w"]),
+                    sizes("50vw"),
+                ],
+                vec![],
+            ),
+            img(vec![src("fallback.jpg"), alt("A sunset")], vec![]),
+        ],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        concat!(
+            "<picture>",
+            r#"<source media="(min-width: 800px)" type="image/avif" srcset="large.avif 1x, large@2x.avif 2x"/>"#,
+            r#"<source type="image/webp" srcset="small.webp 480w, medium.webp 800w" sizes="50vw"/>"#,
+            r#"<img src="fallback.jpg" alt="A sunset"/>"#,
+            "</picture>"
+        )
+    );
+}
+
+#[test]
+fn render_script_style_nonce() {
+    let view: Node<()> = div(
+        vec![],
+        vec![
+            script(vec![r#type("module")], vec![text("start();")]),
+            html::tags::style(vec![], vec![text("p { color: red; }")]),
+            link(vec![rel("stylesheet"), href("main.css")], vec![]),
+            script(vec![attr("nonce", "own")], vec![]),
+            p(vec![], vec![text("hello")]),
+        ],
+    );
+    let options = RenderOptions::compressed().script_style_nonce("r4nd0m");
+    assert_eq!(
+        view.render_to_string_with_options(&options),
+        concat!(
+            "<div>",
+            r#"<script type="module" nonce="r4nd0m">start();</script>"#,
+            r#"<style nonce="r4nd0m">p { color: red; }</style>"#,
+            r#"<link rel="stylesheet" href="main.css"/>"#,
+            r#"<script nonce="own"></script>"#,
+            "<p>hello</p>",
+            "</div>"
+        )
+    );
+
+    // no nonce is rendered by default
+    assert_eq!(
+        view.render_to_string(),
+        concat!(
+            "<div>",
+            r#"<script type="module">start();</script>"#,
+            r#"<style>p { color: red; }</style>"#,
+            r#"<link rel="stylesheet" href="main.css"/>"#,
+            r#"<script nonce="own"></script>"#,
+            "<p>hello</p>",
+            "</div>"
+        )
+    );
+}
+
+#[test]
+fn count_nodes_and_depth() {
+    let view: Node<()> = div(
+        [class("app")],
+        [
+            h1([], [text("Title")]),
+            ul([], [li([], [text("one")]), li([], [text("two")])]),
+        ],
+    );
+    assert_eq!(view.count_nodes(), 8);
+    assert_eq!(view.depth(), 4);
+
+    // the count matches the node indexes used in the diff
+    let (_html, offsets) = render_to_string_with_node_offsets(&view);
+    assert_eq!(
+        offsets.last().map(|(idx, _)| idx + 1),
+        Some(view.count_nodes())
+    );
+
+    let leaf: Node<()> = text("alone");
+    assert_eq!(leaf.count_nodes(), 1);
+    assert_eq!(leaf.depth(), 1);
+
+    // fragments are counted as a node and as a level
+    let fragment: Node<()> = node_list([span([], [text("a")]), span([], [])]);
+    assert_eq!(fragment.count_nodes(), 4);
+    assert_eq!(fragment.depth(), 3);
+}
+
+#[test]
+fn very_deep_trees_are_rendered_without_overflowing_the_stack() {
+    let view: Node<()> = (0..5000).fold(text("deep"), |node, _| div([], [node]));
+    assert_eq!(
+        view.render_to_string(),
+        format!("{}deep{}", "<div>".repeat(5000), "</div>".repeat(5000))
+    );
+    let (_html, offsets) = render_to_string_with_node_offsets(&view);
+    assert_eq!(offsets.last(), Some(&(5000, 5000 * "<div>".len())));
+
+    // take apart the tree without recursion, since dropping a deep tree is recursive too
+    let mut stack = vec![view];
+    while let Some(mut node) = stack.pop() {
+        if let Node::Element(element) = &mut node {
+            stack.append(&mut element.children);
+        }
+    }
+}
+
+#[test]
+fn render_pretty_keeps_the_whitespace_of_pre_and_textarea() {
+    let view: Node<()> = div(
+        [],
+        [
+            pre([], [code([], [text("fn main() {\n    run();\n}")])]),
+            pre([], [text("a "), b([], [text("bold")]), text("  c")]),
+            textarea([], [text("\nstarts with a new line")]),
+        ],
+    );
+    let expected = "<div>\n  <pre><code>fn main() {\n    run();\n}</code></pre>\n  <pre>a <b>bold</b>  c</pre>\n  <textarea>\n\nstarts with a new line</textarea>\n</div>";
+    assert_eq!(view.render_to_string_pretty(), expected);
+
+    // the leading new line is kept in the compressed render too
+    let view: Node<()> = textarea([], [text("\nvalue")]);
+    assert_eq!(view.render_to_string(), "<textarea>\n\nvalue</textarea>");
+}
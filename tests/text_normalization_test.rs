@@ -0,0 +1,49 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::*, *};
+use std::mem::ManuallyDrop;
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn adjacent_text_nodes_are_merged_across_updates() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let elem_id = "text-normalization";
+    let mut program = ManuallyDrop::into_inner(simple_program()).with_text_normalization(true);
+
+    let views: Vec<Node<()>> = vec![
+        div([id(elem_id)], [text("Hello "), text("world")]),
+        div(
+            [id(elem_id)],
+            [text("Hello "), text("there, "), text("world")],
+        ),
+        div(
+            [id(elem_id)],
+            [text("Bye "), node_list([text("for "), text("now")])],
+        ),
+        div(
+            [id(elem_id)],
+            [text("Hello "), text("world"), br([], []), text("!")],
+        ),
+    ];
+    let expected = [
+        (1, "Hello world"),
+        (1, "Hello there, world"),
+        (2, "Bye for now"),
+        (3, "Hello world!"),
+    ];
+
+    for (view, (child_count, content)) in views.into_iter().zip(expected) {
+        program
+            .update_dom_with_vdom(view, None)
+            .expect("must not error");
+        let element = sauron_core::dom::document()
+            .get_element_by_id(elem_id)
+            .expect("must have the element");
+        assert_eq!(element.child_nodes().length(), child_count);
+        assert_eq!(element.text_content().as_deref(), Some(content));
+    }
+}
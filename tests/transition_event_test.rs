@@ -0,0 +1,61 @@
+#![deny(warnings)]
+use sauron::dom::delay;
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn interrupted_transition_dispatches_run_and_cancel() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    console_error_panic_hook::set_once();
+    let events = Rc::new(RefCell::new(vec![]));
+    let run_events = Rc::clone(&events);
+    let cancel_events = Rc::clone(&events);
+
+    let elem_id = "transition-lifecycle";
+    let view: Node<()> = div(
+        vec![
+            id(elem_id),
+            attr("style", "transition: opacity 2s; opacity: 1;"),
+            on_transitionrun(move |event: TransitionEvent| {
+                run_events
+                    .borrow_mut()
+                    .push(format!("run {}", event.property_name()));
+            }),
+            on_transitioncancel(move |event: TransitionEvent| {
+                cancel_events
+                    .borrow_mut()
+                    .push(format!("cancel {}", event.property_name()));
+            }),
+        ],
+        vec![text("fading")],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let element = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap();
+    // reading the layout flushes the initial style, so the next change is transitioned
+    element.client_width();
+    element
+        .set_attribute("style", "transition: opacity 2s; opacity: 0;")
+        .unwrap();
+    delay(100).await;
+    assert_eq!(*events.borrow(), ["run opacity"]);
+
+    // removing the transition interrupts the running transition
+    element
+        .set_attribute("style", "transition: none; opacity: 0;")
+        .unwrap();
+    delay(100).await;
+    assert_eq!(*events.borrow(), ["run opacity", "cancel opacity"]);
+}
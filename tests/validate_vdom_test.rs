@@ -0,0 +1,155 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, *},
+    vdom::{validate, ValidationWarning},
+    *,
+};
+
+#[test]
+fn valid_tree_has_no_warnings() {
+    let view: Node<()> = table(
+        vec![],
+        vec![tr(
+            vec![key(1)],
+            vec![
+                td(vec![], vec![text("cell")]),
+                td(vec![], vec![input(vec![], vec![])]),
+            ],
+        )],
+    );
+    assert!(validate(&view).is_empty());
+}
+
+#[test]
+fn children_in_void_element() {
+    let view: Node<()> = div(vec![], vec![img(vec![], vec![span(vec![], vec![])])]);
+    assert_eq!(
+        validate(&view),
+        vec![ValidationWarning::VoidElementWithChildren { tag: "img" }]
+    );
+}
+
+#[test]
+fn duplicate_keys_in_siblings() {
+    let view: Node<()> = ul(
+        vec![],
+        vec![
+            li(vec![key("a")], vec![]),
+            li(vec![key("b")], vec![]),
+            fragment([li(vec![key("a")], vec![])]),
+        ],
+    );
+    let warnings = validate(&view);
+    assert_eq!(
+        warnings,
+        vec![ValidationWarning::DuplicateKey {
+            parent: "ul",
+            key: "a".to_string()
+        }]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "duplicate key `a` in the children of `<ul>`"
+    );
+}
+
+#[test]
+fn text_in_table_row() {
+    let view: Node<()> = table(
+        vec![],
+        vec![tr(
+            vec![],
+            vec![text("oops"), td(vec![], vec![text("cell")])],
+        )],
+    );
+    assert_eq!(
+        validate(&view),
+        vec![ValidationWarning::TextInTable {
+            tag: "tr",
+            text: "oops".to_string()
+        }]
+    );
+}
+
+#[test]
+fn button_without_accessible_name() {
+    let view: Node<()> = div(
+        vec![],
+        vec![button(
+            vec![class("icon-close")],
+            vec![span(vec![], vec![])],
+        )],
+    );
+    let warnings = validate(&view);
+    assert_eq!(
+        warnings,
+        vec![ValidationWarning::MissingAccessibleName { tag: "button" }]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "`<button>` has no text content nor `aria-label`"
+    );
+}
+
+#[test]
+fn buttons_with_accessible_name() {
+    let view: Node<()> = div(
+        vec![],
+        vec![
+            button(vec![], vec![span(vec![], vec![text("Close")])]),
+            button(vec![attr("aria-label", "Close")], vec![]),
+            button(vec![], vec![img(vec![alt("Close")], vec![])]),
+            a(vec![href("/"), attr("role", "link")], vec![text("Home")]),
+        ],
+    );
+    assert!(validate(&view).is_empty());
+}
+
+#[test]
+fn invalid_role_value() {
+    let view: Node<()> = div(vec![attr("role", "buton")], vec![text("Click")]);
+    assert_eq!(
+        validate(&view),
+        vec![ValidationWarning::InvalidRole {
+            tag: "div",
+            role: "buton".to_string()
+        }]
+    );
+}
+
+#[test]
+fn mixed_keyed_and_unkeyed_siblings() {
+    let view: Node<()> = ul(
+        vec![],
+        vec![
+            li(vec![key("a")], vec![text("a")]),
+            li(vec![], vec![text("no key")]),
+            text(" "),
+            li(vec![key("b")], vec![text("b")]),
+        ],
+    );
+    let warnings = validate(&view);
+    assert_eq!(
+        warnings,
+        vec![ValidationWarning::MixedKeyedChildren {
+            parent: "ul",
+            keyed: 2,
+            unkeyed: 1,
+        }]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "`<ul>` has 2 keyed and 1 unkeyed child elements"
+    );
+
+    // text siblings can not have keys and are not counted as unkeyed
+    let view: Node<()> = ul(
+        vec![],
+        vec![
+            li(vec![key("a")], vec![]),
+            text(" "),
+            li(vec![key("b")], vec![]),
+        ],
+    );
+    assert!(validate(&view).is_empty());
+}
@@ -0,0 +1,57 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn view() -> Node<()> {
+    div(
+        [class("container"), on_click(|_| ())],
+        [
+            span([], [text("no listeners")]),
+            ul(
+                [],
+                [
+                    li([on_mouseover(|_| ()), on_mouseout(|_| ())], [text("one")]),
+                    li([], [button([on_click(|_| ())], [text("two")])]),
+                ],
+            ),
+            input([r#type("text"), on_input(|_| ())], []),
+        ],
+    )
+}
+
+fn vdom_ids(element: &web_sys::Element) -> Vec<String> {
+    let mut ids: Vec<String> = element.get_attribute("data-vdom-id").into_iter().collect();
+    let descendants = element
+        .query_selector_all("[data-vdom-id]")
+        .expect("must query");
+    for i in 0..descendants.length() {
+        let descendant: web_sys::Element = descendants.get(i).unwrap().unchecked_into();
+        ids.extend(descendant.get_attribute("data-vdom-id"));
+    }
+    ids
+}
+
+#[wasm_bindgen_test]
+fn server_rendered_vdom_ids_matches_the_client_created_ids() {
+    console_log::init_with_level(log::Level::Trace).ok();
+
+    let options = RenderOptions::compressed().emit_vdom_id(true);
+    let server_html = view().render_to_string_with_options(&options);
+    let server_node = sauron_core::dom::document()
+        .create_element("div")
+        .expect("must create");
+    server_node.set_inner_html(&server_html);
+    let server_element = server_node.first_element_child().expect("must have child");
+
+    let program = simple_program();
+    let client_element: web_sys::Element = program.create_dom_node(&view()).unchecked_into();
+
+    assert_eq!(vdom_ids(&server_element), ["1", "2", "3", "4"]);
+    assert_eq!(vdom_ids(&client_element), vdom_ids(&server_element));
+}
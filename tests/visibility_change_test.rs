@@ -0,0 +1,46 @@
+#![deny(warnings)]
+use sauron::dom::{delay, is_document_visible, on_visibility_change};
+use sauron::{html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Poller {
+    visibility: Vec<bool>,
+}
+
+enum Msg {
+    VisibilityChanged(bool),
+}
+
+impl Application<Msg> for Poller {
+    fn init(&mut self) -> Cmd<Self, Msg> {
+        Cmd::from(on_visibility_change(Msg::VisibilityChanged))
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::VisibilityChanged(visible) => self.visibility.push(visible),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div([], [text(format!("{:?}", self.visibility))])
+    }
+}
+
+#[wasm_bindgen_test]
+async fn visibility_change_reports_the_visibility_state() {
+    console_error_panic_hook::set_once();
+    let program = Program::mount_to_body(Poller::default());
+
+    // the tab can not be hidden from the test runner, so the event is dispatched manually
+    // and the handler reports the current state of the document, whichever it is
+    let event = web_sys::Event::new("visibilitychange").unwrap();
+    sauron::dom::document().dispatch_event(&event).unwrap();
+    delay(100).await;
+
+    assert_eq!(program.app().visibility, vec![is_document_visible()]);
+}
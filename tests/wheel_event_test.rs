@@ -0,0 +1,56 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::events::*, html::*, *};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn wheel_event_has_deltas() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let deltas = Rc::new(RefCell::new(None));
+    let deltas_clone = Rc::clone(&deltas);
+
+    let elem_id = "wheel-deltas";
+    let view: Node<()> = div(
+        vec![
+            id(elem_id),
+            on_wheel(move |event: WheelEvent| {
+                event.prevent_default();
+                *deltas_clone.borrow_mut() = Some((
+                    event.delta_x(),
+                    event.delta_y(),
+                    event.delta_z(),
+                    event.delta_mode(),
+                ));
+            }),
+        ],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view, None)
+        .expect("must not error");
+
+    let mut wheel_init = web_sys::WheelEventInit::new();
+    wheel_init.delta_x(1.0);
+    wheel_init.delta_y(-120.0);
+    wheel_init.delta_z(0.5);
+    wheel_init.delta_mode(web_sys::WheelEvent::DOM_DELTA_LINE);
+    let wheel_event = web_sys::WheelEvent::new_with_event_init_dict("wheel", &wheel_init).unwrap();
+
+    let element: web_sys::EventTarget = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap()
+        .into();
+    element.dispatch_event(&wheel_event).unwrap();
+
+    assert_eq!(
+        *deltas.borrow(),
+        Some((1.0, -120.0, 0.5, web_sys::WheelEvent::DOM_DELTA_LINE))
+    );
+}
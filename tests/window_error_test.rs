@@ -0,0 +1,55 @@
+#![deny(warnings)]
+use sauron::dom::{delay, Window, WindowError};
+use sauron::{html::*, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct ErrorBoundary {
+    errors: Vec<String>,
+}
+
+enum Msg {
+    Failed(WindowError),
+}
+
+impl Application<Msg> for ErrorBoundary {
+    fn init(&mut self) -> Cmd<Self, Msg> {
+        Cmd::from(Window::on_error(Msg::Failed))
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Failed(error) => self.errors.push(error.message),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div([], [text(self.errors.join(","))])
+    }
+}
+
+#[wasm_bindgen_test]
+async fn window_errors_are_delivered_to_the_app() {
+    console_error_panic_hook::set_once();
+    let program = Program::mount_to_body(ErrorBoundary::default());
+
+    let error_init = web_sys::ErrorEventInit::new();
+    error_init.set_message("uncaught boom");
+    let error_event = web_sys::ErrorEvent::new_with_event_init_dict("error", &error_init).unwrap();
+    sauron_core::dom::window()
+        .dispatch_event(&error_event)
+        .unwrap();
+    delay(100).await;
+    assert_eq!(program.app().errors, vec!["uncaught boom".to_string()]);
+
+    // an unhandled rejection
+    let _rejected = js_sys::Promise::reject(&js_sys::Error::new("rejected boom").into());
+    delay(100).await;
+    assert_eq!(
+        program.app().errors,
+        vec!["uncaught boom".to_string(), "rejected boom".to_string()]
+    );
+}